@@ -63,6 +63,14 @@ pub fn generate(args: &GenerateArgs) -> TokenStream {
         }
     });
 
+    let in_memory_ctor = cfg!(feature = "in-memory").then(|| {
+        quote! {
+            pub fn _in_memory() -> Self {
+                Self(#pcr::PrismaClientInternals::new_in_memory(#pcr::ActionNotifier::new(), super::DATAMODEL_STR))
+            }
+        }
+    });
+
     let raw_queries = match args.connector.name() {
         name if psl::builtin_connectors::MONGODB.name() == name => {
             quote! {
@@ -97,13 +105,19 @@ pub fn generate(args: &GenerateArgs) -> TokenStream {
         pub struct PrismaClientBuilder {
             url: Option<String>,
             action_notifier: #pcr::ActionNotifier,
+            middleware: Vec<Box<dyn #pcr::QueryMiddleware>>,
+            connection_options: #pcr::ConnectionOptions,
+            read_replicas: Vec<String>,
         }
 
         impl PrismaClientBuilder {
             fn new() -> Self {
                 Self {
                     url: None,
-                    action_notifier: #pcr::ActionNotifier::new()
+                    action_notifier: #pcr::ActionNotifier::new(),
+                    middleware: vec![],
+                    connection_options: Default::default(),
+                    read_replicas: vec![],
                 }
             }
 
@@ -112,12 +126,53 @@ pub fn generate(args: &GenerateArgs) -> TokenStream {
                 self
             }
 
+            /// Reads made outside of transactions are load balanced across `urls`,
+            /// falling back to the primary database if a replica can't be reached.
+            pub fn with_read_replicas(mut self, urls: Vec<String>) -> Self {
+                self.read_replicas = urls;
+                self
+            }
+
+            /// Middleware runs in the order it is added,
+            /// with the first middleware added being the first to receive each request.
+            pub fn with_middleware(mut self, middleware: impl #pcr::QueryMiddleware) -> Self {
+                self.middleware.push(Box::new(middleware));
+                self
+            }
+
+            /// Maximum number of connections in the pool.
+            pub fn connection_limit(mut self, limit: u32) -> Self {
+                self.connection_options.connection_limit = Some(limit);
+                self
+            }
+
+            /// How long to wait for a connection from the pool before failing.
+            pub fn pool_timeout(mut self, timeout: ::std::time::Duration) -> Self {
+                self.connection_options.pool_timeout = Some(timeout);
+                self
+            }
+
+            /// How long to wait for a new connection to be opened before failing.
+            pub fn connect_timeout(mut self, timeout: ::std::time::Duration) -> Self {
+                self.connection_options.connect_timeout = Some(timeout);
+                self
+            }
+
+            /// How long to wait for a response from the database before failing.
+            pub fn socket_timeout(mut self, timeout: ::std::time::Duration) -> Self {
+                self.connection_options.socket_timeout = Some(timeout);
+                self
+            }
+
             #callback_fn
 
             pub async fn build(self) -> Result<PrismaClient, #pcr::NewClientError> {
                 let internals = #pcr::PrismaClientInternals::new(
                     self.url,
                     self.action_notifier,
+                    self.middleware,
+                    self.connection_options,
+                    self.read_replicas,
                     super::DATAMODEL_STR
                 ).await?;
 
@@ -141,10 +196,41 @@ pub fn generate(args: &GenerateArgs) -> TokenStream {
 
             #mock_ctor
 
+            #in_memory_ctor
+
             #raw_queries
 
             pub async fn _batch<'batch, T: #pcr::BatchContainer<'batch, Marker>, Marker>(&self, queries: T) -> #pcr::Result<<T as #pcr::BatchContainer<'batch, Marker>>::ReturnType> {
-                #pcr::batch(queries, &self.0).await
+                #pcr::batch(queries, &self.0, #pcr::BatchMode::Transactional).await
+            }
+
+            pub async fn _batch_with_mode<'batch, T: #pcr::BatchContainer<'batch, Marker>, Marker>(&self, queries: T, mode: #pcr::BatchMode) -> #pcr::Result<<T as #pcr::BatchContainer<'batch, Marker>>::ReturnType> {
+                #pcr::batch(queries, &self.0, mode).await
+            }
+
+            pub async fn _batch_settled<'batch, T: #pcr::BatchContainer<'batch, Marker>, Marker>(&self, queries: T, mode: #pcr::BatchMode) -> #pcr::Result<<T as #pcr::BatchContainer<'batch, Marker>>::SettledReturnType> {
+                #pcr::batch_settled(queries, &self.0, mode).await
+            }
+
+            /// Waits for running queries to finish and closes the client's connections.
+            /// Queries made afterwards fail with `QueryError::Disconnected`.
+            pub async fn _disconnect(&self) {
+                self.0.disconnect().await
+            }
+
+            /// Checks that the database is reachable, returning how long it took to get a connection.
+            pub async fn _health_check(&self) -> #pcr::Result<#pcr::HealthCheck> {
+                self.0.health_check().await
+            }
+
+            /// A client that sends every query to the primary database, including reads.
+            pub fn _primary(&self) -> Self {
+                Self(self.0.use_primary())
+            }
+
+            /// The connection options in effect, including those set in the datasource URL.
+            pub fn _connection_options(&self) -> #pcr::ConnectionOptions {
+                self.0.connection_options()
             }
 
             pub fn _transaction(&self) -> #pcr::TransactionBuilder<Self> {
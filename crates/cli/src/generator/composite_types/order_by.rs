@@ -115,7 +115,7 @@ pub fn enum_definition(comp_type: CompositeTypeWalker, args: &GenerateArgs) -> T
 
             (
                 quote! {
-                    #[derive(Clone)]
+                    #[derive(Debug, Clone)]
                     pub enum OrderByParam {
                         #(#variants),*
                     }
@@ -129,6 +129,12 @@ pub fn enum_definition(comp_type: CompositeTypeWalker, args: &GenerateArgs) -> T
                             (k.to_string(), v)
                         }
                     }
+
+                    impl ::serde::Serialize for OrderByParam {
+                        fn serialize<S: ::serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                            #pcr::serde::params::serialize_param(self.clone().into(), serializer)
+                        }
+                    }
                 },
                 field_stuff,
             )
@@ -136,7 +142,7 @@ pub fn enum_definition(comp_type: CompositeTypeWalker, args: &GenerateArgs) -> T
         .unwrap_or_default();
 
     quote! {
-        #[derive(Clone)]
+        #[derive(Debug, Clone)]
         pub enum OrderByParam {
             #(#variants),*
         }
@@ -150,5 +156,11 @@ pub fn enum_definition(comp_type: CompositeTypeWalker, args: &GenerateArgs) -> T
                 (k.to_string(), v)
             }
         }
+
+        impl ::serde::Serialize for OrderByParam {
+            fn serialize<S: ::serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                #pcr::serde::params::serialize_param(self.clone().into(), serializer)
+            }
+        }
     }
 }
@@ -43,7 +43,7 @@ pub fn module_part(comp_type: CompositeTypeWalker) -> CompositeTypeModulePart {
 
     CompositeTypeModulePart {
         data: quote! {
-            #[derive(Clone)]
+            #[derive(Debug, Clone)]
             pub enum WhereParam {
                 #(#variants),*
             }
@@ -57,6 +57,12 @@ pub fn module_part(comp_type: CompositeTypeWalker) -> CompositeTypeModulePart {
                     #pcr::SerializedWhereInput::new(name.to_string(), #pcr::SerializedWhereValue::Value(value))
                 }
             }
+
+            impl ::serde::Serialize for WhereParam {
+                fn serialize<S: ::serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                    #pcr::serde::params::serialize_param(#pcr::WhereInput::serialize(self.clone()).into(), serializer)
+                }
+            }
         },
         fields,
     }
@@ -154,6 +154,13 @@ pub fn struct_definition(model: ModelWalker, args: &GenerateArgs) -> TokenStream
                 )
             }
 
+            pub fn find_unique_or_throw(self, _where: UniqueWhereParam) -> FindUniqueOrThrowQuery<'a> {
+                FindUniqueOrThrowQuery::new(
+                    self.client,
+                    _where.into()
+                )
+            }
+
             pub fn find_first(self, _where: Vec<WhereParam>) -> FindFirstQuery<'a> {
                 FindFirstQuery::new(
                     self.client,
@@ -161,6 +168,13 @@ pub fn struct_definition(model: ModelWalker, args: &GenerateArgs) -> TokenStream
                 )
             }
 
+            pub fn find_first_or_throw(self, _where: Vec<WhereParam>) -> FindFirstOrThrowQuery<'a> {
+                FindFirstOrThrowQuery::new(
+                    self.client,
+                    _where
+                )
+            }
+
             pub fn find_many(self, _where: Vec<WhereParam>) -> FindManyQuery<'a> {
                 FindManyQuery::new(
                     self.client,
@@ -223,6 +237,22 @@ pub fn struct_definition(model: ModelWalker, args: &GenerateArgs) -> TokenStream
                 )
             }
 
+            pub fn aggregate(self, _where: Vec<WhereParam>, _params: Vec<AggregateParam>) -> AggregateQuery<'a> {
+                AggregateQuery::new(
+                    self.client,
+                    _where,
+                    _params
+                )
+            }
+
+            pub fn group_by(self, _by: Vec<ScalarField>, _where: Vec<WhereParam>) -> GroupByQuery<'a> {
+                GroupByQuery::new(
+                    self.client,
+                    _by,
+                    _where
+                )
+            }
+
             #monogo_raw_fns
         }
     }
@@ -0,0 +1,260 @@
+use prisma_client_rust_sdk::prisma::{
+    prisma_models::{
+        walkers::{ModelWalker, ScalarFieldWalker},
+        FieldArity,
+    },
+    psl::parser_database::{ScalarFieldType, ScalarType},
+};
+
+use crate::generator::prelude::*;
+
+use super::ModelModulePart;
+
+#[derive(Clone, Copy)]
+enum AggregateKind {
+    Count,
+    Avg,
+    Sum,
+    Min,
+    Max,
+}
+
+impl AggregateKind {
+    fn snake(&self) -> Ident {
+        format_ident!(
+            "{}",
+            match self {
+                Self::Count => "count",
+                Self::Avg => "avg",
+                Self::Sum => "sum",
+                Self::Min => "min",
+                Self::Max => "max",
+            }
+        )
+    }
+
+    fn pascal(&self) -> Ident {
+        pascal_ident(&self.snake().to_string())
+    }
+
+    fn data_struct(&self) -> Ident {
+        format_ident!("Aggregate{}Data", self.pascal())
+    }
+
+    /// Type of the aggregated value for the field,
+    /// or `None` if Prisma doesn't support the aggregate for it.
+    fn value_type(&self, field: ScalarFieldWalker) -> Option<TokenStream> {
+        let typ = field.scalar_field_type();
+
+        if matches!(
+            typ,
+            ScalarFieldType::CompositeType(_) | ScalarFieldType::Unsupported(_)
+        ) {
+            return None;
+        }
+
+        if let Self::Count = self {
+            return Some(quote!(i64));
+        }
+
+        if field.ast_field().arity.is_list() {
+            return None;
+        }
+
+        match (self, typ) {
+            (
+                Self::Avg,
+                ScalarFieldType::BuiltInScalar(
+                    ScalarType::Int | ScalarType::BigInt | ScalarType::Float,
+                ),
+            ) => Some(quote!(f64)),
+            // Averages of decimals are decimals, so they keep their precision
+            (Self::Avg, ScalarFieldType::BuiltInScalar(ScalarType::Decimal)) => {
+                typ.to_tokens(&quote!(super::), &FieldArity::Required, field.db)
+            }
+            (
+                Self::Sum,
+                ScalarFieldType::BuiltInScalar(
+                    ScalarType::Int | ScalarType::BigInt | ScalarType::Float | ScalarType::Decimal,
+                ),
+            ) => typ.to_tokens(&quote!(super::), &FieldArity::Required, field.db),
+            (
+                Self::Min | Self::Max,
+                ScalarFieldType::BuiltInScalar(ScalarType::Json | ScalarType::Bytes),
+            ) => None,
+            (Self::Min | Self::Max, _) => {
+                typ.to_tokens(&quote!(super::), &FieldArity::Required, field.db)
+            }
+            _ => None,
+        }
+    }
+}
+
+static KINDS: &[AggregateKind] = &[
+    AggregateKind::Count,
+    AggregateKind::Avg,
+    AggregateKind::Sum,
+    AggregateKind::Min,
+    AggregateKind::Max,
+];
+
+pub fn model_data(model: ModelWalker) -> ModelModulePart {
+    let pcr = quote!(::prisma_client_rust);
+
+    let model_name_pascal_str = pascal_ident(model.name()).to_string();
+
+    let specta_derive = |name: String| {
+        cfg!(feature = "specta").then(|| {
+            quote! {
+                #[derive(#pcr::specta::Type)]
+                #[specta(rename = #name, crate = "prisma_client_rust::specta")]
+            }
+        })
+    };
+
+    let data_structs = KINDS.iter().map(|kind| {
+        let struct_name = kind.data_struct();
+
+        let fields = model.scalar_fields().filter_map(|field| {
+            let field_name_str = field.name();
+            let field_name_snake = snake_ident(field_name_str);
+
+            let typ = kind.value_type(field)?;
+
+            Some(quote! {
+                #[serde(rename = #field_name_str, default, skip_serializing_if = "Option::is_none")]
+                pub #field_name_snake: Option<#typ>
+            })
+        });
+
+        let count_all = matches!(kind, AggregateKind::Count).then(|| {
+            quote! {
+                #[serde(rename = "_all", default, skip_serializing_if = "Option::is_none")]
+                pub _all: Option<i64>,
+            }
+        });
+
+        let specta_derive = specta_derive(format!("{model_name_pascal_str}{struct_name}"));
+
+        quote! {
+            #[derive(Debug, Clone, Default, ::serde::Serialize, ::serde::Deserialize)]
+            #specta_derive
+            pub struct #struct_name {
+                #count_all
+                #(#fields),*
+            }
+        }
+    });
+
+    let data_struct_fields = KINDS.iter().map(|kind| {
+        let field_name_str = format!("_{}", kind.snake());
+        let field_name = format_ident!("{field_name_str}");
+        let struct_name = kind.data_struct();
+
+        quote! {
+            #[serde(rename = #field_name_str, default, skip_serializing_if = "Option::is_none")]
+            pub #field_name: Option<#struct_name>
+        }
+    });
+
+    let param_variants = KINDS.iter().map(|kind| {
+        let variant = kind.pascal();
+        quote!(#variant(&'static str))
+    });
+
+    let param_arms = KINDS.iter().map(|kind| {
+        let variant = kind.pascal();
+        let aggregate_str = format!("_{}", kind.snake());
+
+        quote!(Self::#variant(field) => (#aggregate_str, *field))
+    });
+
+    let data_specta_derive = specta_derive(format!("{model_name_pascal_str}AggregateData"));
+
+    // Ordering and having filters don't accept `_all`, but counting a required
+    // field gives the same result. Prisma requires every model to have a unique
+    // criteria of only required fields, so the fallback shouldn't be reached.
+    let count_all_field = model
+        .primary_key()
+        .and_then(|primary_key| primary_key.fields().next())
+        .or_else(|| {
+            model.scalar_fields().find(|field| {
+                field.ast_field().arity.is_required()
+                    && AggregateKind::Count.value_type(*field).is_some()
+            })
+        })
+        .map(|field| field.name())
+        .unwrap_or("_all");
+
+    let fields = model
+        .scalar_fields()
+        .filter_map(|field| {
+            let fns = KINDS
+                .iter()
+                .filter(|kind| kind.value_type(field).is_some())
+                .map(|kind| {
+                    let fn_name = kind.snake();
+                    let variant = kind.pascal();
+
+                    quote! {
+                        pub fn #fn_name() -> super::AggregateParam {
+                            super::AggregateParam::#variant(NAME)
+                        }
+                    }
+                })
+                .collect::<Vec<_>>();
+
+            (!fns.is_empty()).then(|| (field.name().to_string(), quote!(#(#fns)*)))
+        })
+        .collect();
+
+    ModelModulePart {
+        data: quote! {
+            #[derive(Debug, Clone)]
+            pub enum AggregateParam {
+                CountAll,
+                #(#param_variants),*
+            }
+
+            impl AggregateParam {
+                /// The aggregate (eg. `_avg`) and field the param selects
+                fn parts(&self) -> (&'static str, &'static str) {
+                    match self {
+                        Self::CountAll => ("_count", "_all"),
+                        #(#param_arms),*
+                    }
+                }
+
+                /// Like `parts`, but with `CountAll` counting a required field
+                /// since ordering and having filters don't accept `_all`
+                fn filter_parts(&self) -> (&'static str, &'static str) {
+                    match self {
+                        Self::CountAll => ("_count", #count_all_field),
+                        _ => self.parts(),
+                    }
+                }
+            }
+
+            impl Into<(String, #pcr::Selection)> for AggregateParam {
+                fn into(self) -> (String, #pcr::Selection) {
+                    let (aggregate, field) = self.parts();
+
+                    (aggregate.to_string(), #pcr::sel(field))
+                }
+            }
+
+            pub fn count_all() -> AggregateParam {
+                AggregateParam::CountAll
+            }
+
+            #(#data_structs)*
+
+            #[derive(Debug, Clone, Default, ::serde::Serialize, ::serde::Deserialize)]
+            #data_specta_derive
+            pub struct AggregateData {
+                #(#data_struct_fields),*
+            }
+        },
+        fields,
+    }
+}
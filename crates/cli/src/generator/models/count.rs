@@ -0,0 +1,157 @@
+use prisma_client_rust_sdk::prisma::prisma_models::walkers::{ModelWalker, RelationFieldWalker};
+
+use crate::generator::prelude::*;
+
+/// Relation fields that Prisma can count via the `_count` pseudo-field.
+pub fn relation_fields<'a>(
+    model: ModelWalker<'a>,
+) -> impl Iterator<Item = RelationFieldWalker<'a>> + Clone {
+    model
+        .relation_fields()
+        .filter(|f| f.ast_field().arity.is_list())
+}
+
+pub fn has_relation_count(model: ModelWalker) -> bool {
+    relation_fields(model).next().is_some()
+}
+
+/// `_count` module containing the typed counts struct and params
+/// for selecting relation counts through `with`, `select!` and `include!`.
+pub fn module(model: ModelWalker) -> Option<TokenStream> {
+    if !has_relation_count(model) {
+        return None;
+    }
+
+    let pcr = quote!(::prisma_client_rust);
+
+    let model_name_pascal_str = pascal_ident(model.name()).to_string();
+
+    let data_fields = relation_fields(model).map(|field| {
+        let field_name_str = field.name();
+        let field_name_snake = snake_ident(field_name_str);
+
+        quote! {
+            #[serde(rename = #field_name_str, default, skip_serializing_if = "Option::is_none")]
+            pub #field_name_snake: Option<i64>
+        }
+    });
+
+    let param_variants = relation_fields(model).map(|field| {
+        let field_name_pascal = pascal_ident(field.name());
+        let relation_model_name_snake = snake_ident(field.related_model().name());
+
+        quote!(#field_name_pascal(Vec<#relation_model_name_snake::WhereParam>))
+    });
+
+    let param_arms = relation_fields(model).map(|field| {
+        let field_name_snake = snake_ident(field.name());
+        let field_name_pascal = pascal_ident(field.name());
+
+        quote!(Self::#field_name_pascal(where_params) => (super::#field_name_snake::NAME, where_params))
+    });
+
+    let param_fns = relation_fields(model).map(|field| {
+        let field_name_snake = snake_ident(field.name());
+        let field_name_pascal = pascal_ident(field.name());
+        let relation_model_name_snake = snake_ident(field.related_model().name());
+
+        quote! {
+            pub fn #field_name_snake(where_params: Vec<#relation_model_name_snake::WhereParam>) -> Param {
+                Param::#field_name_pascal(where_params)
+            }
+        }
+    });
+
+    let specta_derive = cfg!(feature = "specta").then(|| {
+        let specta_name = format!("{model_name_pascal_str}RelationCountData");
+
+        quote! {
+            #[derive(#pcr::specta::Type)]
+            #[specta(rename = #specta_name, crate = "prisma_client_rust::specta")]
+        }
+    });
+
+    Some(quote! {
+        pub mod _count {
+            use super::super::{_prisma::*, *};
+
+            pub const NAME: &str = "_count";
+
+            #[derive(Debug, Clone, Default, ::serde::Serialize, ::serde::Deserialize)]
+            #specta_derive
+            pub struct Data {
+                #(#data_fields),*
+            }
+
+            #[derive(Debug, Clone)]
+            pub enum Param {
+                #(#param_variants),*
+            }
+
+            impl Param {
+                pub fn to_selection(self) -> #pcr::Selection {
+                    let (name, where_params) = match self {
+                        #(#param_arms),*
+                    };
+
+                    let arguments = (!where_params.is_empty())
+                        .then(|| {
+                            (
+                                "where".to_string(),
+                                #pcr::PrismaValue::Object(#pcr::merge_fields(
+                                    where_params
+                                        .into_iter()
+                                        .map(#pcr::WhereInput::serialize)
+                                        .map(Into::into)
+                                        .collect(),
+                                ))
+                                .into(),
+                            )
+                        })
+                        .into_iter()
+                        .collect::<Vec<(String, #pcr::query_core::ArgumentValue)>>();
+
+                    #pcr::Selection::new(name, None, arguments, [])
+                }
+            }
+
+            #(#param_fns)*
+
+            #[derive(Debug, Clone)]
+            pub struct Fetch(pub Vec<Param>);
+
+            impl Fetch {
+                pub fn to_selection(self) -> #pcr::Selection {
+                    #pcr::Selection::new(
+                        NAME,
+                        None,
+                        [],
+                        self.0.into_iter().map(Param::to_selection).collect::<Vec<_>>(),
+                    )
+                }
+            }
+
+            impl From<Fetch> for super::WithParam {
+                fn from(fetch: Fetch) -> Self {
+                    super::WithParam::_Count(fetch)
+                }
+            }
+
+            impl From<Fetch> for super::SelectParam {
+                fn from(fetch: Fetch) -> Self {
+                    super::SelectParam::_Count(fetch)
+                }
+            }
+
+            impl From<Fetch> for super::IncludeParam {
+                fn from(fetch: Fetch) -> Self {
+                    super::IncludeParam::_Count(fetch)
+                }
+            }
+
+            pub fn fetch(params: Vec<Param>) -> Fetch {
+                Fetch(params)
+            }
+        }
+    })
+}
@@ -134,6 +134,26 @@ pub fn r#struct(model: ModelWalker) -> TokenStream {
             _ => None,
         });
 
+    let has_relation_count = super::count::has_relation_count(model);
+
+    let count_field = has_relation_count.then(|| {
+        let specta_attrs = cfg!(feature = "specta").then(|| quote!(#[specta(skip)]));
+
+        quote! {
+            #[serde(rename = "_count", default, skip_serializing_if = "Option::is_none")]
+            #specta_attrs
+            pub _count: Option<_count::Data>,
+        }
+    });
+
+    let count_accessor = has_relation_count.then(|| {
+        quote! {
+            pub fn _count(&self) -> Result<&_count::Data, #pcr::RelationNotFetchedError> {
+                self._count.as_ref().ok_or(#pcr::RelationNotFetchedError::new("_count"))
+            }
+        }
+    });
+
     let specta_derive = cfg!(feature = "specta").then(|| {
         let model_name_pascal_str = pascal_ident(model.name()).to_string();
 
@@ -147,11 +167,13 @@ pub fn r#struct(model: ModelWalker) -> TokenStream {
         #[derive(Debug, Clone, ::serde::Serialize, ::serde::Deserialize)]
         #specta_derive
         pub struct Data {
+            #count_field
             #(#struct_fields),*
         }
 
         impl Data {
             #(#relation_accessors)*
+            #count_accessor
         }
     }
 }
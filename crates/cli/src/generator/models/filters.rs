@@ -0,0 +1,193 @@
+use prisma_client_rust_sdk::prisma::{
+    dmmf::TypeLocation,
+    prisma_models::{
+        walkers::{ModelWalker, RefinedFieldWalker},
+        FieldArity,
+    },
+    psl::parser_database::ScalarFieldType,
+};
+
+use crate::generator::prelude::*;
+
+use super::ModelModulePart;
+
+/// Implements `FromJsonEntry` for the model's where and order by params,
+/// so that they can be parsed from untrusted JSON.
+pub fn model_data(model: ModelWalker, args: &GenerateArgs) -> ModelModulePart {
+    let data = cfg!(feature = "serde-filters").then(|| {
+        let where_param = where_param(model, args);
+
+        let order_by_params = [
+            ("OrderByWithRelationInput", "OrderByWithRelationParam"),
+            (
+                "OrderByRelationAggregateInput",
+                "OrderByRelationAggregateParam",
+            ),
+        ]
+        .iter()
+        .filter_map(|(input_type, param)| {
+            order_by_param(
+                &format!("{}{input_type}", model.name()),
+                &format_ident!("{param}"),
+                args,
+            )
+        });
+
+        quote! {
+            #where_param
+            #(#order_by_params)*
+        }
+    });
+
+    ModelModulePart {
+        data: data.unwrap_or_default(),
+        fields: Default::default(),
+    }
+}
+
+fn where_param(model: ModelWalker, args: &GenerateArgs) -> TokenStream {
+    let pcr = quote!(::prisma_client_rust);
+
+    let field_arms = model
+        .fields()
+        .filter(|f| f.ast_field().field_type.as_unsupported().is_none())
+        .filter_map(|field| {
+            let field_name = field.name();
+            let field_name_pascal = pascal_ident(field_name);
+
+            match field.refine() {
+                RefinedFieldWalker::Relation(relation_field) => {
+                    let relation_model_name_snake =
+                        snake_ident(relation_field.related_model().name());
+
+                    let method_arms = field.relation_methods().iter().map(|method| {
+                        let method_action_string = method.to_case(Case::Camel, false);
+                        let variant_name =
+                            format_ident!("{}{}", &field_name_pascal, pascal_ident(method));
+
+                        quote! {
+                            #method_action_string => params.push(Self::#variant_name(
+                                #pcr::filters::from_json::<super::#relation_model_name_snake::WhereParam>(value)?
+                            ))
+                        }
+                    });
+
+                    let is_null_arm = field.ast_field().arity.is_optional().then(|| {
+                        let is_null_variant = format_ident!("{field_name_pascal}IsNull");
+
+                        quote!(#pcr::serde_json::Value::Null => params.push(Self::#is_null_variant),)
+                    });
+
+                    Some(quote! {
+                        #field_name => match value {
+                            #is_null_arm
+                            value => for (filter, value) in #pcr::filters::relation_filters(key, value)? {
+                                match filter.as_str() {
+                                    #(#method_arms,)*
+                                    _ => return Err(#pcr::filters::unknown_filter(key, &filter)),
+                                }
+                            }
+                        }
+                    })
+                }
+                RefinedFieldWalker::Scalar(scalar_field) => match scalar_field.scalar_field_type() {
+                    // Composite type filters aren't supported
+                    ScalarFieldType::CompositeType(_) => None,
+                    _ => args.read_filter(scalar_field).map(|read_filter| {
+                        let filter_enum = format_ident!("{}Filter", &read_filter.name);
+
+                        quote! {
+                            #field_name => params.extend(
+                                #pcr::filters::scalar_filters::<super::_prisma::read_filters::#filter_enum>(key, value)?
+                                    .into_iter()
+                                    .map(Self::#field_name_pascal)
+                            )
+                        }
+                    }),
+                },
+            }
+        });
+
+    quote! {
+        impl #pcr::filters::FromJsonEntry for WhereParam {
+            const MODEL: &'static str = NAME;
+
+            fn from_entry(
+                key: &str,
+                value: #pcr::serde_json::Value,
+                params: &mut Vec<Self>
+            ) -> Result<(), #pcr::filters::FilterError> {
+                match key {
+                    "AND" => for value in #pcr::filters::one_or_many(value) {
+                        params.push(Self::And(#pcr::filters::from_json(value)?));
+                    },
+                    "OR" => params.push(Self::Or(
+                        #pcr::filters::one_or_many(value)
+                            .into_iter()
+                            .map(|value| #pcr::filters::from_json(value).map(Self::And))
+                            .collect::<Result<_, _>>()?
+                    )),
+                    "NOT" => for value in #pcr::filters::one_or_many(value) {
+                        params.push(Self::Not(#pcr::filters::from_json(value)?));
+                    },
+                    #(#field_arms,)*
+                    _ => return Err(#pcr::filters::unknown_field(NAME, key)),
+                }
+
+                Ok(())
+            }
+        }
+    }
+}
+
+fn order_by_param(
+    input_type_name: &str,
+    param: &Ident,
+    args: &GenerateArgs,
+) -> Option<TokenStream> {
+    let pcr = quote!(::prisma_client_rust);
+
+    let input_type = args.dmmf.schema.find_input_type(input_type_name)?;
+
+    let arms = input_type.fields.iter().filter_map(|field| {
+        let field_name_str = &field.name;
+        let field_name_pascal = pascal_ident(&field.name);
+
+        let typ_ref = &field.input_types[0];
+
+        // Only fields that have a variant in the param enum
+        typ_ref.to_tokens(&quote!(super::), &FieldArity::Required, &args.schema.db)?;
+
+        let value = match &typ_ref.location {
+            TypeLocation::EnumTypes | TypeLocation::Scalar => {
+                quote!(#pcr::filters::value(key, value)?)
+            }
+            // Composite type order by params aren't supported
+            TypeLocation::InputObjectTypes if !typ_ref.typ.ends_with("OrderByInput") => {
+                quote!(#pcr::filters::from_json(value)?)
+            }
+            _ => return None,
+        };
+
+        Some(quote!(#field_name_str => Self::#field_name_pascal(#value)))
+    });
+
+    Some(quote! {
+        impl #pcr::filters::FromJsonEntry for #param {
+            const MODEL: &'static str = NAME;
+
+            fn from_entry(
+                key: &str,
+                value: #pcr::serde_json::Value,
+                params: &mut Vec<Self>
+            ) -> Result<(), #pcr::filters::FilterError> {
+                params.push(match key {
+                    #(#arms,)*
+                    _ => return Err(#pcr::filters::unknown_field(NAME, key)),
+                });
+
+                Ok(())
+            }
+        }
+    })
+}
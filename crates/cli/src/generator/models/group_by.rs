@@ -0,0 +1,222 @@
+use prisma_client_rust_sdk::prisma::{
+    prisma_models::{
+        walkers::{ModelWalker, ScalarFieldWalker},
+        FieldArity,
+    },
+    psl::parser_database::ScalarFieldType,
+};
+
+use crate::generator::prelude::*;
+
+/// Scalar fields that can be used as `by` arguments and returned in groups
+fn groupable_fields<'a>(model: ModelWalker<'a>) -> impl Iterator<Item = ScalarFieldWalker<'a>> {
+    model.scalar_fields().filter(|field| {
+        !matches!(
+            field.scalar_field_type(),
+            ScalarFieldType::CompositeType(_) | ScalarFieldType::Unsupported(_)
+        )
+    })
+}
+
+pub fn scalar_field_enum(model: ModelWalker) -> TokenStream {
+    let (variants, names): (Vec<_>, Vec<_>) = groupable_fields(model)
+        .map(|field| (pascal_ident(field.name()), field.name()))
+        .unzip();
+
+    quote! {
+        #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+        pub enum ScalarField {
+            #(#variants),*
+        }
+
+        impl ScalarField {
+            pub fn order(self, order: SortOrder) -> OrderByWithAggregationParam {
+                OrderByWithAggregationParam::Field(self, order)
+            }
+        }
+
+        impl Into<String> for ScalarField {
+            fn into(self) -> String {
+                match self {
+                    #(Self::#variants => #names),*
+                }
+                .to_string()
+            }
+        }
+    }
+}
+
+pub fn types(model: ModelWalker) -> TokenStream {
+    let pcr = quote!(::prisma_client_rust);
+
+    let data_fields = groupable_fields(model).flat_map(|field| {
+        let field_name_str = field.name();
+        let field_name_snake = snake_ident(field_name_str);
+
+        let arity = match field.ast_field().arity {
+            FieldArity::List => FieldArity::List,
+            _ => FieldArity::Required,
+        };
+
+        let typ = field
+            .scalar_field_type()
+            .to_tokens(&quote!(super::), &arity, field.db)?;
+
+        Some(quote! {
+            #[serde(rename = #field_name_str, default, skip_serializing_if = "Option::is_none")]
+            pub #field_name_snake: Option<#typ>
+        })
+    });
+
+    let specta_derive = cfg!(feature = "specta").then(|| {
+        let name = format!("{}GroupByData", pascal_ident(model.name()));
+
+        quote! {
+            #[derive(#pcr::specta::Type)]
+            #[specta(rename = #name, crate = "prisma_client_rust::specta")]
+        }
+    });
+
+    quote! {
+        #[derive(Debug, Clone)]
+        pub enum OrderByWithAggregationParam {
+            Field(ScalarField, SortOrder),
+            Aggregate(AggregateParam, SortOrder),
+        }
+
+        impl Into<(String, #pcr::PrismaValue)> for OrderByWithAggregationParam {
+            fn into(self) -> (String, #pcr::PrismaValue) {
+                match self {
+                    Self::Field(field, order) => (field.into(), order.into()),
+                    Self::Aggregate(param, order) => {
+                        let (aggregate, field) = param.filter_parts();
+
+                        (
+                            aggregate.to_string(),
+                            #pcr::PrismaValue::Object(vec![(field.to_string(), order.into())])
+                        )
+                    }
+                }
+            }
+        }
+
+        impl ::serde::Serialize for OrderByWithAggregationParam {
+            fn serialize<S: ::serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                #pcr::serde::params::serialize_param(self.clone().into(), serializer)
+            }
+        }
+
+        #[derive(Debug, Clone)]
+        pub enum HavingParam {
+            Not(Vec<HavingParam>),
+            Or(Vec<HavingParam>),
+            And(Vec<HavingParam>),
+            /// Only scalar field filters are supported,
+            /// relation filters fail when the query is executed.
+            Where(WhereParam),
+            Aggregate(AggregateParam, #pcr::PrismaValue),
+        }
+
+        impl #pcr::WhereInput for HavingParam {
+            fn serialize(self) -> #pcr::SerializedWhereInput {
+                let (name, value) = match self {
+                    Self::Not(value) => (
+                        "NOT",
+                        #pcr::SerializedWhereValue::Object(
+                            #pcr::merge_fields(
+                                value
+                                    .into_iter()
+                                    .map(#pcr::WhereInput::serialize)
+                                    .map(Into::into)
+                                    .collect()
+                            )
+                        ),
+                    ),
+                    Self::Or(value) => (
+                        "OR",
+                        #pcr::SerializedWhereValue::List(
+                            value
+                                .into_iter()
+                                .map(#pcr::WhereInput::serialize)
+                                .map(|p| #pcr::PrismaValue::Object(vec![p.into()]))
+                                .collect()
+                        ),
+                    ),
+                    Self::And(value) => (
+                        "AND",
+                        #pcr::SerializedWhereValue::Object(
+                            #pcr::merge_fields(
+                                value
+                                    .into_iter()
+                                    .map(#pcr::WhereInput::serialize)
+                                    .map(Into::into)
+                                    .collect()
+                            )
+                        ),
+                    ),
+                    Self::Where(param) => return #pcr::WhereInput::serialize(param),
+                    Self::Aggregate(param, value) => {
+                        let (aggregate, field) = param.filter_parts();
+
+                        (
+                            field,
+                            #pcr::SerializedWhereValue::Object(vec![(aggregate.to_string(), value)]),
+                        )
+                    }
+                };
+
+                #pcr::SerializedWhereInput::new(name.to_string(), value)
+            }
+        }
+
+        impl ::serde::Serialize for HavingParam {
+            fn serialize<S: ::serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                #pcr::serde::params::serialize_param(#pcr::WhereInput::serialize(self.clone()).into(), serializer)
+            }
+        }
+
+        impl From<WhereParam> for HavingParam {
+            fn from(param: WhereParam) -> Self {
+                Self::Where(param)
+            }
+        }
+
+        impl From<#pcr::Operator<Self>> for HavingParam {
+            fn from(op: #pcr::Operator<Self>) -> Self {
+                match op {
+                    #pcr::Operator::Not(value) => Self::Not(value),
+                    #pcr::Operator::And(value) => Self::And(value),
+                    #pcr::Operator::Or(value) => Self::Or(value),
+                }
+            }
+        }
+
+        impl AggregateParam {
+            pub fn order(self, order: SortOrder) -> OrderByWithAggregationParam {
+                OrderByWithAggregationParam::Aggregate(self, order)
+            }
+
+            /// Filters groups by the value of the aggregate,
+            /// eg. `post::views::avg().having(read_filters::FloatFilter::Gt(5.0))`
+            pub fn having(self, filter: impl Into<#pcr::SerializedWhereValue>) -> HavingParam {
+                HavingParam::Aggregate(self, Into::<#pcr::SerializedWhereValue>::into(filter).into())
+            }
+        }
+
+        #[derive(Debug, Clone, ::serde::Serialize, ::serde::Deserialize)]
+        #specta_derive
+        pub struct GroupByData {
+            #(#data_fields,)*
+            #[serde(rename = "_count", default, skip_serializing_if = "Option::is_none")]
+            pub _count: Option<AggregateCountData>,
+            #[serde(rename = "_avg", default, skip_serializing_if = "Option::is_none")]
+            pub _avg: Option<AggregateAvgData>,
+            #[serde(rename = "_sum", default, skip_serializing_if = "Option::is_none")]
+            pub _sum: Option<AggregateSumData>,
+            #[serde(rename = "_min", default, skip_serializing_if = "Option::is_none")]
+            pub _min: Option<AggregateMinData>,
+            #[serde(rename = "_max", default, skip_serializing_if = "Option::is_none")]
+            pub _max: Option<AggregateMaxData>,
+        }
+    }
+}
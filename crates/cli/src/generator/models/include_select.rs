@@ -62,10 +62,13 @@ fn model_macro<'a>(
     let selections_pattern_produce = quote!(: $selection_mode:ident {$($selections:tt)+});
     let selections_pattern_consume = quote!(: $selection_mode {$($selections)+});
 
-    let selection_pattern_produce =
-        quote!($field:ident $(#filters_pattern_produce)? $(#selections_pattern_produce)?);
-    let selection_pattern_consume =
-        quote!($field $(#filters_pattern_consume)? $(#selections_pattern_consume)?);
+    let counts_pattern_produce = quote!({$($counts:tt)+});
+    let counts_pattern_consume = quote!({$($counts)+});
+
+    let selection_pattern_produce = quote!($field:ident $(#filters_pattern_produce)? $(#selections_pattern_produce)? $(#counts_pattern_produce)?);
+    let selection_pattern_consume = quote!($field $(#filters_pattern_consume)? $(#selections_pattern_consume)? $(#counts_pattern_consume)?);
+
+    let has_relation_count = super::count::has_relation_count(model);
 
     let field_type_impls = selection_fields.clone().map(|field| {
         let field_name_snake = snake_ident(field.name());
@@ -86,6 +89,9 @@ fn model_macro<'a>(
         }
     });
 
+    let count_field_type_impl = has_relation_count
+        .then(|| quote!((@field_type; _count #counts_pattern_produce) => { _count::Data };));
+
     let field_module_impls = model.relation_fields().map(|field| {
         let field_name_snake = snake_ident(field.name());
         let relation_model_name_snake = snake_ident(field.related_model().name());
@@ -171,6 +177,80 @@ fn model_macro<'a>(
         }
     });
 
+    let count_selection_to_selection_param_impl = has_relation_count.then(|| {
+        quote! {
+            (@selection_field_to_selection_param; _count { $($count_field:ident $(($($count_filters:tt)+))?)+ }) => {{
+                Into::<#model_module::#selection_param>::into(
+                    #model_module::_count::fetch(vec![$(
+                        #model_module::_count::$count_field(#model_module::#variant_ident!(
+                            @filters_to_args;
+                            $($($count_filters)+)?
+                        ))
+                    ),+])
+                )
+            }};
+        }
+    });
+
+    let count_module_impl = has_relation_count.then(|| {
+        let specta_attrs = cfg!(feature = "specta").then(|| {
+            quote! {
+                #[derive(::prisma_client_rust::specta::Type)]
+                #[specta(crate = "prisma_client_rust::specta")]
+                #[specta(inline)]
+            }
+        });
+
+        let specta_rename = cfg!(feature = "specta").then(|| {
+            quote!(#[specta(rename_from_path = #model_module::$count_field::NAME)])
+        });
+
+        quote! {
+            (@count_module; $($count_field:ident $(($($count_filters:tt)+))?)+) => {
+                #[allow(warnings)]
+                #[derive(std::fmt::Debug, Clone)]
+                #specta_attrs
+                pub struct Data {
+                    $(
+                        #specta_rename
+                        pub $count_field: i64,
+                    )+
+                }
+
+                impl ::serde::Serialize for Data {
+                    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+                    where
+                        S: ::serde::Serializer,
+                    {
+                        use ::serde::ser::SerializeStruct;
+
+                        let mut state = serializer.serialize_struct(
+                            "Data",
+                            [$(stringify!($count_field)),+].len()
+                        )?;
+                        $(state.serialize_field(#model_module::$count_field::NAME, &self.$count_field)?;)+
+                        state.end()
+                    }
+                }
+
+                impl<'de> ::serde::Deserialize<'de> for Data {
+                    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+                    where
+                        D: ::serde::Deserializer<'de>,
+                    {
+                        let mut counts = <std::collections::HashMap<String, i64> as ::serde::Deserialize>::deserialize(deserializer)?;
+
+                        Ok(Data {
+                            $($count_field: counts.remove(#model_module::$count_field::NAME).ok_or_else(||
+                                ::serde::de::Error::missing_field(#model_module::$count_field::NAME)
+                            )?,)+
+                        })
+                    }
+                }
+            };
+        }
+    });
+
     let data_struct_scalar_fields = base_fields.clone().map(|f| {
         let field_name_snake = snake_ident(f.name());
         let field_type = f.type_tokens(module_path);
@@ -185,10 +265,13 @@ fn model_macro<'a>(
         }
     });
 
-    let fields_enum_variants = selection_fields.clone().map(|f| {
-        let i = snake_ident(f.name());
-        quote!(#i)
-    });
+    let fields_enum_variants = selection_fields
+        .clone()
+        .map(|f| {
+            let i = snake_ident(f.name());
+            quote!(#i)
+        })
+        .chain(has_relation_count.then(|| quote!(_count)));
 
     let field_serde_names = model
         .fields()
@@ -364,7 +447,7 @@ fn model_macro<'a>(
                 #(#data_struct_scalar_fields,)*
                 $(
                     #specta_rename
-                    pub $field: #model_module::#variant_ident!(@field_type; $field $(#selections_pattern_consume)?),
+                    pub $field: #model_module::#variant_ident!(@field_type; $field $(#selections_pattern_consume)? $(#counts_pattern_consume)?),
                 )+
             }
         }
@@ -477,21 +560,29 @@ fn model_macro<'a>(
                 $($(pub mod $field {
                     #model_module::$selection_mode!(@field_module; $field #selections_pattern_consume);
                 })?)+
+
+                $($(pub mod $field {
+                    #model_module::#variant_ident!(@count_module; $($counts)+);
+                })?)+
             };
 
             #(#field_type_impls)*
+            #count_field_type_impl
             (@field_type; $field:ident $($tokens:tt)*) => { compile_error!(stringify!(Cannot include nonexistent relation $field on model #model_name_pascal_str, available relations are #all_fields_str)) };
 
             #(#field_module_impls)*
             (@field_module; $($tokens:tt)*) => {};
 
             #(#selection_field_to_selection_param_impls)*
+            #count_selection_to_selection_param_impl
             (@selection_field_to_selection_param; $($tokens:tt)*) => { compile_error!(stringify!($($tokens)*)) }; // ::prisma_client_rust::Selection::builder("").build() };
 
             (@selections_to_params; : $macro_name:ident {$(#selection_pattern_produce)+}) => {
                 [ $(#module_path::#model_name_snake::$macro_name!(@selection_field_to_selection_param; #selection_pattern_consume),)+]
             };
 
+            #count_module_impl
+
             (@filters_to_args;) => {
                 vec![]
             };
@@ -657,14 +748,20 @@ fn model_module_enum(model: ModelWalker, variant: Variant) -> TokenStream {
 
     let variant_param = variant.param();
 
+    let has_relation_count = super::count::has_relation_count(model);
+    let count_variant = has_relation_count.then(|| quote!(_Count(_count::Fetch),));
+    let count_arm = has_relation_count.then(|| quote!(Self::_Count(data) => data.to_selection(),));
+
     quote! {
         pub enum #variant_param {
+            #count_variant
             #(#variants),*
         }
 
         impl #variant_param {
             pub fn to_selection(self) -> #pcr::Selection {
                 match self {
+                    #count_arm
                     #(Self::#field_names_pascal(data) => data.to_selection()),*
                 }
             }
@@ -1,6 +1,10 @@
 mod actions;
+mod aggregate;
+mod count;
 mod create;
 mod data;
+mod filters;
+mod group_by;
 mod include_select;
 mod order_by;
 mod pagination;
@@ -94,11 +98,16 @@ pub fn modules(args: &GenerateArgs, module_path: &TokenStream) -> Vec<TokenStrea
                 set_params::model_data(model, args),
                 select::model_data(model, &module_path),
                 include::model_data(model, &module_path),
+                aggregate::model_data(model),
+                filters::model_data(model, args),
             ]);
 
             let create_types = create::types(model);
             let types_struct = types::r#struct(model, module_path);
             let data_struct = data::r#struct(model);
+            let count_module = count::module(model);
+            let scalar_field_enum = group_by::scalar_field_enum(model);
+            let group_by_types = group_by::types(model);
             let partial_unchecked_macro = partial_unchecked::r#macro(model, &module_path);
 
             let mongo_raw_types = cfg!(feature = "mongodb").then(|| quote! {
@@ -116,18 +125,25 @@ pub fn modules(args: &GenerateArgs, module_path: &TokenStream) -> Vec<TokenStrea
                     #create_types
                     #types_struct
                     #data_struct
+                    #count_module
+                    #scalar_field_enum
+                    #group_by_types
                     #partial_unchecked_macro
 
                     pub type UniqueArgs = #pcr::UniqueArgs<Types>;
                     pub type ManyArgs = #pcr::ManyArgs<Types>;
 
                     pub type CountQuery<'a> = #pcr::Count<'a, Types>;
+                    pub type AggregateQuery<'a> = #pcr::Aggregate<'a, Types>;
+                    pub type GroupByQuery<'a> = #pcr::GroupBy<'a, Types>;
                     pub type CreateQuery<'a> = #pcr::Create<'a, Types>;
                     pub type CreateUncheckedQuery<'a> = #pcr::CreateUnchecked<'a, Types>;
                     pub type CreateManyQuery<'a> = #pcr::CreateMany<'a, Types>;
                     pub type FindUniqueQuery<'a> = #pcr::FindUnique<'a, Types>;
+                    pub type FindUniqueOrThrowQuery<'a> = #pcr::FindUniqueOrThrow<'a, Types>;
                     pub type FindManyQuery<'a> = #pcr::FindMany<'a, Types>;
                     pub type FindFirstQuery<'a> = #pcr::FindFirst<'a, Types>;
+                    pub type FindFirstOrThrowQuery<'a> = #pcr::FindFirstOrThrow<'a, Types>;
                     pub type UpdateQuery<'a> = #pcr::Update<'a, Types>;
                     pub type UpdateUncheckedQuery<'a> = #pcr::UpdateUnchecked<'a, Types>;
                     pub type UpdateManyQuery<'a> = #pcr::UpdateMany<'a, Types>;
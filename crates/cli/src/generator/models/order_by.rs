@@ -66,7 +66,7 @@ pub fn model_data(model: ModelWalker, args: &GenerateArgs) -> ModelModulePart {
 
             (
                 quote! {
-                    #[derive(Clone)]
+                    #[derive(Debug, Clone)]
                     pub enum OrderByRelationAggregateParam {
                         #(#variants),*
                     }
@@ -80,6 +80,12 @@ pub fn model_data(model: ModelWalker, args: &GenerateArgs) -> ModelModulePart {
                             (k.to_string(), v)
                         }
                     }
+
+                    impl ::serde::Serialize for OrderByRelationAggregateParam {
+                        fn serialize<S: ::serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                            #pcr::serde::params::serialize_param(self.clone().into(), serializer)
+                        }
+                    }
                 },
                 field_things,
             )
@@ -148,7 +154,7 @@ pub fn model_data(model: ModelWalker, args: &GenerateArgs) -> ModelModulePart {
 
             (
                 quote! {
-                    #[derive(Clone)]
+                    #[derive(Debug, Clone)]
                     pub enum OrderByWithRelationParam {
                        #(#variants),*
                     }
@@ -162,6 +168,12 @@ pub fn model_data(model: ModelWalker, args: &GenerateArgs) -> ModelModulePart {
                             (k.to_string(), v)
                         }
                     }
+
+                    impl ::serde::Serialize for OrderByWithRelationParam {
+                        fn serialize<S: ::serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                            #pcr::serde::params::serialize_param(self.clone().into(), serializer)
+                        }
+                    }
                 },
                 field_stuff,
             )
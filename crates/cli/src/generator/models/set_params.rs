@@ -8,9 +8,12 @@ use prisma_client_rust_sdk::prisma::{
     psl::parser_database::ScalarFieldType,
 };
 
-use crate::generator::{prelude::*, write_params};
+use crate::generator::{
+    prelude::{prisma::psl::datamodel_connector, *},
+    write_params,
+};
 
-use super::ModelModulePart;
+use super::{required_fields, ModelModulePart};
 
 pub struct RelationSetParamConfig {
     pub action: &'static str,
@@ -55,6 +58,496 @@ fn relation_field_set_params(field: RelationFieldWalker) -> Vec<RelationSetParam
     }
 }
 
+/// Required fields of the related model that must be provided when creating it
+/// through `field`, excluding the back-relation since Prisma populates it.
+fn nested_create_fields(
+    field: RelationFieldWalker,
+) -> Option<Vec<(Ident, TokenStream, TokenStream)>> {
+    let opposite_field = field.opposite_relation_field().map(|f| f.name());
+
+    required_fields(field.related_model())?
+        .into_iter()
+        .filter(|f| Some(f.inner.name()) != opposite_field)
+        .map(|f| {
+            let typ = match f.inner.refine() {
+                RefinedFieldWalker::Scalar(scalar_field) => {
+                    match scalar_field.scalar_field_type() {
+                        ScalarFieldType::CompositeType(id) => {
+                            let comp_type_snake = snake_ident(f.inner.db.walk(id).name());
+
+                            quote!(#comp_type_snake::Create)
+                        }
+                        _ => f.inner.type_tokens(&quote!())?,
+                    }
+                }
+                RefinedFieldWalker::Relation(relation_field) => {
+                    let relation_model_name_snake =
+                        snake_ident(relation_field.related_model().name());
+
+                    quote!(#relation_model_name_snake::UniqueWhereParam)
+                }
+            };
+
+            Some((snake_ident(f.inner.name()), typ, f.push_wrapper))
+        })
+        .collect()
+}
+
+/// Required scalar fields of the related model for a nested `createMany`,
+/// excluding the foreign keys that point back to the parent record.
+fn nested_create_many_fields(field: RelationFieldWalker) -> Option<Vec<(Ident, TokenStream)>> {
+    let opposite_field = field.opposite_relation_field()?;
+
+    let foreign_keys = opposite_field
+        .fields()
+        .map(|fields| fields.map(|f| f.name()).collect::<Vec<_>>())
+        .unwrap_or_default();
+
+    field
+        .related_model()
+        .scalar_fields()
+        .filter(|f| f.required_on_create() && !foreign_keys.contains(&f.name()))
+        .map(|f| {
+            let typ = match f.scalar_field_type() {
+                ScalarFieldType::CompositeType(id) => {
+                    let comp_type_snake = snake_ident(f.db.walk(id).name());
+
+                    quote!(#comp_type_snake::Create)
+                }
+                typ => typ.to_tokens(&quote!(), &f.ast_field().arity, f.db)?,
+            };
+
+            Some((snake_ident(f.name()), typ))
+        })
+        .collect()
+}
+
+/// `create`, `createMany`, `connectOrCreate`, `update`, `updateMany`, `upsert`,
+/// `delete` and `deleteMany` nested writes for a relation field.
+fn relation_field_nested_writes(
+    field: RelationFieldWalker,
+    args: &GenerateArgs,
+) -> (Vec<(TokenStream, TokenStream)>, TokenStream) {
+    let pcr = quote!(::prisma_client_rust);
+
+    let field_name_pascal = pascal_ident(field.name());
+    let field_name_snake = snake_ident(field.name());
+    let relation_model_name_snake = snake_ident(field.related_model().name());
+
+    let is_list = field.ast_field().arity.is_list();
+
+    let variant_name =
+        |action: &str| format_ident!("{}{}", pascal_ident(action), &field_name_pascal);
+
+    let create_variant = variant_name("create");
+    let create_many_variant = variant_name("createMany");
+    let connect_or_create_variant = variant_name("connectOrCreate");
+    let update_variant = variant_name("update");
+    let update_many_variant = variant_name("updateMany");
+    let upsert_variant = variant_name("upsert");
+    let delete_variant = variant_name("delete");
+    let delete_many_variant = variant_name("deleteMany");
+
+    let variant = |action: &str, types: TokenStream, bindings: TokenStream, value: TokenStream| {
+        let variant_name = variant_name(action);
+
+        (
+            quote!(#variant_name #types),
+            quote! {
+                Self::#variant_name #bindings => (
+                    #field_name_snake::NAME,
+                    #pcr::PrismaValue::Object(vec![(#action.to_string(), #value)])
+                )
+            },
+        )
+    };
+
+    let object = |entries: Vec<(&str, TokenStream)>| {
+        let (keys, values): (Vec<_>, Vec<_>) = entries.into_iter().unzip();
+
+        quote!(#pcr::PrismaValue::Object(vec![#((#keys.to_string(), #values)),*]))
+    };
+
+    let unique_where = |value: TokenStream| {
+        quote! {
+            #pcr::PrismaValue::Object(
+                [#value]
+                    .into_iter()
+                    .map(Into::<super::#relation_model_name_snake::WhereParam>::into)
+                    .map(#pcr::WhereInput::serialize)
+                    .map(#pcr::SerializedWhereInput::transform_equals)
+                    .collect()
+            )
+        }
+    };
+
+    let many_where = |value: TokenStream| {
+        quote! {
+            #pcr::PrismaValue::Object(#pcr::merge_fields(
+                #value
+                    .into_iter()
+                    .map(#pcr::WhereInput::serialize)
+                    .map(|s| (s.field, s.value.into()))
+                    .collect()
+            ))
+        }
+    };
+
+    let data = |value: TokenStream| {
+        quote! {
+            #pcr::PrismaValue::Object(#pcr::merge_fields(
+                #value.into_iter().map(Into::into).collect()
+            ))
+        }
+    };
+
+    let list = |value: TokenStream, binding: TokenStream, item: TokenStream| {
+        quote! {
+            #pcr::PrismaValue::List(#value.into_iter().map(|#binding| #item).collect())
+        }
+    };
+
+    let mut params = vec![];
+    let mut types = quote!();
+
+    if let Some(fields) = nested_create_fields(field) {
+        let (names, (field_types, push_wrappers)): (Vec<_>, (Vec<_>, Vec<_>)) = fields
+            .into_iter()
+            .map(|(name, typ, push_wrapper)| (name, (typ, push_wrapper)))
+            .unzip();
+
+        types.extend(quote! {
+            /// Data for creating a related record, minus the relation back to the parent
+            #[derive(Debug, Clone)]
+            pub struct Create {
+                #(pub #names: #field_types,)*
+                pub _params: Vec<#relation_model_name_snake::SetParam>
+            }
+
+            impl Create {
+                pub fn to_params(mut self) -> Vec<#relation_model_name_snake::SetParam> {
+                    self._params.extend([
+                        #(#relation_model_name_snake::#names::#push_wrappers(self.#names)),*
+                    ]);
+
+                    self._params
+                }
+            }
+        });
+
+        let create = |value: TokenStream| data(quote!(#value.to_params()));
+
+        let create_type = quote!(#field_name_snake::Create);
+        let where_type = quote!(super::#relation_model_name_snake::UniqueWhereParam);
+        let set_type = quote!(super::#relation_model_name_snake::SetParam);
+
+        if is_list {
+            let (v, arm) = variant(
+                "create",
+                quote!((Vec<#create_type>)),
+                quote!((creates)),
+                list(quote!(creates), quote!(c), create(quote!(c))),
+            );
+            params.push((
+                (v, arm),
+                quote! {
+                    pub fn create(creates: Vec<Create>) -> SetParam {
+                        SetParam::#create_variant(creates)
+                    }
+                },
+            ));
+
+            let (v, arm) = variant(
+                "connectOrCreate",
+                quote!((Vec<(#where_type, #create_type)>)),
+                quote!((values)),
+                list(
+                    quote!(values),
+                    quote!((_where, create)),
+                    object(vec![
+                        ("where", unique_where(quote!(_where))),
+                        ("create", create(quote!(create))),
+                    ]),
+                ),
+            );
+            params.push((
+                (v, arm),
+                quote! {
+                    pub fn connect_or_create(
+                        values: Vec<(#relation_model_name_snake::UniqueWhereParam, Create)>
+                    ) -> SetParam {
+                        SetParam::#connect_or_create_variant(values)
+                    }
+                },
+            ));
+
+            let (v, arm) = variant(
+                "upsert",
+                quote!((Vec<(#where_type, #create_type, Vec<#set_type>)>)),
+                quote!((values)),
+                list(
+                    quote!(values),
+                    quote!((_where, create, update)),
+                    object(vec![
+                        ("where", unique_where(quote!(_where))),
+                        ("create", create(quote!(create))),
+                        ("update", data(quote!(update))),
+                    ]),
+                ),
+            );
+            params.push((
+                (v, arm),
+                quote! {
+                    pub fn upsert(
+                        values: Vec<(
+                            #relation_model_name_snake::UniqueWhereParam,
+                            Create,
+                            Vec<#relation_model_name_snake::SetParam>
+                        )>
+                    ) -> SetParam {
+                        SetParam::#upsert_variant(values)
+                    }
+                },
+            ));
+        } else {
+            let (v, arm) = variant(
+                "create",
+                quote!((#create_type)),
+                quote!((create)),
+                create(quote!(create)),
+            );
+            params.push((
+                (v, arm),
+                quote! {
+                    pub fn create(create: Create) -> SetParam {
+                        SetParam::#create_variant(create)
+                    }
+                },
+            ));
+
+            let (v, arm) = variant(
+                "connectOrCreate",
+                quote!((#where_type, #create_type)),
+                quote!((_where, create)),
+                object(vec![
+                    ("where", unique_where(quote!(_where))),
+                    ("create", create(quote!(create))),
+                ]),
+            );
+            params.push((
+                (v, arm),
+                quote! {
+                    pub fn connect_or_create(
+                        _where: #relation_model_name_snake::UniqueWhereParam,
+                        create: Create
+                    ) -> SetParam {
+                        SetParam::#connect_or_create_variant(_where, create)
+                    }
+                },
+            ));
+
+            let (v, arm) = variant(
+                "upsert",
+                quote!((#create_type, Vec<#set_type>)),
+                quote!((create, update)),
+                object(vec![
+                    ("create", create(quote!(create))),
+                    ("update", data(quote!(update))),
+                ]),
+            );
+            params.push((
+                (v, arm),
+                quote! {
+                    pub fn upsert(
+                        create: Create,
+                        update: Vec<#relation_model_name_snake::SetParam>
+                    ) -> SetParam {
+                        SetParam::#upsert_variant(create, update)
+                    }
+                },
+            ));
+        }
+    }
+
+    let create_many_supported = is_list
+        && args
+            .connector
+            .capabilities()
+            .contains(datamodel_connector::ConnectorCapability::CreateMany)
+        && field
+            .opposite_relation_field()
+            .map(|f| !f.ast_field().arity.is_list())
+            .unwrap_or(false);
+
+    if let Some(fields) = create_many_supported
+        .then(|| nested_create_many_fields(field))
+        .flatten()
+    {
+        let (names, field_types): (Vec<_>, Vec<_>) = fields.into_iter().unzip();
+
+        types.extend(quote! {
+            /// Data for creating many related records, minus the foreign keys to the parent
+            #[derive(Debug, Clone)]
+            pub struct CreateUnchecked {
+                #(pub #names: #field_types,)*
+                pub _params: Vec<#relation_model_name_snake::UncheckedSetParam>
+            }
+
+            impl CreateUnchecked {
+                pub fn to_params(mut self) -> Vec<#relation_model_name_snake::UncheckedSetParam> {
+                    self._params.extend([
+                        #(#relation_model_name_snake::#names::set(self.#names)),*
+                    ]);
+
+                    self._params
+                }
+            }
+        });
+
+        let (v, arm) = variant(
+            "createMany",
+            quote!((Vec<#field_name_snake::CreateUnchecked>)),
+            quote!((creates)),
+            object(vec![(
+                "data",
+                list(quote!(creates), quote!(c), data(quote!(c.to_params()))),
+            )]),
+        );
+        params.push((
+            (v, arm),
+            quote! {
+                pub fn create_many(creates: Vec<CreateUnchecked>) -> SetParam {
+                    SetParam::#create_many_variant(creates)
+                }
+            },
+        ));
+    }
+
+    let where_type = quote!(super::#relation_model_name_snake::UniqueWhereParam);
+    let many_where_type = quote!(super::#relation_model_name_snake::WhereParam);
+    let set_type = quote!(super::#relation_model_name_snake::SetParam);
+
+    if is_list {
+        let (v, arm) = variant(
+            "update",
+            quote!((Vec<(#where_type, Vec<#set_type>)>)),
+            quote!((values)),
+            list(
+                quote!(values),
+                quote!((_where, update)),
+                object(vec![
+                    ("where", unique_where(quote!(_where))),
+                    ("data", data(quote!(update))),
+                ]),
+            ),
+        );
+        params.push((
+            (v, arm),
+            quote! {
+                pub fn update(
+                    values: Vec<(
+                        #relation_model_name_snake::UniqueWhereParam,
+                        Vec<#relation_model_name_snake::SetParam>
+                    )>
+                ) -> SetParam {
+                    SetParam::#update_variant(values)
+                }
+            },
+        ));
+
+        let (v, arm) = variant(
+            "updateMany",
+            quote!((Vec<#many_where_type>, Vec<#set_type>)),
+            quote!((_where, update)),
+            object(vec![
+                ("where", many_where(quote!(_where))),
+                ("data", data(quote!(update))),
+            ]),
+        );
+        params.push((
+            (v, arm),
+            quote! {
+                pub fn update_many(
+                    _where: Vec<#relation_model_name_snake::WhereParam>,
+                    update: Vec<#relation_model_name_snake::SetParam>
+                ) -> SetParam {
+                    SetParam::#update_many_variant(_where, update)
+                }
+            },
+        ));
+
+        let (v, arm) = variant(
+            "delete",
+            quote!((Vec<#where_type>)),
+            quote!((values)),
+            list(quote!(values), quote!(_where), unique_where(quote!(_where))),
+        );
+        params.push((
+            (v, arm),
+            quote! {
+                pub fn delete(
+                    values: Vec<#relation_model_name_snake::UniqueWhereParam>
+                ) -> SetParam {
+                    SetParam::#delete_variant(values)
+                }
+            },
+        ));
+
+        let (v, arm) = variant(
+            "deleteMany",
+            quote!((Vec<#many_where_type>)),
+            quote!((_where)),
+            many_where(quote!(_where)),
+        );
+        params.push((
+            (v, arm),
+            quote! {
+                pub fn delete_many(
+                    _where: Vec<#relation_model_name_snake::WhereParam>
+                ) -> SetParam {
+                    SetParam::#delete_many_variant(_where)
+                }
+            },
+        ));
+    } else {
+        let (v, arm) = variant(
+            "update",
+            quote!((Vec<#set_type>)),
+            quote!((update)),
+            data(quote!(update)),
+        );
+        params.push((
+            (v, arm),
+            quote! {
+                pub fn update(update: Vec<#relation_model_name_snake::SetParam>) -> SetParam {
+                    SetParam::#update_variant(update)
+                }
+            },
+        ));
+
+        if field.ast_field().arity.is_optional() {
+            let (v, arm) = variant(
+                "delete",
+                quote!(),
+                quote!(),
+                quote!(#pcr::PrismaValue::Boolean(true)),
+            );
+            params.push((
+                (v, arm),
+                quote! {
+                    pub fn delete() -> SetParam {
+                        SetParam::#delete_variant
+                    }
+                },
+            ));
+        }
+    }
+
+    let (variants, fns): (Vec<_>, Vec<_>) = params.into_iter().unzip();
+
+    (variants, quote!(#types #(#fns)*))
+}
+
 fn field_set_params(
     field: FieldWalker,
     args: &GenerateArgs,
@@ -584,7 +1077,14 @@ fn field_set_params(
             variants.extend(v);
             functions.extend(f);
 
-            (field.name().to_string(), base)
+            let (nested_params, nested_fns) = relation_field_nested_writes(relation_field, args);
+
+            let (v, f): (Vec<_>, Vec<_>) = nested_params.into_iter().unzip();
+
+            variants.extend(v);
+            functions.extend(f);
+
+            (field.name().to_string(), quote!(#base #nested_fns))
         }
     };
 
@@ -695,7 +1195,7 @@ pub fn model_data(model: ModelWalker, args: &GenerateArgs) -> ModelModulePart {
 
         (
             quote! {
-                #[derive(Clone)]
+                #[derive(Debug, Clone)]
                 pub enum UncheckedSetParam {
                       #(#variants),*
                 }
@@ -709,6 +1209,12 @@ pub fn model_data(model: ModelWalker, args: &GenerateArgs) -> ModelModulePart {
                         (k.to_string(), v)
                     }
                 }
+
+                impl ::serde::Serialize for UncheckedSetParam {
+                    fn serialize<S: ::serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                        #pcr::serde::params::serialize_param(self.clone().into(), serializer)
+                    }
+                }
             },
             field_stuff,
         )
@@ -716,7 +1222,7 @@ pub fn model_data(model: ModelWalker, args: &GenerateArgs) -> ModelModulePart {
 
     ModelModulePart {
         data: quote! {
-            #[derive(Clone)]
+            #[derive(Debug, Clone)]
             pub enum SetParam {
                 #(#variants),*
             }
@@ -731,6 +1237,12 @@ pub fn model_data(model: ModelWalker, args: &GenerateArgs) -> ModelModulePart {
                 }
             }
 
+            impl ::serde::Serialize for SetParam {
+                fn serialize<S: ::serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                    #pcr::serde::params::serialize_param(self.clone().into(), serializer)
+                }
+            }
+
             #unchecked_enum
         },
         fields: unchecked_fields
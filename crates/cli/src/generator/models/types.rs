@@ -34,13 +34,51 @@ fn scalar_selections_fn(model: ModelWalker, module_path: &TokenStream) -> TokenS
     }
 }
 
+/// Fields of the primary key, or of the first unique criteria with only required fields
+/// if the model has no primary key, along with the name used to select them in unique filters.
+fn primary_key_fields(model: ModelWalker) -> (String, Vec<&str>) {
+    let (name, fields): (Option<&str>, Vec<&str>) = match model.primary_key() {
+        Some(primary_key) => (
+            primary_key.name(),
+            primary_key.fields().map(|f| f.name()).collect(),
+        ),
+        None => model
+            .indexes()
+            .filter(|index| index.is_unique())
+            .map(|unique| {
+                let fields = unique
+                    .fields()
+                    .filter_map(|field| {
+                        model
+                            .scalar_fields()
+                            .find(|mf| mf.field_id() == field.field_id())
+                    })
+                    .collect::<Vec<_>>();
+
+                (unique.name(), fields)
+            })
+            .find(|(_, fields)| fields.iter().all(|f| f.ast_field().arity.is_required()))
+            .map(|(name, fields)| (name, fields.into_iter().map(|f| f.name()).collect()))
+            .unwrap_or_default(),
+    };
+
+    let name = match (name, &fields[..]) {
+        (_, [field]) => field.to_string(),
+        (Some(name), _) => name.to_string(),
+        (None, fields) => fields.join("_"),
+    };
+
+    (name, fields)
+}
+
 pub fn r#struct(model: ModelWalker, module_path: &TokenStream) -> TokenStream {
     let pcr = quote!(::prisma_client_rust);
 
     let scalar_selections_fn = scalar_selections_fn(model, module_path);
+    let (primary_key_name, primary_key) = primary_key_fields(model);
 
     quote! {
-        #[derive(Clone)]
+        #[derive(Clone, Debug)]
         pub struct Types;
 
         impl #pcr::ModelTypes for Types {
@@ -51,8 +89,16 @@ pub fn r#struct(model: ModelWalker, module_path: &TokenStream) -> TokenStream {
             type With = WithParam;
             type OrderBy = OrderByWithRelationParam;
             type Cursor = UniqueWhereParam;
+            type Aggregate = AggregateParam;
+            type AggregateData = AggregateData;
+            type ScalarField = ScalarField;
+            type Having = HavingParam;
+            type OrderByWithAggregation = OrderByWithAggregationParam;
+            type GroupByData = GroupByData;
 
             const MODEL: &'static str = NAME;
+            const PRIMARY_KEY: &'static [&'static str] = &[#(#primary_key),*];
+            const PRIMARY_KEY_NAME: &'static str = #primary_key_name;
 
             #scalar_selections_fn
         }
@@ -147,7 +147,7 @@ pub fn collate_entries(entries: Vec<Variant>) -> TokenStream {
     }).unzip();
 
     quote! {
-        #[derive(Clone)]
+        #[derive(Debug, Clone)]
         pub enum WhereParam {
             #(#variants),*
         }
@@ -162,11 +162,23 @@ pub fn collate_entries(entries: Vec<Variant>) -> TokenStream {
             }
         }
 
-        #[derive(Clone)]
+        impl ::serde::Serialize for WhereParam {
+            fn serialize<S: ::serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                #pcr::serde::params::serialize_param(#pcr::WhereInput::serialize(self.clone()).into(), serializer)
+            }
+        }
+
+        #[derive(Debug, Clone)]
         pub enum UniqueWhereParam {
             #(#unique_variants),*
         }
 
+        impl ::serde::Serialize for UniqueWhereParam {
+            fn serialize<S: ::serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                #pcr::serde::params::serialize_param(#pcr::WhereInput::serialize(WhereParam::from(self.clone())).into(), serializer)
+            }
+        }
+
         impl From<UniqueWhereParam> for WhereParam {
             fn from(value: UniqueWhereParam) -> Self {
                 match value {
@@ -5,7 +5,7 @@ use prisma_client_rust_sdk::prisma::prisma_models::{
 
 use crate::generator::prelude::*;
 
-use super::{order_by, pagination, ModelModulePart};
+use super::{count, order_by, pagination, ModelModulePart};
 
 pub fn builder_fn(field: RelationFieldWalker) -> TokenStream {
     let relation_model_name_snake = snake_ident(field.related_model().name());
@@ -70,8 +70,19 @@ fn into_selection_arm(field: RelationFieldWalker) -> TokenStream {
 }
 
 pub fn model_data(model: ModelWalker) -> ModelModulePart {
-    let variants = model.relation_fields().map(enum_variant);
-    let into_selection_arms = model.relation_fields().map(into_selection_arm);
+    let mut variants = model
+        .relation_fields()
+        .map(enum_variant)
+        .collect::<Vec<_>>();
+    let mut into_selection_arms = model
+        .relation_fields()
+        .map(into_selection_arm)
+        .collect::<Vec<_>>();
+
+    if count::has_relation_count(model) {
+        variants.push(quote!(_Count(_count::Fetch)));
+        into_selection_arms.push(quote!(Self::_Count(fetch) => fetch.to_selection()));
+    }
 
     let fields = model
         .relation_fields()
@@ -135,7 +146,7 @@ pub fn model_data(model: ModelWalker) -> ModelModulePart {
 
     ModelModulePart {
         data: quote! {
-            #[derive(Clone)]
+            #[derive(Debug, Clone)]
             pub enum WithParam {
                 #(#variants),*
             }
@@ -17,6 +17,9 @@ pub fn generate_module(args: &GenerateArgs) -> TokenStream {
 
                 let variant_name = pascal_ident(&action_sanitised_str);
 
+                let rename =
+                    cfg!(feature = "serde-filters").then(|| quote!(#[serde(rename = #action_str)]));
+
                 let value_ident = format_ident!("value");
 
                 let value_as_prisma_value = field.to_prisma_value(&value_ident);
@@ -26,7 +29,7 @@ pub fn generate_module(args: &GenerateArgs) -> TokenStream {
                 // https://github.com/Brendonovich/prisma-client-rust/issues/297
                 if filter.name == "JsonNullable" && field.name == "equals" {
                     Some((
-                        quote!(#variant_name(Option<#typ>)),
+                        quote!(#rename #variant_name(Option<#typ>)),
                         quote! {
                             Self::#variant_name(#value_ident) =>
                                 ::prisma_client_rust::SerializedWhereValue::Object(
@@ -40,7 +43,7 @@ pub fn generate_module(args: &GenerateArgs) -> TokenStream {
                     ))
                 } else {
                     Some((
-                        quote!(#variant_name(#typ)),
+                        quote!(#rename #variant_name(#typ)),
                         quote! {
                             Self::#variant_name(#value_ident) =>
                                 ::prisma_client_rust::SerializedWhereValue::Object(
@@ -55,8 +58,12 @@ pub fn generate_module(args: &GenerateArgs) -> TokenStream {
             })
             .unzip();
 
+        let deserialize_derive =
+            cfg!(feature = "serde-filters").then(|| quote!(#[derive(::serde::Deserialize)]));
+
         quote! {
-            #[derive(Clone)]
+            #[derive(Debug, Clone)]
+            #deserialize_derive
             pub enum #name {
                 #(#method_variants),*
             }
@@ -68,6 +75,14 @@ pub fn generate_module(args: &GenerateArgs) -> TokenStream {
                     }
                 }
             }
+
+            impl ::serde::Serialize for #name {
+                fn serialize<S: ::serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                    let value: ::prisma_client_rust::SerializedWhereValue = self.clone().into();
+
+                    ::prisma_client_rust::serde::params::serialize_value(value.into(), serializer)
+                }
+            }
         }
     });
 
@@ -39,7 +39,7 @@ pub fn generate_module(args: &GenerateArgs) -> TokenStream {
             .unzip();
 
         quote! {
-            #[derive(Clone)]
+            #[derive(Debug, Clone)]
             pub enum #name {
                 #(#method_variants),*
             }
@@ -51,6 +51,12 @@ pub fn generate_module(args: &GenerateArgs) -> TokenStream {
                     }
                 }
             }
+
+            impl ::serde::Serialize for #name {
+                fn serialize<S: ::serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                    ::prisma_client_rust::serde::params::serialize_value(self.clone().into(), serializer)
+                }
+            }
         }
     });
 
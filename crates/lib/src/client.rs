@@ -6,10 +6,17 @@ use query_core::{
     BatchDocumentTransaction, CoreError, Operation, TxId,
 };
 
-use std::sync::Arc;
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
 use thiserror::Error;
+use tokio::sync::{RwLock, RwLockReadGuard};
 
-use crate::{prisma_value, QueryError, Result};
+use crate::{
+    connection, middleware, prisma_value, replicas::ReadReplicas, telemetry::QueryTelemetry,
+    BatchMode, ConnectionOptions, MiddlewareQuery, Next, QueryError, QueryMiddleware, Result,
+};
 
 pub type Executor = Box<dyn query_core::QueryExecutor + Send + Sync + 'static>;
 
@@ -20,9 +27,35 @@ pub trait PrismaClient {
 }
 
 pub struct ExecutorConnector {
-    pub executor: Executor,
+    /// `None` once the client has been disconnected
+    executor: RwLock<Option<Executor>>,
     pub query_schema: Arc<QuerySchema>,
     pub url: String,
+    /// The datasource's provider, eg. `postgresql` or `sqlite`
+    pub provider: &'static str,
+    /// The options in effect after applying the client's options to `url`
+    pub connection_options: ConnectionOptions,
+    pub replicas: Option<ReadReplicas>,
+}
+
+impl ExecutorConnector {
+    /// The executor is locked for as long as the guard is held,
+    /// so that disconnecting waits for queries that are already running.
+    pub async fn executor(&self) -> Result<RwLockReadGuard<'_, Executor>> {
+        RwLockReadGuard::try_map(self.executor.read().await, Option::as_ref)
+            .map_err(|_| QueryError::Disconnected)
+    }
+
+    /// Waits for running queries to finish before closing the primary and replica connection pools.
+    pub(crate) async fn disconnect(&self) {
+        let mut executor = self.executor.write().await;
+
+        if let Some(replicas) = &self.replicas {
+            replicas.disconnect().await;
+        }
+
+        executor.take();
+    }
 }
 
 #[derive(Clone)]
@@ -30,26 +63,57 @@ pub(crate) enum ExecutionEngine {
     Real {
         connector: Arc<ExecutorConnector>,
         tx_id: Option<TxId>,
+        /// Send reads to the primary even if there are read replicas
+        use_primary: bool,
     },
     #[cfg(feature = "mocking")]
-    Mock(crate::MockStore),
+    Mock {
+        store: crate::MockStore,
+        tx_id: Option<TxId>,
+    },
+    #[cfg(feature = "in-memory")]
+    InMemory {
+        store: crate::in_memory::InMemoryStore,
+        tx_id: Option<TxId>,
+    },
 }
 
 impl ExecutionEngine {
-    async fn execute(&self, op: Operation) -> Result<serde_value::Value> {
+    pub(crate) async fn execute(&self, query: MiddlewareQuery) -> Result<serde_value::Value> {
         match self {
-            Self::Real { connector, tx_id } => {
-                let response = connector
-                    .executor
-                    .execute(
-                        tx_id.clone(),
-                        op,
-                        connector.query_schema.clone(),
-                        None,
-                        EngineProtocol::Graphql,
-                    )
-                    .await
-                    .map_err(|e| QueryError::Execute(e.into()))?;
+            Self::Real {
+                connector,
+                tx_id,
+                use_primary,
+            } => {
+                let op = query.operation;
+                let executor = connector.executor().await?;
+
+                // Reads outside of transactions can go to a replica
+                let replicas = match (tx_id, use_primary, &op) {
+                    (None, false, Operation::Read(_)) => connector.replicas.as_ref(),
+                    _ => None,
+                };
+
+                let response = match replicas {
+                    Some(replicas) => {
+                        replicas
+                            .execute(&executor, connector.query_schema.clone(), op)
+                            .await
+                    }
+                    _ => {
+                        executor
+                            .execute(
+                                tx_id.clone(),
+                                op,
+                                connector.query_schema.clone(),
+                                None,
+                                EngineProtocol::Graphql,
+                            )
+                            .await
+                    }
+                }
+                .map_err(|e| QueryError::Execute(e.into()))?;
 
                 let data: prisma_value::Item = response.data.into();
 
@@ -60,22 +124,34 @@ impl ExecutionEngine {
                 Ok(data)
             }
             #[cfg(feature = "mocking")]
-            Self::Mock(store) => Ok(store.get_op(&op).await.expect("Mock data not found")),
+            Self::Mock { store, tx_id } => store.get_op(&query.operation, false, tx_id.clone()),
+            #[cfg(feature = "in-memory")]
+            Self::InMemory { store, tx_id } => store.execute(&query, tx_id.as_ref()),
         }
     }
 
     pub async fn execute_all(
         &self,
-        ops: Vec<Operation>,
+        queries: Vec<MiddlewareQuery>,
+        mode: BatchMode,
     ) -> Result<Vec<Result<serde_value::Value>>> {
         match self {
-            Self::Real { connector, .. } => {
+            Self::Real {
+                connector, tx_id, ..
+            } => {
+                // Batches executed by a transaction client always run inside its transaction
+                let transaction = match (tx_id, mode) {
+                    (None, BatchMode::Transactional) => Some(BatchDocumentTransaction::new(None)),
+                    _ => None,
+                };
+
                 let response = connector
-                    .executor
+                    .executor()
+                    .await?
                     .execute_all(
-                        None,
-                        ops,
-                        Some(BatchDocumentTransaction::new(None)),
+                        tx_id.clone(),
+                        queries.into_iter().map(|q| q.operation).collect(),
+                        transaction,
                         connector.query_schema.clone(),
                         None,
                         EngineProtocol::Graphql,
@@ -98,26 +174,56 @@ impl ExecutionEngine {
                     .collect())
             }
             #[cfg(feature = "mocking")]
-            Self::Mock(store) => {
-                let mut ret = vec![];
+            Self::Mock { store, tx_id } => Ok(queries
+                .iter()
+                .map(|q| store.get_op(&q.operation, true, tx_id.clone()))
+                .collect()),
+            #[cfg(feature = "in-memory")]
+            Self::InMemory { store, tx_id } => store.execute_all(&queries, mode, tx_id.as_ref()),
+        }
+    }
 
-                for op in ops {
-                    ret.push(Ok(store.get_op(&op).await.expect("Mock data not found")))
-                }
+    pub(crate) fn is_mock(&self) -> bool {
+        match self {
+            Self::Real { .. } => false,
+            #[cfg(feature = "mocking")]
+            Self::Mock { .. } => true,
+            #[cfg(feature = "in-memory")]
+            Self::InMemory { .. } => false,
+        }
+    }
 
-                Ok(ret)
-            }
+    pub(crate) fn tx_id(&self) -> Option<TxId> {
+        match self {
+            Self::Real { tx_id, .. } => tx_id.clone(),
+            #[cfg(feature = "mocking")]
+            Self::Mock { tx_id, .. } => tx_id.clone(),
+            #[cfg(feature = "in-memory")]
+            Self::InMemory { tx_id, .. } => tx_id.clone(),
         }
     }
 
     fn with_tx_id(&self, tx_id: Option<TxId>) -> Self {
         match self {
-            Self::Real { connector, .. } => Self::Real {
+            Self::Real {
+                connector,
+                use_primary,
+                ..
+            } => Self::Real {
                 connector: connector.clone(),
                 tx_id,
+                use_primary: *use_primary,
             },
             #[cfg(feature = "mocking")]
-            _ => self.clone(),
+            Self::Mock { store, .. } => Self::Mock {
+                store: store.clone(),
+                tx_id,
+            },
+            #[cfg(feature = "in-memory")]
+            Self::InMemory { store, .. } => Self::InMemory {
+                store: store.clone(),
+                tx_id,
+            },
         }
     }
 }
@@ -127,12 +233,43 @@ impl ExecutionEngine {
 #[derive(Clone)]
 pub struct PrismaClientInternals {
     pub(crate) engine: ExecutionEngine,
+    pub(crate) middleware: Arc<Vec<Box<dyn QueryMiddleware>>>,
     pub action_notifier: Arc<crate::ActionNotifier>,
 }
 
 impl PrismaClientInternals {
-    pub(crate) async fn execute(&self, operation: Operation) -> Result<serde_value::Value> {
-        self.engine.execute(operation).await
+    pub(crate) async fn execute(&self, query: MiddlewareQuery) -> Result<serde_value::Value> {
+        self.run_middleware(vec![query], None)
+            .await?
+            .into_iter()
+            .next()
+            .unwrap_or_else(|| Err(QueryError::Middleware("No result returned".to_string())))
+    }
+
+    pub(crate) async fn execute_all(
+        &self,
+        queries: Vec<MiddlewareQuery>,
+        mode: BatchMode,
+    ) -> Result<Vec<Result<serde_value::Value>>> {
+        self.run_middleware(queries, Some(mode)).await
+    }
+
+    async fn run_middleware(
+        &self,
+        queries: Vec<MiddlewareQuery>,
+        batch: Option<BatchMode>,
+    ) -> middleware::MiddlewareResult {
+        let request = middleware::request(queries, batch, self.engine.tx_id());
+
+        let telemetry = QueryTelemetry::start(&request);
+
+        let result = telemetry
+            .instrument(Next::new(&self.middleware, &self.engine).run(request))
+            .await;
+
+        telemetry.finish(&result);
+
+        result
     }
 
     // pub fn notify_model_mutation<'a, Action>(&self)
@@ -157,6 +294,9 @@ impl PrismaClientInternals {
     pub async fn new(
         url: Option<String>,
         action_notifier: ActionNotifier,
+        middleware: Vec<Box<dyn QueryMiddleware>>,
+        connection_options: ConnectionOptions,
+        read_replicas: Vec<String>,
         datamodel: &str,
     ) -> std::result::Result<Self, NewClientError> {
         let schema = Arc::new(psl::validate(datamodel.into()));
@@ -190,20 +330,54 @@ impl PrismaClientInternals {
             }
         };
 
+        let (url, effective_options) =
+            connection::apply(&url, source.active_provider, connection_options)?;
+
         let executor =
             request_handlers::load_executor(source, config.preview_features(), &url).await?;
 
         executor.primary_connector().get_connection().await?;
 
+        let replicas = match read_replicas.is_empty() {
+            true => None,
+            false => {
+                let mut executors = vec![];
+
+                for url in read_replicas {
+                    let (url, _) =
+                        connection::apply(&url, source.active_provider, connection_options)?;
+
+                    let executor =
+                        request_handlers::load_executor(source, config.preview_features(), &url)
+                            .await?;
+
+                    executors.push((executor, url));
+                }
+
+                let replicas = ReadReplicas::new(executors);
+
+                // Unreachable replicas don't prevent the client from being built,
+                // since reads can fall back to the primary
+                replicas.check().await;
+
+                Some(replicas)
+            }
+        };
+
         Ok(Self {
             engine: ExecutionEngine::Real {
                 connector: Arc::new(ExecutorConnector {
-                    executor,
+                    executor: RwLock::new(Some(executor)),
                     query_schema: Arc::new(schema::build(schema.clone(), true)),
                     url,
+                    provider: source.active_provider,
+                    connection_options: effective_options,
+                    replicas,
                 }),
                 tx_id: None,
+                use_primary: false,
             },
+            middleware: Arc::new(middleware),
             action_notifier: Arc::new(action_notifier),
         })
     }
@@ -214,29 +388,114 @@ impl PrismaClientInternals {
 
         (
             Self {
-                engine: ExecutionEngine::Mock(mock_store.clone()),
+                engine: ExecutionEngine::Mock {
+                    store: mock_store.clone(),
+                    tx_id: None,
+                },
+                middleware: Default::default(),
                 action_notifier: Arc::new(action_notifier),
             },
             mock_store,
         )
     }
 
+    #[cfg(feature = "in-memory")]
+    pub fn new_in_memory(action_notifier: ActionNotifier, datamodel: &str) -> Self {
+        Self {
+            engine: ExecutionEngine::InMemory {
+                store: crate::in_memory::InMemoryStore::new(datamodel),
+                tx_id: None,
+            },
+            middleware: Default::default(),
+            action_notifier: Arc::new(action_notifier),
+        }
+    }
+
     pub fn url(&self) -> &str {
         match &self.engine {
             #[cfg(feature = "mocking")]
-            ExecutionEngine::Mock(_) => "mock",
+            ExecutionEngine::Mock { .. } => "mock",
+            #[cfg(feature = "in-memory")]
+            ExecutionEngine::InMemory { .. } => "memory",
             ExecutionEngine::Real { connector, .. } => &connector.url,
         }
     }
 
+    /// Closes the client's connections once running queries have finished.
+    /// Queries made after this, including through other handles to the same client, fail with [`QueryError::Disconnected`].
+    ///
+    /// Mock and in-memory clients don't hold any connections, so this does nothing for them.
+    pub async fn disconnect(&self) {
+        if let ExecutionEngine::Real { connector, .. } = &self.engine {
+            connector.disconnect().await;
+        }
+    }
+
+    /// Acquires a connection to the primary database to check that it is reachable.
+    pub async fn health_check(&self) -> Result<HealthCheck> {
+        let start = Instant::now();
+
+        if let ExecutionEngine::Real { connector, .. } = &self.engine {
+            connector
+                .executor()
+                .await?
+                .primary_connector()
+                .get_connection()
+                .await
+                .map_err(|e| QueryError::Execute(CoreError::from(e).into()))?;
+        }
+
+        Ok(HealthCheck {
+            latency: start.elapsed(),
+        })
+    }
+
+    /// Sends all queries to the primary database, even reads that would otherwise go to a read replica.
+    pub fn use_primary(&self) -> Self {
+        let engine = match &self.engine {
+            ExecutionEngine::Real {
+                connector, tx_id, ..
+            } => ExecutionEngine::Real {
+                connector: connector.clone(),
+                tx_id: tx_id.clone(),
+                use_primary: true,
+            },
+            #[allow(unreachable_patterns)]
+            engine => engine.clone(),
+        };
+
+        Self {
+            engine,
+            middleware: self.middleware.clone(),
+            action_notifier: self.action_notifier.clone(),
+        }
+    }
+
+    /// Mock and in-memory clients don't have any connection options.
+    pub fn connection_options(&self) -> ConnectionOptions {
+        match &self.engine {
+            ExecutionEngine::Real { connector, .. } => connector.connection_options,
+            #[allow(unreachable_patterns)]
+            _ => ConnectionOptions::default(),
+        }
+    }
+
     pub fn with_tx_id(&self, tx_id: Option<TxId>) -> Self {
         Self {
             engine: self.engine.with_tx_id(tx_id),
+            middleware: self.middleware.clone(),
             action_notifier: self.action_notifier.clone(),
         }
     }
 }
 
+/// The result of a successful [`PrismaClientInternals::health_check`].
+#[derive(Debug, Clone, Copy)]
+pub struct HealthCheck {
+    /// How long it took to acquire a connection
+    pub latency: Duration,
+}
+
 trait DiagnosticsToString {
     fn to_string(&self) -> String;
 }
@@ -258,6 +517,9 @@ pub enum NewClientError {
 
     #[error("Error getting database connection: {0}")]
     Connection(#[from] query_core::ConnectorError),
+
+    #[error("Invalid connection option: {0}")]
+    ConnectionOption(String),
 }
 
 impl From<Diagnostics> for NewClientError {
@@ -0,0 +1,234 @@
+use std::time::Duration;
+
+use crate::NewClientError;
+
+/// Connection pool and timeout settings for a client.
+///
+/// Options left as `None` use the value from the datasource URL if it has one,
+/// and otherwise the connector's default.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ConnectionOptions {
+    /// Maximum number of connections in the pool
+    pub connection_limit: Option<u32>,
+    /// How long to wait for a connection from the pool before failing
+    pub pool_timeout: Option<Duration>,
+    /// How long to wait for a new connection to be opened before failing
+    pub connect_timeout: Option<Duration>,
+    /// How long to wait for a response from the database before failing
+    pub socket_timeout: Option<Duration>,
+}
+
+#[derive(Clone, Copy)]
+enum Style {
+    /// `scheme://host/db?key=value&key=value`
+    Query,
+    /// `sqlserver://host;key=value;key=value`
+    Jdbc,
+}
+
+#[derive(Clone, Copy)]
+enum Unit {
+    Seconds,
+    Millis,
+}
+
+/// How a connector accepts connection options in its URL.
+struct Params {
+    style: Style,
+    unit: Unit,
+    connection_limit: Option<&'static str>,
+    pool_timeout: Option<&'static str>,
+    connect_timeout: Option<&'static str>,
+    socket_timeout: Option<&'static str>,
+}
+
+impl Params {
+    fn for_provider(provider: &str) -> Option<Self> {
+        Some(match provider {
+            "postgresql" | "cockroachdb" | "mysql" => Self {
+                style: Style::Query,
+                unit: Unit::Seconds,
+                connection_limit: Some("connection_limit"),
+                pool_timeout: Some("pool_timeout"),
+                connect_timeout: Some("connect_timeout"),
+                socket_timeout: Some("socket_timeout"),
+            },
+            "sqlite" => Self {
+                style: Style::Query,
+                unit: Unit::Seconds,
+                connection_limit: Some("connection_limit"),
+                pool_timeout: None,
+                connect_timeout: None,
+                socket_timeout: Some("socket_timeout"),
+            },
+            "sqlserver" => Self {
+                style: Style::Jdbc,
+                unit: Unit::Seconds,
+                connection_limit: Some("connectionLimit"),
+                pool_timeout: Some("poolTimeout"),
+                connect_timeout: Some("connectTimeout"),
+                socket_timeout: Some("socketTimeout"),
+            },
+            "mongodb" => Self {
+                style: Style::Query,
+                unit: Unit::Millis,
+                connection_limit: Some("maxPoolSize"),
+                pool_timeout: Some("waitQueueTimeoutMS"),
+                connect_timeout: Some("connectTimeoutMS"),
+                socket_timeout: Some("socketTimeoutMS"),
+            },
+            _ => return None,
+        })
+    }
+
+    fn format(&self, option: &str, duration: Duration) -> Result<String, NewClientError> {
+        match self.unit {
+            Unit::Seconds if duration.subsec_nanos() != 0 => Err(invalid(format!(
+                "{option} must be a whole number of seconds for this connector"
+            ))),
+            Unit::Seconds => Ok(duration.as_secs().to_string()),
+            Unit::Millis => Ok(duration.as_millis().to_string()),
+        }
+    }
+
+    fn parse(&self, value: &str) -> Option<Duration> {
+        let value = value.parse().ok()?;
+
+        Some(match self.unit {
+            Unit::Seconds => Duration::from_secs(value),
+            Unit::Millis => Duration::from_millis(value),
+        })
+    }
+}
+
+/// Writes `options` into `url` using the parameter names `provider` expects,
+/// returning the new URL along with the options that are now in effect.
+pub(crate) fn apply(
+    url: &str,
+    provider: &str,
+    options: ConnectionOptions,
+) -> Result<(String, ConnectionOptions), NewClientError> {
+    let params = match Params::for_provider(provider) {
+        Some(params) => params,
+        None if options == ConnectionOptions::default() => {
+            return Ok((url.to_string(), options));
+        }
+        None => {
+            return Err(invalid(format!(
+                "the {provider} connector does not support connection options"
+            )))
+        }
+    };
+
+    if options.connection_limit == Some(0) {
+        return Err(invalid("connection_limit must be at least 1".to_string()));
+    }
+
+    let (base, mut pairs) = split(url, params.style);
+
+    let mut set =
+        |option: &str, name: Option<&str>, value: Option<String>| -> Result<(), NewClientError> {
+            let value = match value {
+                Some(value) => value,
+                None => return Ok(()),
+            };
+
+            let name = name.ok_or_else(|| {
+                invalid(format!(
+                    "{option} is not supported by the {provider} connector"
+                ))
+            })?;
+
+            match pairs
+                .iter_mut()
+                .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            {
+                Some((_, existing)) => *existing = value,
+                None => pairs.push((name.to_string(), value)),
+            }
+
+            Ok(())
+        };
+
+    set(
+        "connection_limit",
+        params.connection_limit,
+        options.connection_limit.map(|l| l.to_string()),
+    )?;
+
+    for (option, name, duration) in [
+        ("pool_timeout", params.pool_timeout, options.pool_timeout),
+        (
+            "connect_timeout",
+            params.connect_timeout,
+            options.connect_timeout,
+        ),
+        (
+            "socket_timeout",
+            params.socket_timeout,
+            options.socket_timeout,
+        ),
+    ] {
+        let value = duration.map(|d| params.format(option, d)).transpose()?;
+
+        set(option, name, value)?;
+    }
+
+    let get = |name: Option<&str>| {
+        name.and_then(|name| {
+            pairs
+                .iter()
+                .find(|(key, _)| key.eq_ignore_ascii_case(name))
+                .map(|(_, value)| value.as_str())
+        })
+    };
+
+    let effective = ConnectionOptions {
+        connection_limit: get(params.connection_limit).and_then(|v| v.parse().ok()),
+        pool_timeout: get(params.pool_timeout).and_then(|v| params.parse(v)),
+        connect_timeout: get(params.connect_timeout).and_then(|v| params.parse(v)),
+        socket_timeout: get(params.socket_timeout).and_then(|v| params.parse(v)),
+    };
+
+    Ok((join(base, &pairs, params.style), effective))
+}
+
+fn split(url: &str, style: Style) -> (&str, Vec<(String, String)>) {
+    let (base, rest) = match style {
+        Style::Query => url.split_once('?').unwrap_or((url, "")),
+        Style::Jdbc => url.split_once(';').unwrap_or((url, "")),
+    };
+
+    let separator = match style {
+        Style::Query => '&',
+        Style::Jdbc => ';',
+    };
+
+    let pairs = rest
+        .split(separator)
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| {
+            let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+            (key.to_string(), value.to_string())
+        })
+        .collect();
+
+    (base, pairs)
+}
+
+fn join(base: &str, pairs: &[(String, String)], style: Style) -> String {
+    if pairs.is_empty() {
+        return base.to_string();
+    }
+
+    let pairs = pairs.iter().map(|(key, value)| format!("{key}={value}"));
+
+    match style {
+        Style::Query => format!("{base}?{}", pairs.collect::<Vec<_>>().join("&")),
+        Style::Jdbc => format!("{base};{}", pairs.collect::<Vec<_>>().join(";")),
+    }
+}
+
+fn invalid(message: String) -> NewClientError {
+    NewClientError::ConnectionOption(message)
+}
@@ -0,0 +1,215 @@
+use serde::{de::DeserializeOwned, Deserialize, Deserializer, Serialize};
+use serde_json::{Map, Value};
+use thiserror::Error;
+
+#[derive(Debug, Error, Serialize, Clone, PartialEq, Eq)]
+pub enum FilterError {
+    #[error("Unknown field `{field}` on model `{model}`")]
+    UnknownField { model: &'static str, field: String },
+
+    #[error("Invalid value for `{field}`: {message}")]
+    InvalidValue { field: String, message: String },
+}
+
+/// Params parsed from the entries of a Prisma-style JSON object,
+/// eg. `{ "title": { "contains": "hello" } }` or `{ "createdAt": "desc" }`.
+///
+/// Implemented by the generated `WhereParam` and order by types when the `serde-filters` feature is enabled.
+pub trait FromJsonEntry: Sized {
+    const MODEL: &'static str;
+
+    /// Parses a single entry, pushing any params it produces onto `params`.
+    fn from_entry(key: &str, value: Value, params: &mut Vec<Self>) -> Result<(), FilterError>;
+}
+
+/// Parses a JSON object into params. Arrays of objects are parsed one after the other,
+/// and `null` produces no params.
+pub fn from_json<T: FromJsonEntry>(value: Value) -> Result<Vec<T>, FilterError> {
+    let mut params = vec![];
+
+    match value {
+        Value::Object(entries) => {
+            for (key, value) in entries {
+                T::from_entry(&key, value, &mut params)?;
+            }
+        }
+        Value::Array(values) => {
+            for value in values {
+                params.extend(from_json::<T>(value)?);
+            }
+        }
+        Value::Null => {}
+        value => {
+            return Err(invalid(
+                T::MODEL,
+                format!("expected an object, found `{value}`"),
+            ))
+        }
+    }
+
+    Ok(params)
+}
+
+/// `AND`, `OR` and `NOT` accept either a single object or an array of them.
+pub fn one_or_many(value: Value) -> Vec<Value> {
+    match value {
+        Value::Array(values) => values,
+        value => vec![value],
+    }
+}
+
+/// Parses the filters for a scalar field, such as `{ "gt": 1, "lt": 5 }`.
+/// Values that aren't objects are treated as `equals`.
+pub fn scalar_filters<F: DeserializeOwned>(
+    field: &str,
+    value: Value,
+) -> Result<Vec<F>, FilterError> {
+    match value {
+        Value::Object(filters) => filters
+            .into_iter()
+            .map(|(op, value)| {
+                parse(field, value, |value| {
+                    Value::Object(Map::from_iter([(op.clone(), value)]))
+                })
+            })
+            .collect(),
+        value => Ok(vec![parse(field, value, |value| {
+            Value::Object(Map::from_iter([("equals".to_string(), value)]))
+        })?]),
+    }
+}
+
+/// Parses the relation filters for a relation field, such as `{ "some": { ... } }`,
+/// returning each filter's name and value.
+pub fn relation_filters(field: &str, value: Value) -> Result<Vec<(String, Value)>, FilterError> {
+    match value {
+        Value::Object(filters) => Ok(filters.into_iter().collect()),
+        value => Err(invalid(
+            field,
+            format!("expected an object of relation filters, found `{value}`"),
+        )),
+    }
+}
+
+/// Parses a single value such as a sort order.
+pub fn value<T: DeserializeOwned>(field: &str, value: Value) -> Result<T, FilterError> {
+    parse(field, value, |value| value)
+}
+
+pub fn unknown_filter(field: &str, filter: &str) -> FilterError {
+    invalid(field, format!("unknown filter `{filter}`"))
+}
+
+pub fn unknown_field(model: &'static str, field: &str) -> FilterError {
+    FilterError::UnknownField {
+        model,
+        field: field.to_string(),
+    }
+}
+
+/// Deserializes `value` after wrapping it with `wrap`.
+///
+/// Query strings only contain strings, so values such as `5` or `true` arrive as `"5"` and `"true"`.
+/// If a string can't be deserialized it is parsed as JSON and tried again.
+fn parse<T: DeserializeOwned>(
+    field: &str,
+    value: Value,
+    wrap: impl Fn(Value) -> Value,
+) -> Result<T, FilterError> {
+    let error = match serde_json::from_value(wrap(value.clone())) {
+        Ok(parsed) => return Ok(parsed),
+        Err(e) => e,
+    };
+
+    match value {
+        Value::String(s) => serde_json::from_str(&s)
+            .ok()
+            .and_then(|value| serde_json::from_value(wrap(value)).ok()),
+        _ => None,
+    }
+    .ok_or_else(|| invalid(field, error.to_string()))
+}
+
+fn invalid(field: &str, message: String) -> FilterError {
+    FilterError::InvalidValue {
+        field: field.to_string(),
+        message,
+    }
+}
+
+/// Params parsed from JSON or a query string, validated against the model they belong to.
+///
+/// ```ignore
+/// let Params(filters): Params<post::WhereParam> =
+///     serde_json::from_str(r#"{ "title": { "contains": "hello" } }"#)?;
+///
+/// client.post().find_many(filters).exec().await?;
+/// ```
+#[derive(Debug, Clone)]
+pub struct Params<T>(pub Vec<T>);
+
+impl<T> Default for Params<T> {
+    fn default() -> Self {
+        Self(vec![])
+    }
+}
+
+impl<'de, T: FromJsonEntry> Deserialize<'de> for Params<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        from_json(Value::deserialize(deserializer)?)
+            .map(Self)
+            .map_err(serde::de::Error::custom)
+    }
+}
+
+/// The `where`, `orderBy`, `skip` and `take` arguments of a `find_many` query,
+/// parsed from JSON or a query string and applied with `FindMany::with_args`.
+#[derive(Debug, Clone)]
+pub struct FindManyArgs<Where, OrderBy> {
+    pub where_params: Vec<Where>,
+    pub order_by_params: Vec<OrderBy>,
+    pub skip: Option<i64>,
+    pub take: Option<i64>,
+}
+
+impl<Where: FromJsonEntry, OrderBy: FromJsonEntry> FindManyArgs<Where, OrderBy> {
+    pub fn from_json(value: Value) -> Result<Self, FilterError> {
+        let mut args = Self {
+            where_params: vec![],
+            order_by_params: vec![],
+            skip: None,
+            take: None,
+        };
+
+        let entries = match value {
+            Value::Object(entries) => entries,
+            Value::Null => return Ok(args),
+            value => {
+                return Err(invalid(
+                    Where::MODEL,
+                    format!("expected an object, found `{value}`"),
+                ))
+            }
+        };
+
+        for (key, value) in entries {
+            match key.as_str() {
+                "where" => args.where_params = from_json(value)?,
+                "orderBy" => args.order_by_params = from_json(value)?,
+                "skip" => args.skip = self::value(&key, value)?,
+                "take" => args.take = self::value(&key, value)?,
+                _ => return Err(invalid(&key, "unknown argument".to_string())),
+            }
+        }
+
+        Ok(args)
+    }
+}
+
+impl<'de, Where: FromJsonEntry, OrderBy: FromJsonEntry> Deserialize<'de>
+    for FindManyArgs<Where, OrderBy>
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Self::from_json(Value::deserialize(deserializer)?).map_err(serde::de::Error::custom)
+    }
+}
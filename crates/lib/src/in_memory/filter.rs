@@ -0,0 +1,279 @@
+use prisma_models::PrismaValue;
+
+use super::{
+    schema::{Model, RelationField, ScalarField},
+    unsupported,
+    value::{as_object, compare, equals, get, one_or_many},
+    Context, Object, Record,
+};
+use crate::{QueryError, Result};
+
+/// Whether `record` matches a `where` argument.
+pub fn matches(ctx: &Context, model: &Model, record: &Record, filter: &Object) -> Result<bool> {
+    for (key, value) in filter {
+        if !matches_entry(ctx, model, record, key, value)? {
+            return Ok(false);
+        }
+    }
+
+    Ok(true)
+}
+
+fn matches_nested(
+    ctx: &Context,
+    model: &Model,
+    record: &Record,
+    value: &PrismaValue,
+) -> Result<bool> {
+    match value {
+        PrismaValue::Object(filter) => matches(ctx, model, record, filter),
+        _ => Err(invalid("Expected a filter object")),
+    }
+}
+
+fn matches_entry(
+    ctx: &Context,
+    model: &Model,
+    record: &Record,
+    key: &str,
+    value: &PrismaValue,
+) -> Result<bool> {
+    if key == "AND" {
+        for filter in one_or_many(value) {
+            if !matches_nested(ctx, model, record, filter)? {
+                return Ok(false);
+            }
+        }
+
+        return Ok(true);
+    }
+
+    if key == "OR" {
+        for filter in one_or_many(value) {
+            if matches_nested(ctx, model, record, filter)? {
+                return Ok(true);
+            }
+        }
+
+        return Ok(false);
+    }
+
+    if key == "NOT" {
+        for filter in one_or_many(value) {
+            if matches_nested(ctx, model, record, filter)? {
+                return Ok(false);
+            }
+        }
+
+        return Ok(true);
+    }
+
+    if let Some(field) = model.scalar_field(key) {
+        return scalar(field, record.get(key), value);
+    }
+
+    if let Some(field) = model.relation_field(key) {
+        return relation(ctx, model, record, field, value);
+    }
+
+    // Compound unique filters are named after their fields, eg. `title_authorId`
+    match value {
+        PrismaValue::Object(fields) => matches(ctx, model, record, fields),
+        _ => Err(invalid(&format!(
+            "Unknown field `{key}` on model `{}`",
+            model.name
+        ))),
+    }
+}
+
+fn scalar(field: &ScalarField, actual: &PrismaValue, filter: &PrismaValue) -> Result<bool> {
+    let filter = match filter {
+        PrismaValue::Object(filter) => filter,
+        value => return Ok(equals(actual, value)),
+    };
+
+    let insensitive = matches!(
+        get(filter, "mode"),
+        Some(PrismaValue::Enum(mode) | PrismaValue::String(mode)) if mode == "insensitive"
+    );
+
+    let text = |value: &PrismaValue| match value {
+        PrismaValue::String(s) | PrismaValue::Enum(s) => Some(match insensitive {
+            true => s.to_lowercase(),
+            false => s.clone(),
+        }),
+        _ => None,
+    };
+
+    let eq = |a: &PrismaValue, b: &PrismaValue| match (insensitive, text(a), text(b)) {
+        (true, Some(a), Some(b)) => a == b,
+        _ => equals(a, b),
+    };
+
+    let string_op =
+        |value: &PrismaValue, op: fn(&str, &str) -> bool| match (text(actual), text(value)) {
+            (Some(actual), Some(value)) => op(&actual, &value),
+            _ => false,
+        };
+
+    let list = match actual {
+        PrismaValue::List(items) => Some(items),
+        _ => None,
+    };
+
+    for (op, value) in filter {
+        let matched = match op.as_str() {
+            "mode" => true,
+            "equals" => eq(actual, value),
+            "not" => match value {
+                PrismaValue::Object(nested) => {
+                    let mut nested = nested.clone();
+
+                    if insensitive {
+                        nested.push((
+                            "mode".to_string(),
+                            PrismaValue::Enum("insensitive".to_string()),
+                        ));
+                    }
+
+                    !matches!(actual, PrismaValue::Null)
+                        && !scalar(field, actual, &PrismaValue::Object(nested))?
+                }
+                PrismaValue::Null => !matches!(actual, PrismaValue::Null),
+                value => !matches!(actual, PrismaValue::Null) && !eq(actual, value),
+            },
+            "in" => one_or_many(value).into_iter().any(|v| eq(actual, v)),
+            "notIn" => {
+                !matches!(actual, PrismaValue::Null)
+                    && !one_or_many(value).into_iter().any(|v| eq(actual, v))
+            }
+            "lt" | "lte" | "gt" | "gte" => {
+                let ordering = match actual {
+                    PrismaValue::Null => None,
+                    actual => compare(actual, value),
+                };
+
+                match (op.as_str(), ordering) {
+                    (_, None) => false,
+                    ("lt", Some(o)) => o.is_lt(),
+                    ("lte", Some(o)) => o.is_le(),
+                    ("gt", Some(o)) => o.is_gt(),
+                    (_, Some(o)) => o.is_ge(),
+                }
+            }
+            "contains" => string_op(value, |a, b| a.contains(b)),
+            "startsWith" => string_op(value, |a, b| a.starts_with(b)),
+            "endsWith" => string_op(value, |a, b| a.ends_with(b)),
+            "has" => list
+                .map(|items| items.iter().any(|i| equals(i, value)))
+                .unwrap_or(false),
+            "hasEvery" => list
+                .map(|items| {
+                    one_or_many(value)
+                        .into_iter()
+                        .all(|v| items.iter().any(|i| equals(i, v)))
+                })
+                .unwrap_or(false),
+            "hasSome" => list
+                .map(|items| {
+                    one_or_many(value)
+                        .into_iter()
+                        .any(|v| items.iter().any(|i| equals(i, v)))
+                })
+                .unwrap_or(false),
+            "isEmpty" => {
+                let empty = list.map(|items| items.is_empty()).unwrap_or(false);
+
+                matches!(value, PrismaValue::Boolean(b) if *b == empty)
+            }
+            op => return Err(unsupported(&format!("`{op}` filters"))),
+        };
+
+        if !matched {
+            return Ok(false);
+        }
+    }
+
+    Ok(true)
+}
+
+fn relation(
+    ctx: &Context,
+    model: &Model,
+    record: &Record,
+    field: &RelationField,
+    filter: &PrismaValue,
+) -> Result<bool> {
+    let related_model = ctx.model(&field.related_model)?;
+    let related = ctx.related(model, field, record);
+
+    let filter = match filter {
+        PrismaValue::Null => return Ok(related.is_empty()),
+        PrismaValue::Object(filter) => filter,
+        _ => return Err(invalid("Expected a relation filter")),
+    };
+
+    let any = |value: &PrismaValue| -> Result<bool> {
+        for record in &related {
+            if matches_nested(ctx, related_model, record, value)? {
+                return Ok(true);
+            }
+        }
+
+        Ok(false)
+    };
+
+    let is_relation_filter = filter
+        .iter()
+        .all(|(k, _)| ["some", "every", "none", "is", "isNot"].contains(&k.as_str()));
+
+    // To-one relation filters can omit `is`
+    if !is_relation_filter {
+        return any(&PrismaValue::Object(filter.clone()));
+    }
+
+    for (op, value) in filter {
+        let matched = match (op.as_str(), value) {
+            ("is", PrismaValue::Null) => related.is_empty(),
+            ("isNot", PrismaValue::Null) => !related.is_empty(),
+            ("some" | "is", value) => any(value)?,
+            ("none" | "isNot", value) => !any(value)?,
+            (_, value) => {
+                let mut all = true;
+
+                for record in &related {
+                    if !matches_nested(ctx, related_model, record, value)? {
+                        all = false;
+                        break;
+                    }
+                }
+
+                all
+            }
+        };
+
+        if !matched {
+            return Ok(false);
+        }
+    }
+
+    Ok(true)
+}
+
+/// Whether the values of `fields` in `record` match those in `values`,
+/// used for finding records by foreign keys.
+pub fn fields_equal(record: &Record, fields: &[String], values: &[PrismaValue]) -> bool {
+    !values.iter().any(|v| matches!(v, PrismaValue::Null))
+        && fields
+            .iter()
+            .zip(values)
+            .all(|(field, value)| equals(record.get(field), value))
+}
+
+pub fn as_filter(value: &PrismaValue) -> Result<&Object> {
+    as_object(value).ok_or_else(|| invalid("Expected a filter object"))
+}
+
+pub fn invalid(message: &str) -> QueryError {
+    super::unknown(message.to_string())
+}
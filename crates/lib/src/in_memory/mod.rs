@@ -0,0 +1,629 @@
+//! An in-memory stand-in for the query engine, used by clients created with `PrismaClient::_in_memory`.
+//!
+//! Queries are executed directly against records stored in memory,
+//! using the validated schema to resolve relations, defaults and unique constraints.
+
+mod filter;
+mod read;
+mod schema;
+mod value;
+mod write;
+
+use std::{
+    collections::{HashMap, HashSet},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex, MutexGuard,
+    },
+};
+
+use prisma_models::PrismaValue;
+use query_core::{Operation, Selection, TxId};
+use serde_value::Value;
+use user_facing_errors::{query_engine::RecordRequiredButNotFound, KnownError, UserFacingError};
+
+use crate::{
+    prisma_value::Item, BatchMode, MiddlewareQuery, ModelOperation, ModelReadOperation,
+    ModelWriteOperation, QueryError, Result,
+};
+
+use filter::{fields_equal, invalid};
+use schema::{Model, RelationField, Schema};
+use value::{from_argument, get};
+use write::Writer;
+
+pub type Object = Vec<(String, PrismaValue)>;
+
+static NULL: PrismaValue = PrismaValue::Null;
+
+#[derive(Clone, Debug)]
+pub struct Record {
+    /// Internal identifier, stable across updates to the record's fields
+    id: u64,
+    values: HashMap<String, PrismaValue>,
+}
+
+impl Record {
+    pub fn get(&self, field: &str) -> &PrismaValue {
+        self.values.get(field).unwrap_or(&NULL)
+    }
+}
+
+#[derive(Clone, Default)]
+pub struct Data {
+    records: HashMap<String, Vec<Record>>,
+    /// Pairs of records in implicit many-to-many relations,
+    /// keyed by the relation field whose records come first in each pair.
+    links: HashMap<(String, String), Vec<(u64, u64)>>,
+    /// Last value generated for each `autoincrement()` field.
+    /// Shared with transactions' copies of the data, like sequences in a database,
+    /// so that concurrent transactions don't generate the same values.
+    sequences: Arc<Mutex<HashMap<(String, String), i64>>>,
+    /// Last internal id given to a record, shared in the same way as `sequences`
+    last_id: Arc<AtomicU64>,
+}
+
+impl Data {
+    fn records(&self, model: &str) -> impl Iterator<Item = &Record> {
+        self.records.get(model).into_iter().flatten()
+    }
+
+    fn contains(&self, model: &str, id: u64) -> bool {
+        self.records(model).any(|r| r.id == id)
+    }
+
+    fn record(&self, model: &str, id: u64) -> &Record {
+        self.records(model)
+            .find(|r| r.id == id)
+            .expect("Record ids are only taken from existing records")
+    }
+
+    fn record_mut(&mut self, model: &str, id: u64) -> &mut Record {
+        self.records
+            .get_mut(model)
+            .and_then(|records| records.iter_mut().find(|r| r.id == id))
+            .expect("Record ids are only taken from existing records")
+    }
+
+    fn insert(&mut self, model: &str, values: HashMap<String, PrismaValue>) -> u64 {
+        let id = self.last_id.fetch_add(1, Ordering::Relaxed) + 1;
+
+        self.records
+            .entry(model.to_string())
+            .or_default()
+            .push(Record { id, values });
+
+        id
+    }
+
+    fn remove(&mut self, model: &str, id: u64) {
+        if let Some(records) = self.records.get_mut(model) {
+            records.retain(|r| r.id != id);
+        }
+    }
+
+    fn linked(&self, model: &Model, field: &RelationField, id: u64) -> Vec<u64> {
+        let (key, first) = link_key(model, field);
+
+        self.links
+            .get(&key)
+            .into_iter()
+            .flatten()
+            .filter_map(|&(a, b)| match first {
+                true if a == id => Some(b),
+                false if b == id => Some(a),
+                _ => None,
+            })
+            .collect()
+    }
+
+    fn link(&mut self, model: &Model, field: &RelationField, id: u64, other: u64) {
+        let (key, first) = link_key(model, field);
+        let pair = if first { (id, other) } else { (other, id) };
+
+        let links = self.links.entry(key).or_default();
+
+        if !links.contains(&pair) {
+            links.push(pair);
+        }
+    }
+
+    /// Applies the changes made between `base` and `changes` to this data.
+    ///
+    /// Changes are applied record by record, so a record changed both here and in `changes`
+    /// takes the values from `changes`, and records deleted here stay deleted.
+    /// Unique constraints aren't checked again.
+    fn merge(&mut self, base: &Data, changes: Data) {
+        // Copies only ever gain models and relations, so everything in `base` is also in `changes`
+        for (model, records) in changes.records {
+            let base_records = base
+                .records(&model)
+                .map(|r| (r.id, &r.values))
+                .collect::<HashMap<_, _>>();
+            let ids = records.iter().map(|r| r.id).collect::<HashSet<_>>();
+
+            for id in base_records.keys() {
+                if !ids.contains(id) {
+                    self.remove(&model, *id);
+                }
+            }
+
+            let current = self.records.entry(model).or_default();
+
+            for record in records {
+                match base_records.get(&record.id) {
+                    None => current.push(record),
+                    Some(values) if **values != record.values => {
+                        if let Some(existing) = current.iter_mut().find(|r| r.id == record.id) {
+                            existing.values = record.values;
+                        }
+                    }
+                    Some(_) => {}
+                }
+            }
+        }
+
+        for (key, pairs) in changes.links {
+            let base_pairs = base.links.get(&key).map(Vec::as_slice).unwrap_or_default();
+            let current = self.links.entry(key).or_default();
+
+            current.retain(|pair| !base_pairs.contains(pair) || pairs.contains(pair));
+
+            for pair in pairs {
+                if !base_pairs.contains(&pair) && !current.contains(&pair) {
+                    current.push(pair);
+                }
+            }
+        }
+    }
+
+    fn unlink(&mut self, model: &Model, field: &RelationField, id: u64, other: Option<u64>) {
+        let (key, first) = link_key(model, field);
+
+        if let Some(links) = self.links.get_mut(&key) {
+            links.retain(|&(a, b)| {
+                let (this, that) = if first { (a, b) } else { (b, a) };
+
+                this != id || other.map(|other| other != that).unwrap_or(false)
+            });
+        }
+    }
+}
+
+/// The key of an implicit many-to-many relation's links, and whether `field` is the first side of it.
+fn link_key(model: &Model, field: &RelationField) -> ((String, String), bool) {
+    let this = (model.name.clone(), field.name.clone());
+    let that = (field.related_model.clone(), field.opposite.clone());
+
+    match this <= that {
+        true => (this, true),
+        false => (that, false),
+    }
+}
+
+/// Read-only access to the schema and data of a store.
+pub struct Context<'a> {
+    schema: &'a Schema,
+    data: &'a Data,
+}
+
+impl<'a> Context<'a> {
+    pub fn model(&self, name: &str) -> Result<&'a Model> {
+        find_model(self.schema, name)
+    }
+
+    pub fn records(&self, model: &str) -> Vec<&'a Record> {
+        self.data.records(model).collect()
+    }
+
+    /// Records connected to `record` through `field`.
+    pub fn related(
+        &self,
+        model: &Model,
+        field: &RelationField,
+        record: &Record,
+    ) -> Vec<&'a Record> {
+        let opposite = self.schema.opposite(field);
+        let records = self.data.records(&field.related_model);
+
+        if !field.fields.is_empty() {
+            let values = field
+                .fields
+                .iter()
+                .map(|f| record.get(f).clone())
+                .collect::<Vec<_>>();
+
+            records
+                .filter(|r| fields_equal(r, &field.references, &values))
+                .collect()
+        } else if !opposite.fields.is_empty() {
+            let values = opposite
+                .references
+                .iter()
+                .map(|f| record.get(f).clone())
+                .collect::<Vec<_>>();
+
+            records
+                .filter(|r| fields_equal(r, &opposite.fields, &values))
+                .collect()
+        } else {
+            let ids = self.data.linked(model, field, record.id);
+
+            records.filter(|r| ids.contains(&r.id)).collect()
+        }
+    }
+}
+
+fn find_model<'a>(schema: &'a Schema, name: &str) -> Result<&'a Model> {
+    schema
+        .model(name)
+        .ok_or_else(|| invalid(&format!("Unknown model `{name}`")))
+}
+
+/// An open transaction's copy of the data, along with the data it was copied from
+struct Transaction {
+    base: Data,
+    data: Data,
+}
+
+#[derive(Default)]
+struct State {
+    data: Data,
+    /// Copies of the data being modified by each open transaction,
+    /// whose changes are merged into `data` when the transaction is committed.
+    transactions: HashMap<TxId, Transaction>,
+    /// Snapshots of each transaction's data taken by nested transactions, most recent last
+    savepoints: HashMap<TxId, Vec<Data>>,
+    transaction_count: usize,
+}
+
+impl State {
+    fn data(&mut self, tx_id: Option<&TxId>) -> Result<&mut Data> {
+        match tx_id {
+            Some(tx_id) => self
+                .transactions
+                .get_mut(tx_id)
+                .map(|transaction| &mut transaction.data)
+                .ok_or_else(|| transaction_not_found(tx_id)),
+            None => Ok(&mut self.data),
+        }
+    }
+}
+
+/// Records stored by a client created with `PrismaClient::_in_memory`.
+///
+/// Transactions operate on their own copy of the data, and their changes are merged into the
+/// store's data when committed. Concurrent transactions don't see each other's changes,
+/// and when they change the same record the last one to commit wins.
+#[derive(Clone)]
+pub(crate) struct InMemoryStore {
+    schema: Arc<Schema>,
+    state: Arc<Mutex<State>>,
+}
+
+impl InMemoryStore {
+    pub fn new(datamodel: &str) -> Self {
+        Self {
+            schema: Arc::new(Schema::new(&psl::validate(datamodel.into()))),
+            state: Default::default(),
+        }
+    }
+
+    fn state(&self) -> MutexGuard<State> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    pub fn execute(&self, query: &MiddlewareQuery, tx_id: Option<&TxId>) -> Result<Value> {
+        let mut state = self.state();
+
+        atomically(state.data(tx_id)?, is_write(query), |data| {
+            execute(&self.schema, data, query)
+        })
+    }
+
+    pub fn execute_all(
+        &self,
+        queries: &[MiddlewareQuery],
+        mode: BatchMode,
+        tx_id: Option<&TxId>,
+    ) -> Result<Vec<Result<Value>>> {
+        let mut state = self.state();
+        let data = state.data(tx_id)?;
+
+        match (tx_id, mode) {
+            // Batches inside transactions are rolled back along with the transaction
+            (None, BatchMode::Transactional) => {
+                atomically(data, queries.iter().any(is_write), |data| {
+                    queries
+                        .iter()
+                        .map(|query| execute(&self.schema, data, query).map(Ok))
+                        .collect()
+                })
+            }
+            _ => Ok(queries
+                .iter()
+                .map(|query| {
+                    atomically(data, is_write(query), |data| {
+                        execute(&self.schema, data, query)
+                    })
+                })
+                .collect()),
+        }
+    }
+
+    pub fn begin(&self) -> TxId {
+        let mut state = self.state();
+
+        state.transaction_count += 1;
+
+        let tx_id: TxId = format!("in-memory-transaction-{}", state.transaction_count).into();
+        let base = state.data.clone();
+        let data = base.clone();
+
+        state
+            .transactions
+            .insert(tx_id.clone(), Transaction { base, data });
+
+        tx_id
+    }
+
+    pub fn commit(&self, tx_id: &TxId) -> Result<()> {
+        let mut state = self.state();
+
+        let transaction = state
+            .transactions
+            .remove(tx_id)
+            .ok_or_else(|| transaction_not_found(tx_id))?;
+        state.savepoints.remove(tx_id);
+
+        state.data.merge(&transaction.base, transaction.data);
+
+        Ok(())
+    }
+
+    pub fn rollback(&self, tx_id: &TxId) {
+        let mut state = self.state();
+
+        state.transactions.remove(tx_id);
+        state.savepoints.remove(tx_id);
+    }
+
+    pub fn savepoint(&self, tx_id: &TxId) -> Result<()> {
+        let mut state = self.state();
+
+        let snapshot = state.data(Some(tx_id))?.clone();
+
+        state
+            .savepoints
+            .entry(tx_id.clone())
+            .or_default()
+            .push(snapshot);
+
+        Ok(())
+    }
+
+    pub fn release_savepoint(&self, tx_id: &TxId) -> Result<()> {
+        self.state()
+            .savepoints
+            .get_mut(tx_id)
+            .and_then(|savepoints| savepoints.pop())
+            .map(|_| ())
+            .ok_or_else(|| transaction_not_found(tx_id))
+    }
+
+    pub fn rollback_to_savepoint(&self, tx_id: &TxId) -> Result<()> {
+        let mut state = self.state();
+
+        let snapshot = state
+            .savepoints
+            .get_mut(tx_id)
+            .and_then(|savepoints| savepoints.pop())
+            .ok_or_else(|| transaction_not_found(tx_id))?;
+
+        *state.data(Some(tx_id))? = snapshot;
+
+        Ok(())
+    }
+}
+
+fn is_write(query: &MiddlewareQuery) -> bool {
+    matches!(query.operation, Operation::Write(_))
+}
+
+/// Runs `f`, restoring `data` to its previous state if it fails.
+/// Reads can't modify the data, so it's only copied when `write` is true.
+fn atomically<T>(
+    data: &mut Data,
+    write: bool,
+    f: impl FnOnce(&mut Data) -> Result<T>,
+) -> Result<T> {
+    if !write {
+        return f(data);
+    }
+
+    let snapshot = data.clone();
+
+    let result = f(data);
+
+    if result.is_err() {
+        *data = snapshot;
+    }
+
+    result
+}
+
+fn execute(schema: &Schema, data: &mut Data, query: &MiddlewareQuery) -> Result<Value> {
+    let selection = match &query.operation {
+        Operation::Read(selection) | Operation::Write(selection) => selection,
+    };
+
+    let (action, model) = match (query.action(), query.model()) {
+        (Some(action), Some(model)) => (action, find_model(schema, model)?),
+        _ => return Err(unsupported("Raw queries")),
+    };
+
+    let args = arguments(selection)?;
+    let nested = selection.nested_selections();
+
+    let item = match action {
+        ModelOperation::Read(op) => {
+            let ctx = Context { schema, data };
+            let records = read::query(&ctx, model, ctx.records(&model.name), &args)?;
+
+            match op {
+                ModelReadOperation::FindMany => Item::List(
+                    records
+                        .into_iter()
+                        .map(|r| read::render(&ctx, model, r, nested))
+                        .collect::<Result<_>>()?,
+                ),
+                ModelReadOperation::Aggregate | ModelReadOperation::Count => {
+                    read::aggregate(&ctx, model, &records, nested)?
+                }
+                ModelReadOperation::GroupBy => return Err(unsupported("groupBy queries")),
+                // `*_or_throw` queries are sent as their regular counterparts,
+                // with missing records turned into `QueryError::NotFound` by the client
+                ModelReadOperation::FindUnique
+                | ModelReadOperation::FindUniqueOrThrow
+                | ModelReadOperation::FindFirst
+                | ModelReadOperation::FindFirstOrThrow => match records.first() {
+                    Some(record) => read::render(&ctx, model, record, nested)?,
+                    None => read::value(PrismaValue::Null),
+                },
+            }
+        }
+        ModelOperation::Write(op) => {
+            let mut writer = Writer { schema, data };
+
+            let id = match op {
+                ModelWriteOperation::Create => {
+                    writer.create(model, required(&args, "data")?, None)?
+                }
+                ModelWriteOperation::CreateMany => {
+                    let skip_duplicates = matches!(
+                        get(&args, "skipDuplicates"),
+                        Some(PrismaValue::Boolean(true))
+                    );
+
+                    let count =
+                        writer.create_many(model, required(&args, "data")?, skip_duplicates)?;
+
+                    return count_result(count);
+                }
+                ModelWriteOperation::Update => {
+                    let id = writer
+                        .find_unique(model, required(&args, "where")?)?
+                        .ok_or_else(|| not_found("Record to update not found."))?;
+
+                    writer.update(model, id, required(&args, "data")?)?;
+
+                    id
+                }
+                ModelWriteOperation::UpdateMany => {
+                    let ids = writer.find(model, get(&args, "where"))?;
+
+                    for id in &ids {
+                        writer.update(model, *id, required(&args, "data")?)?;
+                    }
+
+                    return count_result(ids.len());
+                }
+                ModelWriteOperation::Upsert => {
+                    match writer.find_unique(model, required(&args, "where")?)? {
+                        Some(id) => {
+                            writer.update(model, id, required(&args, "update")?)?;
+
+                            id
+                        }
+                        None => writer.create(model, required(&args, "create")?, None)?,
+                    }
+                }
+                ModelWriteOperation::Delete => {
+                    let id = writer
+                        .find_unique(model, required(&args, "where")?)?
+                        .ok_or_else(|| not_found("Record to delete does not exist."))?;
+
+                    // Deleted records are returned as they were before being deleted
+                    let ctx = Context {
+                        schema,
+                        data: &*writer.data,
+                    };
+                    let item = read::render(&ctx, model, ctx.data.record(&model.name, id), nested)?;
+
+                    writer.delete(model, id)?;
+
+                    return to_value(item);
+                }
+                ModelWriteOperation::DeleteMany => {
+                    let ids = writer.find(model, get(&args, "where"))?;
+
+                    for id in &ids {
+                        writer.delete(model, *id)?;
+                    }
+
+                    return count_result(ids.len());
+                }
+            };
+
+            let ctx = Context {
+                schema,
+                data: &*writer.data,
+            };
+
+            read::render(&ctx, model, ctx.data.record(&model.name, id), nested)?
+        }
+    };
+
+    to_value(item)
+}
+
+fn count_result(count: usize) -> Result<Value> {
+    let mut map = indexmap::IndexMap::new();
+
+    map.insert(
+        "count".to_string(),
+        read::value(PrismaValue::Int(count as i64)),
+    );
+
+    to_value(Item::Map(map))
+}
+
+fn to_value(item: Item) -> Result<Value> {
+    serde_value::to_value(item)
+        .map_err(|e| e.to_string())
+        .map_err(QueryError::Deserialize)
+}
+
+fn arguments(selection: &Selection) -> Result<Object> {
+    selection
+        .arguments()
+        .iter()
+        .map(|(key, value)| Ok((key.clone(), from_argument(value.clone())?)))
+        .collect()
+}
+
+fn required<'a>(args: &'a Object, key: &str) -> Result<&'a PrismaValue> {
+    get(args, key).ok_or_else(|| invalid(&format!("Argument `{key}` is missing.")))
+}
+
+fn unknown(message: String) -> QueryError {
+    QueryError::Execute(user_facing_errors::Error::new_non_panic_with_current_backtrace(message))
+}
+
+fn known(error: impl UserFacingError) -> QueryError {
+    QueryError::Execute(KnownError::new(error).into())
+}
+
+fn unsupported(what: &str) -> QueryError {
+    unknown(format!("{what} are not supported by the in-memory engine"))
+}
+
+fn not_found(cause: &str) -> QueryError {
+    known(RecordRequiredButNotFound {
+        cause: cause.to_string(),
+    })
+}
+
+fn transaction_not_found(tx_id: &TxId) -> QueryError {
+    unknown(format!("Transaction {tx_id} not found"))
+}
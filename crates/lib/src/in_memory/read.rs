@@ -0,0 +1,388 @@
+use std::cmp::Ordering;
+
+use bigdecimal::{BigDecimal, ToPrimitive};
+use indexmap::IndexMap;
+use prisma_models::PrismaValue;
+use query_core::Selection;
+
+use super::{
+    arguments,
+    filter::{as_filter, invalid, matches},
+    schema::{Model, ScalarKind},
+    unsupported,
+    value::{as_i64, compare, get, one_or_many},
+    Context, Object, Record,
+};
+use crate::{prisma_value::Item, Result};
+
+/// Applies the `where`, `orderBy`, `cursor`, `distinct`, `skip` and `take` arguments to `records`.
+pub fn query<'a>(
+    ctx: &Context,
+    model: &Model,
+    records: Vec<&'a Record>,
+    args: &Object,
+) -> Result<Vec<&'a Record>> {
+    let mut filtered = vec![];
+
+    match get(args, "where") {
+        Some(filter) => {
+            let filter = as_filter(filter)?;
+
+            for record in records {
+                if matches(ctx, model, record, filter)? {
+                    filtered.push(record);
+                }
+            }
+        }
+        None => filtered = records,
+    }
+
+    if let Some(order_by) = get(args, "orderBy") {
+        let order_by = one_or_many(order_by);
+
+        let mut keyed = filtered
+            .into_iter()
+            .map(|record| {
+                let keys = order_by
+                    .iter()
+                    .map(|entry| sort_key(ctx, model, record, entry))
+                    .collect::<Result<Vec<_>>>()?;
+
+                Ok((keys, record))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        keyed.sort_by(|(a, _), (b, _)| {
+            a.iter()
+                .zip(b)
+                .map(|(a, b)| a.cmp(b))
+                .find(|o| o.is_ne())
+                .unwrap_or(Ordering::Equal)
+        });
+
+        filtered = keyed.into_iter().map(|(_, record)| record).collect();
+    }
+
+    let take = get(args, "take").and_then(as_i64);
+
+    if let Some(cursor) = get(args, "cursor") {
+        let cursor = as_filter(cursor)?;
+
+        let mut position = None;
+
+        for (i, record) in filtered.iter().enumerate() {
+            if matches(ctx, model, record, cursor)? {
+                position = Some(i);
+                break;
+            }
+        }
+
+        match (position, take) {
+            (None, _) => return Ok(vec![]),
+            (Some(i), Some(take)) if take < 0 => filtered.truncate(i + 1),
+            (Some(i), _) => {
+                filtered.drain(..i);
+            }
+        }
+    }
+
+    if let Some(distinct) = get(args, "distinct") {
+        let fields = one_or_many(distinct)
+            .into_iter()
+            .filter_map(|f| match f {
+                PrismaValue::Enum(f) | PrismaValue::String(f) => Some(f.as_str()),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+
+        let mut seen: Vec<Vec<&PrismaValue>> = vec![];
+
+        filtered.retain(|record| {
+            let values = fields.iter().map(|f| record.get(f)).collect::<Vec<_>>();
+
+            let duplicate = seen.iter().any(|other| {
+                other
+                    .iter()
+                    .zip(&values)
+                    .all(|(a, b)| compare(a, b) == Some(Ordering::Equal))
+            });
+
+            if !duplicate {
+                seen.push(values);
+            }
+
+            !duplicate
+        });
+    }
+
+    let skip = get(args, "skip").and_then(as_i64).unwrap_or(0).max(0) as usize;
+
+    Ok(match take {
+        Some(take) if take < 0 => {
+            let end = filtered.len().saturating_sub(skip);
+            let start = end.saturating_sub(take.unsigned_abs() as usize);
+
+            filtered[start..end].to_vec()
+        }
+        take => filtered
+            .into_iter()
+            .skip(skip)
+            .take(take.map(|t| t as usize).unwrap_or(usize::MAX))
+            .collect(),
+    })
+}
+
+/// A value to sort by, along with the direction and null placement to sort it with.
+struct SortKey {
+    value: PrismaValue,
+    descending: bool,
+    nulls_last: bool,
+}
+
+impl SortKey {
+    fn cmp(&self, other: &Self) -> Ordering {
+        let ordering = match (&self.value, &other.value) {
+            (PrismaValue::Null, PrismaValue::Null) => Ordering::Equal,
+            (PrismaValue::Null, _) => return null_ordering(self.nulls_last),
+            (_, PrismaValue::Null) => return null_ordering(self.nulls_last).reverse(),
+            (a, b) => compare(a, b).unwrap_or(Ordering::Equal),
+        };
+
+        match self.descending {
+            true => ordering.reverse(),
+            false => ordering,
+        }
+    }
+}
+
+fn null_ordering(nulls_last: bool) -> Ordering {
+    match nulls_last {
+        true => Ordering::Greater,
+        false => Ordering::Less,
+    }
+}
+
+fn direction(value: &PrismaValue) -> Result<(bool, Option<bool>)> {
+    Ok(match value {
+        PrismaValue::Enum(dir) | PrismaValue::String(dir) => (dir == "desc", None),
+        PrismaValue::Object(sort) => {
+            let (descending, _) =
+                direction(get(sort, "sort").ok_or_else(|| invalid("Expected a sort order"))?)?;
+
+            let nulls_last = get(sort, "nulls").map(|nulls| {
+                matches!(nulls, PrismaValue::Enum(n) | PrismaValue::String(n) if n == "last")
+            });
+
+            (descending, nulls_last)
+        }
+        _ => return Err(invalid("Expected a sort order")),
+    })
+}
+
+fn sort_key(ctx: &Context, model: &Model, record: &Record, entry: &PrismaValue) -> Result<SortKey> {
+    let entry = as_filter(entry)?;
+
+    let (key, value) = match entry.as_slice() {
+        [(key, value)] => (key, value),
+        _ => return Err(invalid("Expected a single field to order by")),
+    };
+
+    if model.scalar_field(key).is_some() {
+        let (descending, nulls_last) = direction(value)?;
+
+        return Ok(SortKey {
+            value: record.get(key).clone(),
+            descending,
+            // Nulls are considered the smallest values by default
+            nulls_last: nulls_last.unwrap_or(descending),
+        });
+    }
+
+    let field = model
+        .relation_field(key)
+        .ok_or_else(|| invalid(&format!("Unknown field `{key}` on model `{}`", model.name)))?;
+    let related = ctx.related(model, field, record);
+
+    let nested = as_filter(value)?;
+
+    // Ordering by the number of related records, eg. `{ posts: { _count: asc } }`
+    if let Some(count_direction) = get(nested, "_count") {
+        let (descending, _) = direction(count_direction)?;
+
+        return Ok(SortKey {
+            value: PrismaValue::Int(related.len() as i64),
+            descending,
+            nulls_last: false,
+        });
+    }
+
+    let related_model = ctx.model(&field.related_model)?;
+
+    match related.first() {
+        Some(related) => sort_key(ctx, related_model, related, value),
+        None => Ok(SortKey {
+            value: PrismaValue::Null,
+            descending: false,
+            nulls_last: false,
+        }),
+    }
+}
+
+/// Converts `record` into the shape requested by the nested selections of a query.
+pub fn render(
+    ctx: &Context,
+    model: &Model,
+    record: &Record,
+    selections: &[Selection],
+) -> Result<Item> {
+    let mut map = IndexMap::new();
+
+    for selection in selections {
+        let name: &str = selection.name();
+
+        let item = if name == "_count" {
+            let mut counts = IndexMap::new();
+
+            for count in selection.nested_selections() {
+                let field = model.relation_field(count.name()).ok_or_else(|| {
+                    invalid(&format!(
+                        "Cannot count `{}` on `{}`",
+                        count.name(),
+                        model.name
+                    ))
+                })?;
+
+                let related = ctx.related(model, field, record);
+                let related = query(
+                    ctx,
+                    ctx.model(&field.related_model)?,
+                    related,
+                    &arguments(count)?,
+                )?;
+
+                counts.insert(
+                    count.name().to_string(),
+                    value(PrismaValue::Int(related.len() as i64)),
+                );
+            }
+
+            Item::Map(counts)
+        } else if model.scalar_field(name).is_some() {
+            value(record.get(name).clone())
+        } else if let Some(field) = model.relation_field(name) {
+            let related_model = ctx.model(&field.related_model)?;
+            let related = ctx.related(model, field, record);
+
+            match field.list {
+                true => Item::List(
+                    query(ctx, related_model, related, &arguments(selection)?)?
+                        .into_iter()
+                        .map(|r| render(ctx, related_model, r, selection.nested_selections()))
+                        .collect::<Result<_>>()?,
+                ),
+                false => match related.first() {
+                    Some(r) => render(ctx, related_model, r, selection.nested_selections())?,
+                    None => value(PrismaValue::Null),
+                },
+            }
+        } else {
+            return Err(invalid(&format!(
+                "Unknown field `{name}` on model `{}`",
+                model.name
+            )));
+        };
+
+        map.insert(name.to_string(), item);
+    }
+
+    Ok(Item::Map(map))
+}
+
+pub fn value(value: PrismaValue) -> Item {
+    Item::Value(value.into())
+}
+
+/// Executes an `aggregate` query, supporting `_count`, `_min`, `_max`, `_sum` and `_avg`.
+pub fn aggregate(
+    ctx: &Context,
+    model: &Model,
+    records: &[&Record],
+    selections: &[Selection],
+) -> Result<Item> {
+    let mut map = IndexMap::new();
+
+    for selection in selections {
+        let aggregate: &str = selection.name();
+        let mut fields = IndexMap::new();
+
+        for field in selection.nested_selections() {
+            let name: &str = field.name();
+
+            let values = || {
+                records
+                    .iter()
+                    .map(|r| r.get(name))
+                    .filter(|v| !matches!(v, PrismaValue::Null))
+            };
+
+            let kind = model.scalar_field(name).map(|f| f.kind);
+
+            if name != "_all" && kind.is_none() {
+                return Err(invalid(&format!(
+                    "Unknown field `{name}` on model `{}`",
+                    model.name
+                )));
+            }
+
+            let result = match aggregate {
+                "_count" if name == "_all" => PrismaValue::Int(records.len() as i64),
+                "_count" => PrismaValue::Int(values().count() as i64),
+                "_min" => values()
+                    .min_by(|a, b| compare(a, b).unwrap_or(Ordering::Equal))
+                    .cloned()
+                    .unwrap_or(PrismaValue::Null),
+                "_max" => values()
+                    .max_by(|a, b| compare(a, b).unwrap_or(Ordering::Equal))
+                    .cloned()
+                    .unwrap_or(PrismaValue::Null),
+                "_sum" | "_avg" => {
+                    let numbers = values()
+                        .map(|v| match v {
+                            PrismaValue::Int(v) | PrismaValue::BigInt(v) => {
+                                Some(BigDecimal::from(*v))
+                            }
+                            PrismaValue::Float(v) => Some(v.clone()),
+                            _ => None,
+                        })
+                        .collect::<Option<Vec<_>>>()
+                        .ok_or_else(|| invalid(&format!("Cannot aggregate `{name}`")))?;
+
+                    if numbers.is_empty() {
+                        PrismaValue::Null
+                    } else {
+                        let count = numbers.len();
+                        let sum = numbers.into_iter().fold(BigDecimal::from(0), |a, b| a + b);
+
+                        match (aggregate, kind) {
+                            ("_avg", _) => PrismaValue::Float(sum / BigDecimal::from(count as i64)),
+                            (_, Some(ScalarKind::Int)) => {
+                                PrismaValue::Int(sum.to_i64().unwrap_or_default())
+                            }
+                            (_, Some(ScalarKind::BigInt)) => {
+                                PrismaValue::BigInt(sum.to_i64().unwrap_or_default())
+                            }
+                            _ => PrismaValue::Float(sum),
+                        }
+                    }
+                }
+                other => return Err(unsupported(&format!("`{other}` aggregates"))),
+            };
+
+            fields.insert(name.to_string(), value(result));
+        }
+
+        map.insert(aggregate.to_string(), Item::Map(fields));
+    }
+
+    Ok(Item::Map(map))
+}
@@ -0,0 +1,260 @@
+use std::collections::HashMap;
+
+use bigdecimal::BigDecimal;
+use chrono::DateTime;
+use prisma_models::{
+    walkers::{ModelWalker, RelationFieldWalker, ScalarFieldWalker},
+    PrismaValue,
+};
+use psl::{
+    parser_database::{ast::Expression, ReferentialAction, ScalarFieldType, ScalarType},
+    ValidatedSchema,
+};
+
+/// How a field's value is generated when it isn't provided on create.
+#[derive(Clone, Debug)]
+pub enum DefaultValue {
+    Cuid,
+    Uuid,
+    Now,
+    Autoincrement,
+    Value(PrismaValue),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ScalarKind {
+    Int,
+    BigInt,
+    Float,
+    Decimal,
+    Boolean,
+    String,
+    DateTime,
+    Json,
+    Bytes,
+    Enum,
+    Other,
+}
+
+/// What happens to records referencing a record when it is deleted.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OnDelete {
+    Cascade,
+    SetNull,
+    Restrict,
+}
+
+#[derive(Debug)]
+pub struct ScalarField {
+    pub name: String,
+    pub kind: ScalarKind,
+    pub optional: bool,
+    pub list: bool,
+    pub default: Option<DefaultValue>,
+    pub updated_at: bool,
+}
+
+#[derive(Debug)]
+pub struct RelationField {
+    pub name: String,
+    /// Name of the relation, used in errors
+    pub relation: String,
+    pub related_model: String,
+    pub list: bool,
+    pub optional: bool,
+    /// Scalar fields on this model that reference the related model, if this side holds the foreign key
+    pub fields: Vec<String>,
+    /// Scalar fields on the related model referenced by `fields`
+    pub references: Vec<String>,
+    /// Name of the relation field on the related model
+    pub opposite: String,
+    /// Only meaningful on the side holding the foreign key
+    pub on_delete: OnDelete,
+}
+
+impl RelationField {
+    /// Whether the relation is an implicit many-to-many relation,
+    /// stored separately from the records on either side.
+    pub fn is_implicit_many_to_many(&self, opposite: &RelationField) -> bool {
+        self.fields.is_empty() && opposite.fields.is_empty()
+    }
+}
+
+#[derive(Debug)]
+pub struct Model {
+    pub name: String,
+    pub scalar_fields: Vec<ScalarField>,
+    pub relation_fields: Vec<RelationField>,
+    /// Sets of fields that uniquely identify a record
+    pub uniques: Vec<Vec<String>>,
+}
+
+impl Model {
+    pub fn scalar_field(&self, name: &str) -> Option<&ScalarField> {
+        self.scalar_fields.iter().find(|f| f.name == name)
+    }
+
+    pub fn relation_field(&self, name: &str) -> Option<&RelationField> {
+        self.relation_fields.iter().find(|f| f.name == name)
+    }
+}
+
+/// The parts of a Prisma schema needed to execute queries in memory.
+#[derive(Debug)]
+pub struct Schema {
+    models: HashMap<String, Model>,
+}
+
+impl Schema {
+    pub fn new(schema: &ValidatedSchema) -> Self {
+        Self {
+            models: schema
+                .db
+                .walk_models()
+                .map(|model| (model.name().to_string(), build_model(model)))
+                .collect(),
+        }
+    }
+
+    pub fn model(&self, name: &str) -> Option<&Model> {
+        self.models.get(name)
+    }
+
+    /// The relation field on the other side of `field`.
+    pub fn opposite(&self, field: &RelationField) -> &RelationField {
+        self.models[&field.related_model]
+            .relation_field(&field.opposite)
+            .expect("Relation fields always have an opposite field")
+    }
+}
+
+fn build_model(model: ModelWalker) -> Model {
+    let mut uniques = vec![];
+
+    if let Some(primary_key) = model.primary_key() {
+        uniques.push(primary_key.fields().map(|f| f.name().to_string()).collect());
+    }
+
+    uniques.extend(model.indexes().filter(|i| i.is_unique()).map(|index| {
+        index
+            .fields()
+            .filter_map(|f| f.as_scalar_field())
+            .map(|f| f.name().to_string())
+            .collect()
+    }));
+
+    Model {
+        name: model.name().to_string(),
+        scalar_fields: model.scalar_fields().map(build_scalar_field).collect(),
+        relation_fields: model.relation_fields().map(build_relation_field).collect(),
+        uniques,
+    }
+}
+
+fn build_scalar_field(field: ScalarFieldWalker) -> ScalarField {
+    let kind = match field.scalar_field_type() {
+        ScalarFieldType::BuiltInScalar(typ) => match typ {
+            ScalarType::Int => ScalarKind::Int,
+            ScalarType::BigInt => ScalarKind::BigInt,
+            ScalarType::Float => ScalarKind::Float,
+            ScalarType::Decimal => ScalarKind::Decimal,
+            ScalarType::Boolean => ScalarKind::Boolean,
+            ScalarType::String => ScalarKind::String,
+            ScalarType::DateTime => ScalarKind::DateTime,
+            ScalarType::Json => ScalarKind::Json,
+            ScalarType::Bytes => ScalarKind::Bytes,
+        },
+        ScalarFieldType::Enum(_) => ScalarKind::Enum,
+        _ => ScalarKind::Other,
+    };
+
+    let default = field.default_value().and_then(|default| {
+        if default.is_cuid() {
+            Some(DefaultValue::Cuid)
+        } else if default.is_uuid() {
+            Some(DefaultValue::Uuid)
+        } else if default.is_now() {
+            Some(DefaultValue::Now)
+        } else if default.is_autoincrement() {
+            Some(DefaultValue::Autoincrement)
+        } else {
+            literal(default.value(), kind).map(DefaultValue::Value)
+        }
+    });
+
+    ScalarField {
+        name: field.name().to_string(),
+        kind,
+        optional: field.ast_field().arity.is_optional(),
+        list: field.ast_field().arity.is_list(),
+        default,
+        updated_at: field.is_updated_at(),
+    }
+}
+
+fn build_relation_field(field: RelationFieldWalker) -> RelationField {
+    let optional = field.ast_field().arity.is_optional();
+
+    let on_delete = match field.explicit_on_delete() {
+        Some(ReferentialAction::Cascade) => OnDelete::Cascade,
+        Some(ReferentialAction::SetNull) => OnDelete::SetNull,
+        Some(_) => OnDelete::Restrict,
+        None if optional => OnDelete::SetNull,
+        None => OnDelete::Restrict,
+    };
+
+    RelationField {
+        name: field.name().to_string(),
+        relation: field.relation_name().to_string(),
+        related_model: field.related_model().name().to_string(),
+        list: field.ast_field().arity.is_list(),
+        optional,
+        fields: field
+            .fields()
+            .map(|fields| fields.map(|f| f.name().to_string()).collect())
+            .unwrap_or_default(),
+        references: field
+            .referenced_fields()
+            .map(|fields| fields.map(|f| f.name().to_string()).collect())
+            .unwrap_or_default(),
+        opposite: field
+            .opposite_relation_field()
+            .map(|f| f.name().to_string())
+            .unwrap_or_default(),
+        on_delete,
+    }
+}
+
+/// Converts a literal default value from the schema into a value of the field's type.
+/// Functions that can't be evaluated in memory, such as `dbgenerated()`, return `None`.
+fn literal(expression: &Expression, kind: ScalarKind) -> Option<PrismaValue> {
+    Some(match expression {
+        Expression::NumericValue(value, _) => match kind {
+            ScalarKind::Int => PrismaValue::Int(value.parse().ok()?),
+            ScalarKind::BigInt => PrismaValue::BigInt(value.parse().ok()?),
+            ScalarKind::Float | ScalarKind::Decimal => PrismaValue::Float(value.parse().ok()?),
+            _ => return None,
+        },
+        Expression::StringValue(value, _) => match kind {
+            ScalarKind::DateTime => {
+                PrismaValue::DateTime(DateTime::parse_from_rfc3339(value).ok()?)
+            }
+            ScalarKind::Json => PrismaValue::Json(value.clone()),
+            ScalarKind::Decimal => PrismaValue::Float(value.parse::<BigDecimal>().ok()?),
+            _ => PrismaValue::String(value.clone()),
+        },
+        Expression::ConstantValue(value, _) => match (kind, value.as_str()) {
+            (ScalarKind::Boolean, "true") => PrismaValue::Boolean(true),
+            (ScalarKind::Boolean, "false") => PrismaValue::Boolean(false),
+            (ScalarKind::Enum, value) => PrismaValue::Enum(value.to_string()),
+            _ => return None,
+        },
+        Expression::Array(values, _) => PrismaValue::List(
+            values
+                .iter()
+                .map(|value| literal(value, kind))
+                .collect::<Option<_>>()?,
+        ),
+        _ => return None,
+    })
+}
@@ -0,0 +1,101 @@
+use std::cmp::Ordering;
+
+use bigdecimal::BigDecimal;
+use prisma_models::PrismaValue;
+use query_core::ArgumentValue;
+
+use super::{unsupported, Object};
+use crate::Result;
+
+/// Flattens an argument from the client into a plain value,
+/// since objects and lists can appear as either argument or scalar values.
+pub fn from_argument(argument: ArgumentValue) -> Result<PrismaValue> {
+    Ok(match argument {
+        ArgumentValue::Scalar(value) => value,
+        ArgumentValue::Object(object) => PrismaValue::Object(
+            object
+                .into_iter()
+                .map(|(k, v)| Ok((k, from_argument(v)?)))
+                .collect::<Result<_>>()?,
+        ),
+        ArgumentValue::List(values) => PrismaValue::List(
+            values
+                .into_iter()
+                .map(from_argument)
+                .collect::<Result<_>>()?,
+        ),
+        _ => return Err(unsupported("field references")),
+    })
+}
+
+pub fn as_object(value: &PrismaValue) -> Option<&Object> {
+    match value {
+        PrismaValue::Object(object) => Some(object),
+        _ => None,
+    }
+}
+
+pub fn get<'a>(object: &'a Object, key: &str) -> Option<&'a PrismaValue> {
+    object.iter().find(|(k, _)| k == key).map(|(_, v)| v)
+}
+
+/// Values that are either a single item or a list of items, such as `AND` or nested `create`.
+pub fn one_or_many(value: &PrismaValue) -> Vec<&PrismaValue> {
+    match value {
+        PrismaValue::List(values) => values.iter().collect(),
+        value => vec![value],
+    }
+}
+
+fn decimal(value: &PrismaValue) -> Option<BigDecimal> {
+    match value {
+        PrismaValue::Int(v) | PrismaValue::BigInt(v) => Some(BigDecimal::from(*v)),
+        PrismaValue::Float(v) => Some(v.clone()),
+        _ => None,
+    }
+}
+
+/// Compares two values of the same type, with nulls sorting first.
+/// Returns `None` for values that can't be compared.
+pub fn compare(a: &PrismaValue, b: &PrismaValue) -> Option<Ordering> {
+    use PrismaValue::*;
+
+    Some(match (a, b) {
+        (Null, Null) => Ordering::Equal,
+        (Null, _) => Ordering::Less,
+        (_, Null) => Ordering::Greater,
+        (String(a), String(b)) | (Enum(a), Enum(b)) | (Json(a), Json(b)) => a.cmp(b),
+        (Enum(a), String(b)) | (String(a), Enum(b)) => a.cmp(b),
+        (Boolean(a), Boolean(b)) => a.cmp(b),
+        (Int(a), Int(b)) | (BigInt(a), BigInt(b)) | (Int(a), BigInt(b)) | (BigInt(a), Int(b)) => {
+            a.cmp(b)
+        }
+        (DateTime(a), DateTime(b)) => a.cmp(b),
+        (Uuid(a), Uuid(b)) => a.cmp(b),
+        (Uuid(a), String(b)) => a.to_string().cmp(b),
+        (String(a), Uuid(b)) => a.cmp(&b.to_string()),
+        (Bytes(a), Bytes(b)) => a.cmp(b),
+        (List(a), List(b)) => {
+            for (a, b) in a.iter().zip(b) {
+                match compare(a, b)? {
+                    Ordering::Equal => continue,
+                    ordering => return Some(ordering),
+                }
+            }
+
+            a.len().cmp(&b.len())
+        }
+        (a, b) => decimal(a)?.cmp(&decimal(b)?),
+    })
+}
+
+pub fn equals(a: &PrismaValue, b: &PrismaValue) -> bool {
+    compare(a, b) == Some(Ordering::Equal)
+}
+
+pub fn as_i64(value: &PrismaValue) -> Option<i64> {
+    match value {
+        PrismaValue::Int(v) | PrismaValue::BigInt(v) => Some(*v),
+        _ => None,
+    }
+}
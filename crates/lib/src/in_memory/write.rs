@@ -0,0 +1,743 @@
+use std::collections::HashMap;
+
+use bigdecimal::BigDecimal;
+use prisma_models::PrismaValue;
+use user_facing_errors::query_engine::{DatabaseConstraint, RelationViolation, UniqueKeyViolation};
+use uuid::Uuid;
+
+use super::{
+    filter::{as_filter, fields_equal, invalid, matches},
+    find_model, known, not_found, required,
+    schema::{DefaultValue, Model, OnDelete, RelationField, ScalarField, ScalarKind, Schema},
+    unsupported,
+    value::{as_i64, as_object, get, one_or_many},
+    Context, Data, Record,
+};
+use crate::{QueryError, Result};
+
+/// Mutable access to the schema and data of a store.
+pub struct Writer<'a> {
+    pub schema: &'a Schema,
+    pub data: &'a mut Data,
+}
+
+impl<'a> Writer<'a> {
+    fn ctx(&self) -> Context<'_> {
+        Context {
+            schema: self.schema,
+            data: self.data,
+        }
+    }
+
+    fn model(&self, name: &str) -> Result<&'a Model> {
+        find_model(self.schema, name)
+    }
+
+    /// Ids of the records matching `filter`, or all records if there is no filter.
+    pub fn find(&self, model: &Model, filter: Option<&PrismaValue>) -> Result<Vec<u64>> {
+        let ctx = self.ctx();
+
+        filter_ids(&ctx, model, ctx.records(&model.name), filter)
+    }
+
+    pub fn find_unique(&self, model: &Model, filter: &PrismaValue) -> Result<Option<u64>> {
+        Ok(self.find(model, Some(filter))?.into_iter().next())
+    }
+
+    /// Ids of the records connected to a record through `field` that match `filter`.
+    fn find_related(
+        &self,
+        model: &Model,
+        id: u64,
+        field: &RelationField,
+        filter: Option<&PrismaValue>,
+    ) -> Result<Vec<u64>> {
+        let ctx = self.ctx();
+        let related = ctx.related(model, field, self.data.record(&model.name, id));
+
+        filter_ids(&ctx, ctx.model(&field.related_model)?, related, filter)
+    }
+
+    /// Creates a record from `input`, connecting it to `parent` if it is being created by a nested write.
+    pub fn create(
+        &mut self,
+        model: &'a Model,
+        input: &PrismaValue,
+        parent: Option<(&'a RelationField, u64)>,
+    ) -> Result<u64> {
+        let input = as_object(input).ok_or_else(|| invalid("Expected create data"))?;
+
+        let mut values = HashMap::new();
+        let mut nested = vec![];
+
+        for (key, value) in input {
+            if model.scalar_field(key).is_some() {
+                let value = match value {
+                    PrismaValue::Object(ops) => get(ops, "set")
+                        .cloned()
+                        .ok_or_else(|| invalid(&format!("Expected a value for `{key}`")))?,
+                    value => value.clone(),
+                };
+
+                values.insert(key.clone(), value);
+            } else if let Some(field) = model.relation_field(key) {
+                // Foreign keys held by this record need to be known before it can be inserted
+                match field.fields.is_empty() {
+                    true => nested.push((field, value)),
+                    false => {
+                        let target = self.create_target(field, value)?;
+
+                        values.extend(self.foreign_key(field, target));
+                    }
+                }
+            } else {
+                return Err(unknown_field(model, key));
+            }
+        }
+
+        if let Some((field, parent)) = parent {
+            if !field.fields.is_empty() {
+                values.extend(self.foreign_key(field, parent));
+            }
+        }
+
+        for field in &model.scalar_fields {
+            if values.contains_key(&field.name) {
+                continue;
+            }
+
+            let value = match &field.default {
+                Some(default) => self.generate(model, field, default),
+                None if field.updated_at => now(),
+                None if field.list => PrismaValue::List(vec![]),
+                None if field.optional => PrismaValue::Null,
+                None => return Err(invalid(&format!("Argument `{}` is missing.", field.name))),
+            };
+
+            values.insert(field.name.clone(), value);
+        }
+
+        self.check_unique(model, None, &values)?;
+
+        let id = self.data.insert(&model.name, values);
+
+        if let Some((field, parent)) = parent {
+            if field.fields.is_empty() {
+                self.connect(model, id, field, parent)?;
+            }
+        }
+
+        for (field, value) in nested {
+            self.write_relation(model, id, field, value)?;
+        }
+
+        Ok(id)
+    }
+
+    /// Creates a record for each item in `input`, returning how many were created.
+    pub fn create_many(
+        &mut self,
+        model: &'a Model,
+        input: &PrismaValue,
+        skip_duplicates: bool,
+    ) -> Result<usize> {
+        let mut count = 0;
+
+        for data in one_or_many(input) {
+            match self.create(model, data, None) {
+                Ok(_) => count += 1,
+                Err(e) if skip_duplicates && e.is_prisma_error::<UniqueKeyViolation>() => {}
+                Err(e) => return Err(e),
+            }
+        }
+
+        Ok(count)
+    }
+
+    /// Resolves the record a new record's foreign key will point to.
+    fn create_target(&mut self, field: &'a RelationField, value: &PrismaValue) -> Result<u64> {
+        let related_model = self.model(&field.related_model)?;
+
+        let (op, value) = as_object(value)
+            .and_then(|ops| ops.first())
+            .ok_or_else(|| invalid(&format!("Expected a nested write for `{}`", field.name)))?;
+
+        match op.as_str() {
+            "connect" => self.require(related_model, value),
+            "create" => self.create(related_model, value, None),
+            "connectOrCreate" => {
+                let (filter, data) = connect_or_create(value)?;
+
+                match self.find_unique(related_model, filter)? {
+                    Some(id) => Ok(id),
+                    None => self.create(related_model, data, None),
+                }
+            }
+            op => Err(unsupported(&format!("`{op}` writes on create"))),
+        }
+    }
+
+    /// Applies `input` to an existing record.
+    pub fn update(&mut self, model: &'a Model, id: u64, input: &PrismaValue) -> Result<()> {
+        let input = as_object(input).ok_or_else(|| invalid("Expected update data"))?;
+
+        let mut values = self.data.record(&model.name, id).values.clone();
+        let mut nested = vec![];
+
+        for (key, value) in input {
+            if let Some(field) = model.scalar_field(key) {
+                let current = values.remove(key).unwrap_or(PrismaValue::Null);
+
+                values.insert(key.clone(), update_value(field, current, value)?);
+            } else if let Some(field) = model.relation_field(key) {
+                nested.push((field, value));
+            } else {
+                return Err(unknown_field(model, key));
+            }
+        }
+
+        for field in &model.scalar_fields {
+            if field.updated_at && get(input, &field.name).is_none() {
+                values.insert(field.name.clone(), now());
+            }
+        }
+
+        self.check_unique(model, Some(id), &values)?;
+
+        self.data.record_mut(&model.name, id).values = values;
+
+        for (field, value) in nested {
+            self.write_relation(model, id, field, value)?;
+        }
+
+        Ok(())
+    }
+
+    /// Deletes a record, applying the referential actions of any relations referencing it.
+    pub fn delete(&mut self, model: &'a Model, id: u64) -> Result<()> {
+        // Records can be deleted by an earlier cascade
+        if !self.data.contains(&model.name, id) {
+            return Ok(());
+        }
+
+        let schema = self.schema;
+        let mut dependents = vec![];
+
+        for field in &model.relation_fields {
+            let opposite = schema.opposite(field);
+
+            // Records holding a foreign key don't have anything depending on them through it
+            if opposite.fields.is_empty() {
+                continue;
+            }
+
+            let ids = self.find_related(model, id, field, None)?;
+
+            if !ids.is_empty() && opposite.on_delete == OnDelete::Restrict {
+                return Err(relation_violation(model, field));
+            }
+
+            dependents.push((self.model(&field.related_model)?, opposite, ids));
+        }
+
+        for field in &model.relation_fields {
+            if field.is_implicit_many_to_many(schema.opposite(field)) {
+                self.data.unlink(model, field, id, None);
+            }
+        }
+
+        self.data.remove(&model.name, id);
+
+        for (related_model, opposite, ids) in dependents {
+            for dependent in ids {
+                match opposite.on_delete {
+                    OnDelete::Cascade => self.delete(related_model, dependent)?,
+                    _ if self.data.contains(&related_model.name, dependent) => {
+                        self.set(related_model, dependent, nulls(&opposite.fields))?
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Applies a nested write such as `connect` or `create` to the records connected through `field`.
+    fn write_relation(
+        &mut self,
+        model: &'a Model,
+        id: u64,
+        field: &'a RelationField,
+        value: &PrismaValue,
+    ) -> Result<()> {
+        let related_model = self.model(&field.related_model)?;
+        let opposite = self.schema.opposite(field);
+
+        let ops = as_object(value)
+            .ok_or_else(|| invalid(&format!("Expected a nested write for `{}`", field.name)))?;
+
+        for (op, value) in ops {
+            match op.as_str() {
+                "connect" => {
+                    for unique in one_or_many(value) {
+                        let target = self.require(related_model, unique)?;
+
+                        self.connect(model, id, field, target)?;
+                    }
+                }
+                "disconnect" => {
+                    let targets = match value {
+                        PrismaValue::Boolean(true) => self.find_related(model, id, field, None)?,
+                        PrismaValue::Boolean(false) => vec![],
+                        value => self.find_related_each(model, id, field, value)?,
+                    };
+
+                    for target in targets {
+                        self.disconnect(model, id, field, target)?;
+                    }
+                }
+                "set" => {
+                    for target in self.find_related(model, id, field, None)? {
+                        self.disconnect(model, id, field, target)?;
+                    }
+
+                    for unique in one_or_many(value) {
+                        let target = self.require(related_model, unique)?;
+
+                        self.connect(model, id, field, target)?;
+                    }
+                }
+                "create" => {
+                    for data in one_or_many(value) {
+                        self.create(related_model, data, Some((opposite, id)))?;
+                    }
+                }
+                "createMany" => {
+                    let input =
+                        as_object(value).ok_or_else(|| invalid("Expected createMany data"))?;
+                    let skip_duplicates = matches!(
+                        get(input, "skipDuplicates"),
+                        Some(PrismaValue::Boolean(true))
+                    );
+
+                    for data in one_or_many(required(input, "data")?) {
+                        match self.create(related_model, data, Some((opposite, id))) {
+                            Ok(_) => {}
+                            Err(e)
+                                if skip_duplicates && e.is_prisma_error::<UniqueKeyViolation>() => {
+                            }
+                            Err(e) => return Err(e),
+                        }
+                    }
+                }
+                "connectOrCreate" => {
+                    for item in one_or_many(value) {
+                        let (filter, data) = connect_or_create(item)?;
+
+                        match self.find_unique(related_model, filter)? {
+                            Some(target) => self.connect(model, id, field, target)?,
+                            None => {
+                                self.create(related_model, data, Some((opposite, id)))?;
+                            }
+                        }
+                    }
+                }
+                "update" if !field.list => {
+                    let target = self.require_related(model, id, field, None)?;
+
+                    self.update(related_model, target, value)?;
+                }
+                "update" | "updateMany" => {
+                    for item in one_or_many(value) {
+                        let item =
+                            as_object(item).ok_or_else(|| invalid("Expected update data"))?;
+                        let filter = get(item, "where");
+
+                        let targets = match op.as_str() {
+                            "update" => vec![self.require_related(model, id, field, filter)?],
+                            _ => self.find_related(model, id, field, filter)?,
+                        };
+
+                        for target in targets {
+                            self.update(related_model, target, required(item, "data")?)?;
+                        }
+                    }
+                }
+                "upsert" => {
+                    for item in one_or_many(value) {
+                        let item =
+                            as_object(item).ok_or_else(|| invalid("Expected upsert data"))?;
+
+                        let existing = self
+                            .find_related(model, id, field, get(item, "where"))?
+                            .into_iter()
+                            .next();
+
+                        match existing {
+                            Some(target) => {
+                                self.update(related_model, target, required(item, "update")?)?
+                            }
+                            None => {
+                                self.create(
+                                    related_model,
+                                    required(item, "create")?,
+                                    Some((opposite, id)),
+                                )?;
+                            }
+                        }
+                    }
+                }
+                "delete" => {
+                    let targets = match value {
+                        PrismaValue::Boolean(true) => {
+                            vec![self.require_related(model, id, field, None)?]
+                        }
+                        PrismaValue::Boolean(false) => vec![],
+                        value => one_or_many(value)
+                            .into_iter()
+                            .map(|unique| self.require_related(model, id, field, Some(unique)))
+                            .collect::<Result<_>>()?,
+                    };
+
+                    for target in targets {
+                        self.delete(related_model, target)?;
+                    }
+                }
+                "deleteMany" => {
+                    for target in self.find_related_each(model, id, field, value)? {
+                        self.delete(related_model, target)?;
+                    }
+                }
+                op => return Err(unsupported(&format!("`{op}` nested writes"))),
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Ids of the related records matching any of the filters in `filters`.
+    fn find_related_each(
+        &self,
+        model: &Model,
+        id: u64,
+        field: &RelationField,
+        filters: &PrismaValue,
+    ) -> Result<Vec<u64>> {
+        let mut ids = vec![];
+
+        for filter in one_or_many(filters) {
+            for target in self.find_related(model, id, field, Some(filter))? {
+                if !ids.contains(&target) {
+                    ids.push(target);
+                }
+            }
+        }
+
+        Ok(ids)
+    }
+
+    fn require(&self, model: &Model, filter: &PrismaValue) -> Result<u64> {
+        self.find_unique(model, filter)?.ok_or_else(|| {
+            not_found(&format!(
+                "Expected a `{}` record to connect, found none.",
+                model.name
+            ))
+        })
+    }
+
+    fn require_related(
+        &self,
+        model: &Model,
+        id: u64,
+        field: &RelationField,
+        filter: Option<&PrismaValue>,
+    ) -> Result<u64> {
+        self.find_related(model, id, field, filter)?
+            .into_iter()
+            .next()
+            .ok_or_else(|| {
+                not_found(&format!(
+                    "No `{}` record was found for a nested write on `{}.{}`.",
+                    field.related_model, model.name, field.name
+                ))
+            })
+    }
+
+    fn connect(
+        &mut self,
+        model: &'a Model,
+        id: u64,
+        field: &'a RelationField,
+        target: u64,
+    ) -> Result<()> {
+        let related_model = self.model(&field.related_model)?;
+        let opposite = self.schema.opposite(field);
+
+        if !field.fields.is_empty() {
+            let values = self.foreign_key(field, target);
+
+            self.set(model, id, values)
+        } else if !opposite.fields.is_empty() {
+            // One-to-one relations can only be connected to a single record at a time
+            if !field.list {
+                for existing in self.find_related(model, id, field, None)? {
+                    if existing != target {
+                        self.disconnect(model, id, field, existing)?;
+                    }
+                }
+            }
+
+            let values = self.foreign_key(opposite, id);
+
+            self.set(related_model, target, values)
+        } else {
+            self.data.link(model, field, id, target);
+
+            Ok(())
+        }
+    }
+
+    fn disconnect(
+        &mut self,
+        model: &'a Model,
+        id: u64,
+        field: &'a RelationField,
+        target: u64,
+    ) -> Result<()> {
+        let related_model = self.model(&field.related_model)?;
+        let opposite = self.schema.opposite(field);
+
+        if !field.fields.is_empty() {
+            if !field.optional {
+                return Err(relation_violation(model, field));
+            }
+
+            self.set(model, id, nulls(&field.fields))
+        } else if !opposite.fields.is_empty() {
+            if !opposite.optional {
+                return Err(relation_violation(model, field));
+            }
+
+            self.set(related_model, target, nulls(&opposite.fields))
+        } else {
+            self.data.unlink(model, field, id, Some(target));
+
+            Ok(())
+        }
+    }
+
+    /// Values for the foreign key fields of `field` that point to `target`.
+    fn foreign_key(&self, field: &RelationField, target: u64) -> Vec<(String, PrismaValue)> {
+        let target = self.data.record(&field.related_model, target);
+
+        field
+            .fields
+            .iter()
+            .zip(&field.references)
+            .map(|(field, reference)| (field.clone(), target.get(reference).clone()))
+            .collect()
+    }
+
+    /// Sets fields on a record without applying any other update logic.
+    fn set(&mut self, model: &Model, id: u64, values: Vec<(String, PrismaValue)>) -> Result<()> {
+        let mut updated = self.data.record(&model.name, id).values.clone();
+
+        updated.extend(values);
+
+        self.check_unique(model, Some(id), &updated)?;
+
+        self.data.record_mut(&model.name, id).values = updated;
+
+        Ok(())
+    }
+
+    fn check_unique(
+        &self,
+        model: &Model,
+        id: Option<u64>,
+        values: &HashMap<String, PrismaValue>,
+    ) -> Result<()> {
+        for fields in &model.uniques {
+            let key = fields
+                .iter()
+                .map(|f| values.get(f).cloned().unwrap_or(PrismaValue::Null))
+                .collect::<Vec<_>>();
+
+            let conflict = self
+                .data
+                .records(&model.name)
+                .any(|r| Some(r.id) != id && fields_equal(r, fields, &key));
+
+            if conflict {
+                return Err(known(UniqueKeyViolation {
+                    constraint: DatabaseConstraint::Fields(fields.clone()),
+                }));
+            }
+        }
+
+        Ok(())
+    }
+
+    fn generate(
+        &mut self,
+        model: &Model,
+        field: &ScalarField,
+        default: &DefaultValue,
+    ) -> PrismaValue {
+        match default {
+            DefaultValue::Cuid => {
+                PrismaValue::String(format!("c{}", &Uuid::new_v4().simple().to_string()[..24]))
+            }
+            DefaultValue::Uuid => PrismaValue::String(Uuid::new_v4().to_string()),
+            DefaultValue::Now => now(),
+            DefaultValue::Autoincrement => {
+                // Explicitly provided values move the sequence forward, like they do in Postgres and SQLite
+                let max = self
+                    .data
+                    .records(&model.name)
+                    .filter_map(|r| as_i64(r.get(&field.name)))
+                    .max()
+                    .unwrap_or(0);
+
+                let mut sequences = self
+                    .data
+                    .sequences
+                    .lock()
+                    .unwrap_or_else(|e| e.into_inner());
+                let sequence = sequences
+                    .entry((model.name.clone(), field.name.clone()))
+                    .or_default();
+
+                *sequence = (*sequence).max(max) + 1;
+
+                match field.kind {
+                    ScalarKind::BigInt => PrismaValue::BigInt(*sequence),
+                    _ => PrismaValue::Int(*sequence),
+                }
+            }
+            DefaultValue::Value(value) => value.clone(),
+        }
+    }
+}
+
+fn filter_ids(
+    ctx: &Context,
+    model: &Model,
+    records: Vec<&Record>,
+    filter: Option<&PrismaValue>,
+) -> Result<Vec<u64>> {
+    let filter = filter.map(as_filter).transpose()?;
+    let mut ids = vec![];
+
+    for record in records {
+        match filter {
+            Some(filter) if !matches(ctx, model, record, filter)? => {}
+            _ => ids.push(record.id),
+        }
+    }
+
+    Ok(ids)
+}
+
+/// Applies an update operation such as `set` or `increment` to a field's current value.
+fn update_value(
+    field: &ScalarField,
+    current: PrismaValue,
+    value: &PrismaValue,
+) -> Result<PrismaValue> {
+    let (op, operand) = match value {
+        PrismaValue::Object(ops) => match ops.as_slice() {
+            [(op, operand)] => (op.as_str(), operand),
+            _ => {
+                return Err(invalid(&format!(
+                    "Expected a single update for `{}`",
+                    field.name
+                )))
+            }
+        },
+        value => return Ok(value.clone()),
+    };
+
+    Ok(match op {
+        "set" => operand.clone(),
+        "unset" => PrismaValue::Null,
+        "push" => {
+            let mut items = match current {
+                PrismaValue::List(items) => items,
+                _ => vec![],
+            };
+
+            items.extend(one_or_many(operand).into_iter().cloned());
+
+            PrismaValue::List(items)
+        }
+        "increment" | "decrement" | "multiply" | "divide" => arithmetic(op, &current, operand)
+            .ok_or_else(|| invalid(&format!("Cannot {op} `{}` by {operand:?}", field.name)))?,
+        op => return Err(unsupported(&format!("`{op}` updates"))),
+    })
+}
+
+/// Numeric updates, which leave null values as null like SQL does.
+fn arithmetic(op: &str, current: &PrismaValue, operand: &PrismaValue) -> Option<PrismaValue> {
+    let integer = |a: i64, b: i64| match op {
+        "increment" => a.checked_add(b),
+        "decrement" => a.checked_sub(b),
+        "multiply" => a.checked_mul(b),
+        _ => a.checked_div(b),
+    };
+
+    Some(match current {
+        PrismaValue::Null => PrismaValue::Null,
+        PrismaValue::Int(a) => PrismaValue::Int(integer(*a, as_i64(operand)?)?),
+        PrismaValue::BigInt(a) => PrismaValue::BigInt(integer(*a, as_i64(operand)?)?),
+        PrismaValue::Float(a) => {
+            let b = match operand {
+                PrismaValue::Int(b) | PrismaValue::BigInt(b) => BigDecimal::from(*b),
+                PrismaValue::Float(b) => b.clone(),
+                _ => return None,
+            };
+
+            PrismaValue::Float(match op {
+                "increment" => a.clone() + b,
+                "decrement" => a.clone() - b,
+                "multiply" => a.clone() * b,
+                _ if b == BigDecimal::from(0) => return None,
+                _ => a.clone() / b,
+            })
+        }
+        _ => return None,
+    })
+}
+
+fn connect_or_create(value: &PrismaValue) -> Result<(&PrismaValue, &PrismaValue)> {
+    let value = as_object(value).ok_or_else(|| invalid("Expected connectOrCreate data"))?;
+
+    Ok((required(value, "where")?, required(value, "create")?))
+}
+
+fn nulls(fields: &[String]) -> Vec<(String, PrismaValue)> {
+    fields
+        .iter()
+        .map(|f| (f.clone(), PrismaValue::Null))
+        .collect()
+}
+
+fn now() -> PrismaValue {
+    PrismaValue::DateTime(chrono::Utc::now().into())
+}
+
+fn unknown_field(model: &Model, field: &str) -> QueryError {
+    invalid(&format!(
+        "Unknown field `{field}` on model `{}`",
+        model.name
+    ))
+}
+
+fn relation_violation(model: &Model, field: &RelationField) -> QueryError {
+    known(RelationViolation {
+        relation_name: field.relation.clone(),
+        model_a_name: model.name.clone(),
+        model_b_name: field.related_model.clone(),
+    })
+}
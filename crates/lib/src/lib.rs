@@ -1,6 +1,12 @@
 pub mod actions;
 mod client;
+mod connection;
+#[cfg(feature = "serde-filters")]
+pub mod filters;
 mod gen_macros;
+#[cfg(feature = "in-memory")]
+mod in_memory;
+mod middleware;
 #[cfg(feature = "migrations")]
 pub mod migrations;
 #[cfg(feature = "mocking")]
@@ -9,7 +15,9 @@ pub mod operator;
 mod prisma_value;
 pub mod queries;
 pub mod raw;
+mod replicas;
 pub mod serde;
+mod telemetry;
 mod traits;
 mod transaction;
 
@@ -22,6 +30,7 @@ pub use psl;
 pub use query_core;
 pub use query_core::{schema, Selection};
 pub use serde_json;
+pub use serde_value;
 use thiserror::Error;
 #[cfg(feature = "migrations")]
 pub use tokio;
@@ -29,6 +38,8 @@ pub use user_facing_errors as prisma_errors;
 
 pub use actions::*;
 pub use client::*;
+pub use connection::ConnectionOptions;
+pub use middleware::{MiddlewareQuery, MiddlewareRequest, MiddlewareResult, Next, QueryMiddleware};
 #[cfg(feature = "mocking")]
 pub use mock::*;
 pub use operator::Operator;
@@ -125,6 +136,16 @@ pub fn merge_fields(fields: Vec<(String, PrismaValue)>) -> Vec<(String, PrismaVa
     merged.into_iter().collect()
 }
 
+/// Creates the list of enum values passed as a `distinct` argument.
+pub(crate) fn distinct_fields(fields: Vec<impl Into<String>>) -> PrismaValue {
+    PrismaValue::List(
+        fields
+            .into_iter()
+            .map(|field| PrismaValue::Enum(field.into()))
+            .collect(),
+    )
+}
+
 pub fn sel(name: &str) -> Selection {
     Selection::new(name, None, [], [])
 }
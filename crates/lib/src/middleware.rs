@@ -0,0 +1,138 @@
+use futures::future::{BoxFuture, FutureExt};
+use query_core::{Operation, TxId};
+use serde_value::Value;
+
+use crate::{BatchMode, ExecutionEngine, ModelOperation, Result};
+
+/// A single operation on its way to the query engine.
+pub struct MiddlewareQuery {
+    /// The operation that will be executed.
+    /// Middleware can modify this to rewrite the query.
+    pub operation: Operation,
+    model: Option<&'static str>,
+    action: Option<ModelOperation>,
+}
+
+impl MiddlewareQuery {
+    /// Creates a query for `operation`, along with the model and operation it performs
+    /// if it operates on a model.
+    pub fn new(
+        operation: Operation,
+        model_operation: Option<(&'static str, ModelOperation)>,
+    ) -> Self {
+        Self {
+            operation,
+            model: model_operation.map(|(model, _)| model),
+            action: model_operation.map(|(_, action)| action),
+        }
+    }
+
+    /// Name of the query as sent to the query engine, eg. `findManyUser` or `executeRaw`.
+    pub fn name(&self) -> &str {
+        match &self.operation {
+            Operation::Read(selection) | Operation::Write(selection) => selection.name(),
+        }
+    }
+
+    /// Name of the model being queried, or `None` for raw queries.
+    pub fn model(&self) -> Option<&str> {
+        self.model
+    }
+
+    /// The model operation being performed, or `None` for raw queries.
+    pub fn action(&self) -> Option<ModelOperation> {
+        self.action
+    }
+}
+
+/// One call to the query engine, made up of either a single query or a batch.
+pub struct MiddlewareRequest {
+    pub queries: Vec<MiddlewareQuery>,
+    /// How the queries are executed if they are a batch.
+    /// `None` executes them one after the other as regular queries.
+    pub batch: Option<BatchMode>,
+    tx_id: Option<TxId>,
+}
+
+impl MiddlewareRequest {
+    /// ID of the interactive transaction the request is executing inside of, if any.
+    pub fn tx_id(&self) -> Option<&TxId> {
+        self.tx_id.as_ref()
+    }
+}
+
+/// The results of a [`MiddlewareRequest`], one per query in the same order as `queries`.
+/// The outer error is for failures of the request as a whole, such as a batch being rolled back.
+pub type MiddlewareResult = Result<Vec<Result<Value>>>;
+
+/// Intercepts every request made to the query engine,
+/// including batches and queries executed inside transactions.
+///
+/// Middleware receives each request along with the rest of the middleware chain,
+/// and can inspect or modify the request before passing it on with [`Next::run`],
+/// inspect or modify the result afterwards,
+/// or skip the rest of the chain entirely by returning its own result.
+///
+/// Results are the raw values returned by the query engine,
+/// which are deserialized into the query's return type after all middleware has run.
+pub trait QueryMiddleware: Send + Sync + 'static {
+    fn handle<'a>(
+        &'a self,
+        request: MiddlewareRequest,
+        next: Next<'a>,
+    ) -> BoxFuture<'a, MiddlewareResult>;
+}
+
+/// The remainder of the middleware chain, ending at the query engine.
+pub struct Next<'a> {
+    middleware: &'a [Box<dyn QueryMiddleware>],
+    engine: &'a ExecutionEngine,
+}
+
+impl<'a> Next<'a> {
+    pub(crate) fn new(
+        middleware: &'a [Box<dyn QueryMiddleware>],
+        engine: &'a ExecutionEngine,
+    ) -> Self {
+        Self { middleware, engine }
+    }
+
+    pub fn run(self, request: MiddlewareRequest) -> BoxFuture<'a, MiddlewareResult> {
+        match self.middleware.split_first() {
+            Some((middleware, rest)) => middleware.handle(
+                request,
+                Next {
+                    middleware: rest,
+                    engine: self.engine,
+                },
+            ),
+            None => execute(self.engine, request).boxed(),
+        }
+    }
+}
+
+pub(crate) fn request(
+    queries: Vec<MiddlewareQuery>,
+    batch: Option<BatchMode>,
+    tx_id: Option<TxId>,
+) -> MiddlewareRequest {
+    MiddlewareRequest {
+        queries,
+        batch,
+        tx_id,
+    }
+}
+
+async fn execute(engine: &ExecutionEngine, request: MiddlewareRequest) -> MiddlewareResult {
+    if let Some(mode) = request.batch {
+        return engine.execute_all(request.queries, mode).await;
+    }
+
+    let mut results = vec![];
+
+    for query in request.queries {
+        results.push(engine.execute(query).await);
+    }
+
+    Ok(results)
+}
@@ -1,17 +1,94 @@
-use std::sync::Arc;
+use std::sync::{Arc, Mutex, MutexGuard};
 
-use query_core::{Operation, Selection};
+use query_core::{Operation, Selection, TxId};
 
 use serde::Serialize;
 use serde_value::Value;
-use tokio::sync::Mutex;
 
-use crate::Query;
+use crate::{Query, QueryError};
 
+type ErrorFn = Arc<dyn Fn() -> QueryError + Send + Sync>;
+
+#[derive(Clone)]
+enum MockResponse {
+    Value(Value),
+    Error(ErrorFn),
+}
+
+#[derive(Clone, Copy)]
+enum Times {
+    /// Default for expectations, allowing the query to be made any number of times
+    /// but requiring it to be made at least once.
+    AtLeastOnce,
+    Exactly(usize),
+    Any,
+}
+
+impl Times {
+    fn satisfied(&self, calls: usize) -> bool {
+        match self {
+            Self::AtLeastOnce => calls >= 1,
+            Self::Exactly(n) => calls == *n,
+            Self::Any => true,
+        }
+    }
+
+    fn exhausted(&self, calls: usize) -> bool {
+        match self {
+            Self::Exactly(n) => calls >= *n,
+            _ => false,
+        }
+    }
+}
+
+struct ExpectationState {
+    operation: Operation,
+    response: MockResponse,
+    times: Times,
+    calls: usize,
+    /// ID of the sequence the expectation belongs to, and its position within it
+    sequence: Option<(usize, usize)>,
+}
+
+/// A query made to a mock client.
+#[derive(Clone)]
+pub struct MockCall {
+    pub operation: Operation,
+    /// Whether the query was made as part of a batch.
+    pub batch: bool,
+    /// ID of the transaction the query was made inside of, if any.
+    pub tx_id: Option<TxId>,
+}
+
+#[derive(Default)]
+struct MockState {
+    expectations: Vec<ExpectationState>,
+    calls: Vec<MockCall>,
+    unexpected: Vec<(MockCall, &'static str)>,
+    sequences: usize,
+    transactions: usize,
+}
+
+impl MockState {
+    fn sequence_ready(&self, sequence: Option<(usize, usize)>) -> bool {
+        let (id, position) = match sequence {
+            Some(sequence) => sequence,
+            None => return true,
+        };
+
+        self.expectations.iter().all(|e| match e.sequence {
+            Some((other_id, other_position)) if other_id == id && other_position < position => {
+                e.times.satisfied(e.calls)
+            }
+            _ => true,
+        })
+    }
+}
+
+/// Expectations and recorded calls for a mock client created with `PrismaClient::_mock`.
 #[derive(Default, Clone)]
 pub struct MockStore {
-    read: Arc<Mutex<Vec<(Selection, Value)>>>,
-    write: Arc<Mutex<Vec<(Selection, Value)>>>,
+    state: Arc<Mutex<MockState>>,
 }
 
 impl MockStore {
@@ -19,34 +96,225 @@ impl MockStore {
         Default::default()
     }
 
+    fn state(&self) -> MutexGuard<MockState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
     // monomorphization optimisation moment
-    async fn add_op(&self, op: Operation, expected: Value) {
-        let (sel, mutex) = match op {
-            Operation::Read(sel) => (sel, &self.read),
-            Operation::Write(sel) => (sel, &self.write),
-        };
+    fn add_op(&self, operation: Operation, response: MockResponse) -> Expectation {
+        let mut state = self.state();
+
+        state.expectations.push(ExpectationState {
+            operation,
+            response,
+            times: Times::AtLeastOnce,
+            calls: 0,
+            sequence: None,
+        });
 
-        mutex.lock().await.push((sel, expected))
+        Expectation {
+            store: self.clone(),
+            index: state.expectations.len() - 1,
+        }
     }
-    pub async fn expect<'a, Q: Query<'a>>(&self, query: Q, expected: Q::ReturnValue)
+
+    /// Expects `query` to be made, resolving it with `expected`.
+    pub async fn expect<'a, Q: Query<'a>>(&self, query: Q, expected: Q::ReturnValue) -> Expectation
     where
         Q::ReturnValue: Serialize,
     {
-        self.add_op(query.graphql().0, serde_value::to_value(expected).unwrap())
-            .await;
+        self.add_op(
+            query.graphql().0,
+            MockResponse::Value(serde_value::to_value(expected).unwrap()),
+        )
     }
 
-    pub(crate) async fn get_op(&self, op: &Operation) -> Option<Value> {
-        let (sel, mutex) = match op {
-            Operation::Read(sel) => (sel, &self.read),
-            Operation::Write(sel) => (sel, &self.write),
-        };
+    /// Expects `query` to be made, failing it with the error returned by `error`.
+    pub async fn expect_error<'a, Q: Query<'a>>(
+        &self,
+        query: Q,
+        error: impl Fn() -> QueryError + Send + Sync + 'static,
+    ) -> Expectation {
+        self.add_op(query.graphql().0, MockResponse::Error(Arc::new(error)))
+    }
+
+    /// Creates a sequence that expectations can be added to with [`Expectation::in_sequence`].
+    pub fn sequence(&self) -> Sequence {
+        let mut state = self.state();
+
+        state.sequences += 1;
 
-        mutex
-            .lock()
-            .await
+        Sequence {
+            id: state.sequences,
+            next: Arc::new(Mutex::new(0)),
+        }
+    }
+
+    /// All queries made to the mock client, in the order they were made.
+    pub fn calls(&self) -> Vec<MockCall> {
+        self.state().calls.clone()
+    }
+
+    /// Number of times `query` has been made.
+    pub fn call_count<'a, Q: Query<'a>>(&self, query: Q) -> usize {
+        let operation = query.graphql().0;
+
+        self.state()
+            .calls
             .iter()
-            .find(|(s, _)| s == sel)
-            .map(|(_, e)| e.clone())
+            .filter(|call| call.operation == operation)
+            .count()
+    }
+
+    /// Panics if any expectations haven't been met or any unexpected queries were made,
+    /// listing each of them.
+    pub fn verify(&self) {
+        let state = self.state();
+
+        let mut problems = state
+            .expectations
+            .iter()
+            .filter(|e| !e.times.satisfied(e.calls))
+            .map(|e| {
+                let expected = match e.times {
+                    Times::AtLeastOnce => "at least 1".to_string(),
+                    Times::Exactly(n) => n.to_string(),
+                    Times::Any => unreachable!(),
+                };
+
+                format!(
+                    "Unmet expectation: {} - expected {expected} calls, received {}",
+                    describe(&e.operation),
+                    e.calls
+                )
+            })
+            .collect::<Vec<_>>();
+
+        problems.extend(state.unexpected.iter().map(|(call, reason)| {
+            format!("Unexpected query: {} - {reason}", describe(&call.operation))
+        }));
+
+        if !problems.is_empty() {
+            panic!("Mock verification failed:\n{}", problems.join("\n"));
+        }
+    }
+
+    pub(crate) fn new_tx_id(&self) -> TxId {
+        let mut state = self.state();
+
+        state.transactions += 1;
+
+        format!("mock-transaction-{}", state.transactions).into()
+    }
+
+    pub(crate) fn get_op(
+        &self,
+        operation: &Operation,
+        batch: bool,
+        tx_id: Option<TxId>,
+    ) -> crate::Result<Value> {
+        let mut state = self.state();
+
+        let call = MockCall {
+            operation: operation.clone(),
+            batch,
+            tx_id,
+        };
+
+        state.calls.push(call.clone());
+
+        let mut out_of_order = false;
+        let mut found = None;
+
+        for (i, expectation) in state.expectations.iter().enumerate() {
+            if &expectation.operation != operation || expectation.times.exhausted(expectation.calls)
+            {
+                continue;
+            }
+
+            if !state.sequence_ready(expectation.sequence) {
+                out_of_order = true;
+                continue;
+            }
+
+            found = Some(i);
+            break;
+        }
+
+        let index = match found {
+            Some(index) => index,
+            None => {
+                let reason = match out_of_order {
+                    true => "made out of sequence",
+                    false => "no matching expectation",
+                };
+
+                state.unexpected.push((call, reason));
+
+                return Err(QueryError::Mock(format!(
+                    "{} was {reason}",
+                    describe(operation)
+                )));
+            }
+        };
+
+        let expectation = &mut state.expectations[index];
+
+        expectation.calls += 1;
+
+        match &expectation.response {
+            MockResponse::Value(value) => Ok(value.clone()),
+            MockResponse::Error(error) => Err(error()),
+        }
+    }
+}
+
+fn describe(operation: &Operation) -> String {
+    let selection: &Selection = match operation {
+        Operation::Read(selection) | Operation::Write(selection) => selection,
+    };
+
+    format!("{} {:?}", selection.name(), selection.arguments())
+}
+
+/// Handle to an expectation added to a [`MockStore`], used to configure it further.
+pub struct Expectation {
+    store: MockStore,
+    index: usize,
+}
+
+impl Expectation {
+    fn update(self, f: impl FnOnce(&mut ExpectationState)) -> Self {
+        f(&mut self.store.state().expectations[self.index]);
+        self
     }
+
+    /// Requires the query to be made exactly `n` times.
+    /// Once the query has been made `n` times, later calls will match the next expectation for it.
+    pub fn times(self, n: usize) -> Self {
+        self.update(|e| e.times = Times::Exactly(n))
+    }
+
+    /// Allows the query to be made any number of times, including none.
+    pub fn any_times(self) -> Self {
+        self.update(|e| e.times = Times::Any)
+    }
+
+    /// Requires the query to be made after all previous expectations in `sequence` have been met.
+    pub fn in_sequence(self, sequence: &Sequence) -> Self {
+        let position = {
+            let mut next = sequence.next.lock().unwrap_or_else(|e| e.into_inner());
+            *next += 1;
+            *next
+        };
+
+        self.update(|e| e.sequence = Some((sequence.id, position)))
+    }
+}
+
+/// An ordered group of expectations, created with [`MockStore::sequence`].
+#[derive(Clone)]
+pub struct Sequence {
+    id: usize,
+    next: Arc<Mutex<usize>>,
 }
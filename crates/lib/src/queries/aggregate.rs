@@ -0,0 +1,184 @@
+use prisma_models::PrismaValue;
+use query_core::{Operation, Selection};
+
+use crate::{
+    merge_fields, ModelOperation, ModelQuery, ModelReadOperation, ModelTypes, OrderByQuery,
+    PaginatedQuery, PrismaClientInternals, Query, QueryConvert, SerializedWhereInput, WhereInput,
+    WhereQuery,
+};
+
+#[derive(Clone)]
+pub struct Aggregate<'a, Actions: ModelTypes> {
+    client: &'a PrismaClientInternals,
+    pub where_params: Vec<Actions::Where>,
+    pub aggregate_params: Vec<Actions::Aggregate>,
+    pub order_by_params: Vec<Actions::OrderBy>,
+    pub cursor_params: Vec<Actions::Cursor>,
+    pub skip: Option<i64>,
+    pub take: Option<i64>,
+}
+
+impl<'a, Actions: ModelTypes> Aggregate<'a, Actions> {
+    pub fn new(
+        client: &'a PrismaClientInternals,
+        where_params: Vec<Actions::Where>,
+        aggregate_params: Vec<Actions::Aggregate>,
+    ) -> Self {
+        Self {
+            client,
+            where_params,
+            aggregate_params,
+            order_by_params: vec![],
+            cursor_params: vec![],
+            skip: None,
+            take: None,
+        }
+    }
+
+    pub fn order_by(mut self, param: Actions::OrderBy) -> Self {
+        self.order_by_params.push(param);
+        self
+    }
+
+    pub fn cursor(mut self, param: Actions::Cursor) -> Self {
+        self.cursor_params.push(param);
+        self
+    }
+
+    pub fn skip(mut self, skip: i64) -> Self {
+        self.skip = Some(skip);
+        self
+    }
+
+    pub fn take(mut self, take: i64) -> Self {
+        self.take = Some(take);
+        self
+    }
+
+    pub async fn exec(self) -> super::Result<Actions::AggregateData> {
+        super::exec(self).await
+    }
+}
+
+/// Groups field selections by the aggregate they belong to (`_count`, `_avg` etc),
+/// preserving the order in which each aggregate was first requested.
+pub fn aggregate_selections(
+    params: impl IntoIterator<Item = (String, Selection)>,
+) -> Vec<Selection> {
+    let mut groups: Vec<(String, Vec<Selection>)> = vec![];
+
+    for (aggregate, selection) in params {
+        match groups.iter_mut().find(|(name, _)| name == &aggregate) {
+            Some((_, selections)) => selections.push(selection),
+            None => groups.push((aggregate, vec![selection])),
+        }
+    }
+
+    groups
+        .into_iter()
+        .map(|(name, selections)| Selection::new(name, None, [], selections))
+        .collect()
+}
+
+impl<'a, Actions: ModelTypes> QueryConvert for Aggregate<'a, Actions> {
+    type RawType = Actions::AggregateData;
+    type ReturnValue = Self::RawType;
+
+    fn convert(raw: Self::RawType) -> super::Result<Self::ReturnValue> {
+        Ok(raw)
+    }
+}
+
+impl<'a, Actions: ModelTypes> Query<'a> for Aggregate<'a, Actions> {
+    fn graphql(self) -> (Operation, &'a PrismaClientInternals) {
+        (
+            Operation::Read(Self::base_selection(
+                [
+                    (!self.where_params.is_empty()).then(|| {
+                        (
+                            "where".to_string(),
+                            PrismaValue::Object(merge_fields(
+                                self.where_params
+                                    .into_iter()
+                                    .map(WhereInput::serialize)
+                                    .map(|s| (s.field, s.value.into()))
+                                    .collect(),
+                            ))
+                            .into(),
+                        )
+                    }),
+                    (!self.order_by_params.is_empty()).then(|| {
+                        (
+                            "orderBy".to_string(),
+                            PrismaValue::List(
+                                self.order_by_params
+                                    .into_iter()
+                                    .map(|p| PrismaValue::Object(vec![p.into()]))
+                                    .collect(),
+                            )
+                            .into(),
+                        )
+                    }),
+                    (!self.cursor_params.is_empty()).then(|| {
+                        (
+                            "cursor".to_string(),
+                            PrismaValue::Object(
+                                self.cursor_params
+                                    .into_iter()
+                                    .map(Into::into)
+                                    .map(WhereInput::serialize)
+                                    .map(SerializedWhereInput::transform_equals)
+                                    .collect(),
+                            )
+                            .into(),
+                        )
+                    }),
+                    self.skip
+                        .map(|skip| ("skip".to_string(), PrismaValue::Int(skip as i64).into())),
+                    self.take
+                        .map(|take| ("take".to_string(), PrismaValue::Int(take as i64).into())),
+                ]
+                .into_iter()
+                .flatten(),
+                aggregate_selections(self.aggregate_params.into_iter().map(Into::into)),
+            )),
+            self.client,
+        )
+    }
+
+    fn model_operation(&self) -> Option<(&'static str, ModelOperation)> {
+        Some((Actions::MODEL, Self::TYPE))
+    }
+}
+
+impl<'a, Actions: ModelTypes> ModelQuery<'a> for Aggregate<'a, Actions> {
+    type Types = Actions;
+
+    const TYPE: ModelOperation = ModelOperation::Read(ModelReadOperation::Aggregate);
+}
+
+impl<'a, Actions: ModelTypes> WhereQuery<'a> for Aggregate<'a, Actions> {
+    fn add_where(&mut self, param: Actions::Where) {
+        self.where_params.push(param);
+    }
+}
+
+impl<'a, Actions: ModelTypes> OrderByQuery<'a> for Aggregate<'a, Actions> {
+    fn add_order_by(&mut self, param: Actions::OrderBy) {
+        self.order_by_params.push(param);
+    }
+}
+
+impl<'a, Actions: ModelTypes> PaginatedQuery<'a> for Aggregate<'a, Actions> {
+    fn add_cursor(&mut self, param: Actions::Cursor) {
+        self.cursor_params.push(param);
+    }
+
+    fn set_skip(&mut self, skip: i64) {
+        self.skip = Some(skip);
+    }
+
+    fn set_take(&mut self, take: i64) {
+        self.take = Some(take);
+    }
+}
@@ -1,8 +1,6 @@
 use std::{collections::VecDeque, num::NonZeroUsize};
 
-use query_core::Operation;
-
-use crate::{PrismaClientInternals, Query, QueryConvert};
+use crate::{MiddlewareQuery, PrismaClientInternals, Query, QueryConvert, QueryError};
 
 pub enum VecMeta {
     Empty,
@@ -16,7 +14,7 @@ pub enum BatchItemDataMeta {
 }
 
 pub enum BatchItemData {
-    Query(Operation),
+    Query(MiddlewareQuery),
     Vec(Vec<Self>),
     Tuple(Vec<Self>),
 }
@@ -34,11 +32,11 @@ impl BatchItemData {
         }
     }
 
-    fn operations(self, v: &mut Vec<Operation>) {
+    fn queries(self, v: &mut Vec<MiddlewareQuery>) {
         match self {
-            Self::Query(op) => v.push(op),
-            Self::Vec(items) => items.into_iter().for_each(|i| i.operations(v)),
-            Self::Tuple(items) => items.into_iter().for_each(|i| i.operations(v)),
+            Self::Query(query) => v.push(query),
+            Self::Vec(items) => items.into_iter().for_each(|i| i.queries(v)),
+            Self::Tuple(items) => items.into_iter().for_each(|i| i.queries(v)),
         }
     }
 }
@@ -65,87 +63,169 @@ impl BatchData {
         }
     }
 
-    fn operations(self) -> Vec<Operation> {
+    fn queries(self) -> Vec<MiddlewareQuery> {
         let items = match self {
             Self::Tuple(items) => items,
             Self::Iterator(items) => items,
         };
 
-        let mut ops = vec![];
+        let mut queries = vec![];
 
-        items.into_iter().for_each(|i| i.operations(&mut ops));
+        items.into_iter().for_each(|i| i.queries(&mut queries));
 
-        ops
+        queries
     }
 }
 
+/// How the queries in a batch are executed.
+///
+/// Batches executed by a transaction client always run inside its transaction,
+/// regardless of the mode.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum BatchMode {
+    /// Queries are executed in a transaction,
+    /// so if one fails the changes made by the others are rolled back.
+    #[default]
+    Transactional,
+    /// Queries are executed independently of each other,
+    /// so if one fails the changes made by the others are kept.
+    Independent,
+}
+
 pub async fn batch<'batch, 'b, T: BatchContainer<'batch, Marker>, Marker>(
     container: T,
     client: &'b PrismaClientInternals,
+    mode: BatchMode,
 ) -> super::Result<<T as BatchContainer<'batch, Marker>>::ReturnType> {
-    let data = container.data();
+    let data = container.data()?;
     let meta = data.meta();
 
-    let operations = data.operations();
+    let queries = data.queries();
 
-    let values = client
-        .engine
-        .execute_all(operations)
-        .await?
-        .into_iter()
-        .collect::<super::Result<VecDeque<_>>>()?;
+    let values = BatchValues::new(client, client.execute_all(queries, mode).await?);
 
     T::resolve(meta, values)
 }
 
+/// Like [`batch`], but returns a result for each query in the batch instead of failing on the first error.
+/// The error returned from this function is for failures of the batch as a whole,
+/// including queries that fail [`Query::validate`], in which case nothing is executed.
+pub async fn batch_settled<'batch, 'b, T: BatchContainer<'batch, Marker>, Marker>(
+    container: T,
+    client: &'b PrismaClientInternals,
+    mode: BatchMode,
+) -> super::Result<<T as BatchContainer<'batch, Marker>>::SettledReturnType> {
+    let data = container.data()?;
+    let meta = data.meta();
+
+    let queries = data.queries();
+
+    let values = BatchValues::new(client, client.execute_all(queries, mode).await?);
+
+    Ok(T::resolve_settled(meta, values))
+}
+
+/// Results of the queries in a batch, consumed in order as the batch is resolved.
+pub struct BatchValues {
+    values: VecDeque<super::Result<serde_value::Value>>,
+    mock: bool,
+}
+
+impl BatchValues {
+    fn new(client: &PrismaClientInternals, values: Vec<super::Result<serde_value::Value>>) -> Self {
+        Self {
+            values: values.into(),
+            mock: client.engine.is_mock(),
+        }
+    }
+
+    fn next<Q: QueryConvert>(&mut self) -> super::Result<Q::ReturnValue> {
+        let value = self.values.pop_front().ok_or_else(|| {
+            QueryError::Deserialize("Missing result for batch item".to_string())
+        })??;
+
+        // Mock stores hold return values rather than raw query engine responses
+        if self.mock {
+            return deserialize(value);
+        }
+
+        Q::convert(deserialize(value)?)
+    }
+}
+
+fn deserialize<T: super::Data>(value: serde_value::Value) -> super::Result<T> {
+    value
+        .deserialize_into()
+        .map_err(|e| QueryError::Deserialize(e.to_string()))
+}
+
 pub trait BatchItemParent {
     type ReturnValue;
+    type SettledReturnValue;
 }
 
 pub trait BatchItem<'a>: BatchItemParent {
-    fn data(self) -> BatchItemData;
+    fn data(self) -> super::Result<BatchItemData>;
 
     fn resolve(
         meta: &BatchItemDataMeta,
-        values: &mut VecDeque<serde_value::Value>,
+        values: &mut BatchValues,
     ) -> super::Result<<Self as BatchItemParent>::ReturnValue>;
+
+    fn resolve_settled(
+        meta: &BatchItemDataMeta,
+        values: &mut BatchValues,
+    ) -> <Self as BatchItemParent>::SettledReturnValue;
 }
 
 impl<Q: QueryConvert> BatchItemParent for Q {
     type ReturnValue = Q::ReturnValue;
+    type SettledReturnValue = super::Result<Q::ReturnValue>;
 }
 
 impl<'a, 'b, Q: Query<'a>> BatchItem<'b> for Q {
-    fn data(self) -> BatchItemData {
-        BatchItemData::Query(self.graphql().0)
+    fn data(self) -> super::Result<BatchItemData> {
+        self.validate()?;
+
+        let model_operation = self.model_operation();
+
+        Ok(BatchItemData::Query(MiddlewareQuery::new(
+            self.graphql().0,
+            model_operation,
+        )))
     }
 
     fn resolve(
         _: &BatchItemDataMeta,
-        values: &mut VecDeque<serde_value::Value>,
+        values: &mut BatchValues,
     ) -> super::Result<<Self as BatchItemParent>::ReturnValue> {
-        Q::convert(
-            values
-                .pop_front()
-                .unwrap()
-                .deserialize_into::<Q::RawType>()
-                .unwrap(),
-        )
+        values.next::<Q>()
+    }
+
+    fn resolve_settled(
+        meta: &BatchItemDataMeta,
+        values: &mut BatchValues,
+    ) -> <Self as BatchItemParent>::SettledReturnValue {
+        <Self as BatchItem>::resolve(meta, values)
     }
 }
 
 impl<'batch, I: BatchItemParent> BatchItemParent for Vec<I> {
     type ReturnValue = Vec<<I as BatchItemParent>::ReturnValue>;
+    type SettledReturnValue = Vec<<I as BatchItemParent>::SettledReturnValue>;
 }
 
 impl<'batch, 'query, I: Query<'query>> BatchItem<'batch> for Vec<I> {
-    fn data(self) -> BatchItemData {
-        BatchItemData::Vec(self.into_iter().map(BatchItem::data).collect())
+    fn data(self) -> super::Result<BatchItemData> {
+        self.into_iter()
+            .map(BatchItem::data)
+            .collect::<super::Result<_>>()
+            .map(BatchItemData::Vec)
     }
 
     fn resolve(
         meta: &BatchItemDataMeta,
-        values: &mut VecDeque<serde_value::Value>,
+        values: &mut BatchValues,
     ) -> super::Result<<Self as BatchItemParent>::ReturnValue> {
         Ok(match meta {
             BatchItemDataMeta::Vec(meta) => match meta {
@@ -157,33 +237,49 @@ impl<'batch, 'query, I: Query<'query>> BatchItem<'batch> for Vec<I> {
             _ => unreachable!(),
         })
     }
+
+    fn resolve_settled(
+        meta: &BatchItemDataMeta,
+        values: &mut BatchValues,
+    ) -> <Self as BatchItemParent>::SettledReturnValue {
+        match meta {
+            BatchItemDataMeta::Vec(meta) => match meta {
+                VecMeta::Empty => vec![],
+                VecMeta::NotEmpty(size, meta) => (0..size.get())
+                    .map(|_| <I as BatchItem>::resolve_settled(meta.as_ref(), values))
+                    .collect(),
+            },
+            _ => unreachable!(),
+        }
+    }
 }
 
 /// A container that can hold queries to batch into a transaction
 pub trait BatchContainer<'batch, Marker> {
     type ReturnType;
+    type SettledReturnType;
 
-    fn data(self) -> BatchData;
+    fn data(self) -> super::Result<BatchData>;
 
-    fn resolve(
-        meta: BatchDataMeta,
-        values: VecDeque<serde_value::Value>,
-    ) -> super::Result<Self::ReturnType>;
+    fn resolve(meta: BatchDataMeta, values: BatchValues) -> super::Result<Self::ReturnType>;
+
+    fn resolve_settled(meta: BatchDataMeta, values: BatchValues) -> Self::SettledReturnType;
 }
 
 impl<'batch, 't: 'batch, T: BatchItem<'t>, I: IntoIterator<Item = T>> BatchContainer<'batch, ()>
     for I
 {
     type ReturnType = Vec<<T as BatchItemParent>::ReturnValue>;
+    type SettledReturnType = Vec<<T as BatchItemParent>::SettledReturnValue>;
 
-    fn data(self) -> BatchData {
-        BatchData::Iterator(self.into_iter().map(BatchItem::data).collect())
+    fn data(self) -> super::Result<BatchData> {
+        self.into_iter()
+            .map(BatchItem::data)
+            .collect::<super::Result<_>>()
+            .map(BatchData::Iterator)
     }
 
-    fn resolve(
-        meta: BatchDataMeta,
-        mut values: VecDeque<serde_value::Value>,
-    ) -> super::Result<Self::ReturnType> {
+    fn resolve(meta: BatchDataMeta, mut values: BatchValues) -> super::Result<Self::ReturnType> {
         Ok(match meta {
             BatchDataMeta::Iterator(meta) => match meta {
                 VecMeta::Empty => vec![],
@@ -194,6 +290,18 @@ impl<'batch, 't: 'batch, T: BatchItem<'t>, I: IntoIterator<Item = T>> BatchConta
             _ => unreachable!(),
         })
     }
+
+    fn resolve_settled(meta: BatchDataMeta, mut values: BatchValues) -> Self::SettledReturnType {
+        match meta {
+            BatchDataMeta::Iterator(meta) => match meta {
+                VecMeta::Empty => vec![],
+                VecMeta::NotEmpty(size, meta) => (0..size.get())
+                    .map(|_| T::resolve_settled(&meta, &mut values))
+                    .collect(),
+            },
+            _ => unreachable!(),
+        }
+    }
 }
 
 pub enum TupleMarker {}
@@ -210,16 +318,17 @@ macro_rules! impl_tuple {
             #[allow(warnings)]
             impl<'batch, $( [< "'" $generic >]: 'batch),+, $($generic: BatchItem<[< "'" $generic >]>),+> BatchContainer<'batch, TupleMarker> for ($($generic),+) {
                 type ReturnType = ($(<$generic as BatchItemParent>::ReturnValue),+);
+                type SettledReturnType = ($(<$generic as BatchItemParent>::SettledReturnValue),+);
 
-                fn data(self) -> BatchData {
+                fn data(self) -> $crate::Result<BatchData> {
                     let ($($generic),+) = self;
 
-                    BatchData::Tuple(
-                        vec![$(BatchItem::data($generic)),+]
-                    )
+                    Ok(BatchData::Tuple(
+                        vec![$(BatchItem::data($generic)?),+]
+                    ))
                 }
 
-                fn resolve(meta: BatchDataMeta, mut values: VecDeque<serde_value::Value>) -> $crate::Result<Self::ReturnType> {
+                fn resolve(meta: BatchDataMeta, mut values: BatchValues) -> $crate::Result<Self::ReturnType> {
                     Ok(match meta {
                         BatchDataMeta::Tuple(metas) => {
                             let mut metas_iter = metas.iter();
@@ -229,26 +338,38 @@ macro_rules! impl_tuple {
                         _ => unreachable!()
                     })
                 }
+
+                fn resolve_settled(meta: BatchDataMeta, mut values: BatchValues) -> Self::SettledReturnType {
+                    match meta {
+                        BatchDataMeta::Tuple(metas) => {
+                            let mut metas_iter = metas.iter();
+
+                            ($(<$generic as BatchItem>::resolve_settled(metas_iter.next().unwrap(), &mut values)),+)
+                        },
+                        _ => unreachable!()
+                    }
+                }
             }
 
             #[allow(warnings)]
             impl<$($generic: BatchItemParent),+> BatchItemParent for ($($generic),+) {
                 type ReturnValue = ($(<$generic as BatchItemParent>::ReturnValue),+);
+                type SettledReturnValue = ($(<$generic as BatchItemParent>::SettledReturnValue),+);
             }
 
             #[allow(warnings)]
             impl<'batch, $( [< "'" $generic >]: 'batch),+, $($generic: BatchItem<[< "'" $generic >]>),+> BatchItem<'batch> for ($($generic),+) {
-                fn data(self) -> BatchItemData {
+                fn data(self) -> $crate::Result<BatchItemData> {
                     let ($($generic),+) = self;
 
-                    BatchItemData::Tuple(
-                        vec![$(BatchItem::data($generic)),+]
-                    )
+                    Ok(BatchItemData::Tuple(
+                        vec![$(BatchItem::data($generic)?),+]
+                    ))
                 }
 
                 fn resolve(
                     meta: &BatchItemDataMeta,
-                    values: &mut VecDeque<serde_value::Value>,
+                    values: &mut BatchValues,
                 ) -> $crate::Result<<Self as BatchItemParent>::ReturnValue> {
                     Ok(match meta {
                         BatchItemDataMeta::Tuple(meta) => {
@@ -259,6 +380,20 @@ macro_rules! impl_tuple {
                         _ => unreachable!(),
                     })
                 }
+
+                fn resolve_settled(
+                    meta: &BatchItemDataMeta,
+                    values: &mut BatchValues,
+                ) -> <Self as BatchItemParent>::SettledReturnValue {
+                    match meta {
+                        BatchItemDataMeta::Tuple(meta) => {
+                            let mut meta = meta.iter();
+
+                            ($($generic::resolve_settled(meta.next().unwrap(), values)),+)
+                        },
+                        _ => unreachable!(),
+                    }
+                }
             }
         }
     };
@@ -270,15 +405,13 @@ impl_tuple!(T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11, T12, T13, T14, T15, T1
 #[allow(warnings)]
 impl<'batch, 'query, Q: Query<'query>> BatchContainer<'batch, TupleMarker> for Q {
     type ReturnType = Q::ReturnValue;
+    type SettledReturnType = super::Result<Q::ReturnValue>;
 
-    fn data(self) -> BatchData {
-        BatchData::Tuple(vec![BatchItem::data(self)])
+    fn data(self) -> super::Result<BatchData> {
+        Ok(BatchData::Tuple(vec![BatchItem::data(self)?]))
     }
 
-    fn resolve(
-        meta: BatchDataMeta,
-        mut values: VecDeque<serde_value::Value>,
-    ) -> super::Result<Self::ReturnType> {
+    fn resolve(meta: BatchDataMeta, mut values: BatchValues) -> super::Result<Self::ReturnType> {
         Ok(match meta {
             BatchDataMeta::Tuple(metas) => {
                 let mut metas_iter = metas.iter();
@@ -288,4 +421,15 @@ impl<'batch, 'query, Q: Query<'query>> BatchContainer<'batch, TupleMarker> for Q
             _ => unreachable!(),
         })
     }
+
+    fn resolve_settled(meta: BatchDataMeta, mut values: BatchValues) -> Self::SettledReturnType {
+        match meta {
+            BatchDataMeta::Tuple(metas) => {
+                let mut metas_iter = metas.iter();
+
+                <Q as BatchItem>::resolve_settled(metas_iter.next().unwrap(), &mut values)
+            }
+            _ => unreachable!(),
+        }
+    }
 }
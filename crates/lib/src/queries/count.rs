@@ -8,6 +8,7 @@ use crate::{
     WhereQuery,
 };
 
+#[derive(Clone)]
 pub struct Count<'a, Actions: ModelTypes> {
     client: &'a PrismaClientInternals,
     pub where_params: Vec<Actions::Where>,
@@ -134,6 +135,10 @@ impl<'a, Actions: ModelTypes> Query<'a> for Count<'a, Actions> {
             self.client,
         )
     }
+
+    fn model_operation(&self) -> Option<(&'static str, ModelOperation)> {
+        Some((Actions::MODEL, Self::TYPE))
+    }
 }
 
 impl<'a, Actions: ModelTypes> ModelQuery<'a> for Count<'a, Actions> {
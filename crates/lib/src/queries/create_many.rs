@@ -1,15 +1,19 @@
+use std::marker::PhantomData;
+
 use prisma_models::PrismaValue;
-use query_core::{Operation, Selection};
+use query_core::{Operation, Selection, SelectionArgument};
+use serde::{de::IntoDeserializer, Deserialize};
 
 use crate::{
-    merge_fields, BatchResult, ModelOperation, ModelQuery, ModelTypes, ModelWriteOperation,
-    PrismaClientInternals, Query, QueryConvert,
+    merge_fields, BatchMode, BatchResult, IncludeType, MiddlewareQuery, ModelOperation, ModelQuery,
+    ModelTypes, ModelWriteOperation, PrismaClientInternals, Query, QueryConvert, QueryError,
+    SelectType,
 };
 
+#[derive(Clone)]
 pub struct CreateMany<'a, Actions: ModelTypes> {
     client: &'a PrismaClientInternals,
     pub set_params: Vec<Vec<Actions::UncheckedSet>>,
-    pub skip_duplicates: bool,
 }
 
 impl<'a, Actions: ModelTypes> CreateMany<'a, Actions> {
@@ -17,17 +21,12 @@ impl<'a, Actions: ModelTypes> CreateMany<'a, Actions> {
         client: &'a PrismaClientInternals,
         set_params: Vec<Vec<Actions::UncheckedSet>>,
     ) -> Self {
-        Self {
-            client,
-            set_params,
-            skip_duplicates: false,
-        }
+        Self { client, set_params }
     }
 
     #[cfg(any(feature = "mysql", feature = "sqlite", feature = "postgresql"))]
-    pub fn skip_duplicates(mut self) -> Self {
-        self.skip_duplicates = true;
-        self
+    pub fn skip_duplicates(self) -> CreateManySkipDuplicates<'a, Actions> {
+        CreateManySkipDuplicates { inner: self }
     }
 
     fn to_selection(
@@ -64,6 +63,32 @@ impl<'a, Actions: ModelTypes> CreateMany<'a, Actions> {
         )
     }
 
+    /// Returns the created records instead of how many were created.
+    ///
+    /// The query engine this version is built on (4.14) has no `createManyAndReturn`,
+    /// so on every database each record is created with `createOne`
+    /// in a single batched transaction. Duplicates can't be skipped this way,
+    /// which is why this isn't available after `skip_duplicates`.
+    pub fn returning(self) -> CreateManyReturning<'a, Actions::Data> {
+        CreateManyReturning::new::<Actions>(self, Actions::scalar_selections())
+    }
+
+    /// Same as [`returning`](Self::returning), but only fetches the fields chosen by `select`.
+    pub fn select<S: SelectType<ModelData = Actions::Data>>(
+        self,
+        select: S,
+    ) -> CreateManyReturning<'a, S::Data> {
+        CreateManyReturning::new::<Actions>(self, select.to_selections())
+    }
+
+    /// Same as [`returning`](Self::returning), but also fetches the relations chosen by `include`.
+    pub fn include<I: IncludeType<ModelData = Actions::Data>>(
+        self,
+        include: I,
+    ) -> CreateManyReturning<'a, I::Data> {
+        CreateManyReturning::new::<Actions>(self, include.to_selections())
+    }
+
     pub async fn exec(self) -> super::Result<i64> {
         super::exec(self).await
     }
@@ -83,12 +108,16 @@ impl<'a, Actions: ModelTypes> Query<'a> for CreateMany<'a, Actions> {
         (
             Operation::Write(Self::to_selection(
                 self.set_params,
-                self.skip_duplicates,
+                false,
                 [BatchResult::selection()],
             )),
             self.client,
         )
     }
+
+    fn model_operation(&self) -> Option<(&'static str, ModelOperation)> {
+        Some((Actions::MODEL, Self::TYPE))
+    }
 }
 
 impl<'a, Actions: ModelTypes> ModelQuery<'a> for CreateMany<'a, Actions> {
@@ -96,3 +125,114 @@ impl<'a, Actions: ModelTypes> ModelQuery<'a> for CreateMany<'a, Actions> {
 
     const TYPE: ModelOperation = ModelOperation::Write(ModelWriteOperation::CreateMany);
 }
+
+/// Result of calling `skip_duplicates` on a [`CreateMany`].
+/// Records can't be returned when skipping duplicates,
+/// so unlike [`CreateMany`] this has no `returning`, `select` or `include`.
+#[derive(Clone)]
+pub struct CreateManySkipDuplicates<'a, Actions: ModelTypes> {
+    inner: CreateMany<'a, Actions>,
+}
+
+impl<'a, Actions: ModelTypes> CreateManySkipDuplicates<'a, Actions> {
+    pub async fn exec(self) -> super::Result<i64> {
+        super::exec(self).await
+    }
+}
+
+impl<'a, Actions: ModelTypes> QueryConvert for CreateManySkipDuplicates<'a, Actions> {
+    type RawType = BatchResult;
+    type ReturnValue = i64;
+
+    fn convert(raw: Self::RawType) -> super::Result<Self::ReturnValue> {
+        Ok(raw.count)
+    }
+}
+
+impl<'a, Actions: ModelTypes> Query<'a> for CreateManySkipDuplicates<'a, Actions> {
+    fn graphql(self) -> (Operation, &'a PrismaClientInternals) {
+        (
+            Operation::Write(CreateMany::<Actions>::to_selection(
+                self.inner.set_params,
+                true,
+                [BatchResult::selection()],
+            )),
+            self.inner.client,
+        )
+    }
+
+    fn model_operation(&self) -> Option<(&'static str, ModelOperation)> {
+        Some((Actions::MODEL, Self::TYPE))
+    }
+}
+
+impl<'a, Actions: ModelTypes> ModelQuery<'a> for CreateManySkipDuplicates<'a, Actions> {
+    type Types = Actions;
+
+    const TYPE: ModelOperation = ModelOperation::Write(ModelWriteOperation::CreateMany);
+}
+
+/// Result of calling `returning`, `select` or `include` on a [`CreateMany`].
+/// Holds one `createOne` query per record, executed together in a transaction.
+pub struct CreateManyReturning<'a, Data> {
+    client: &'a PrismaClientInternals,
+    queries: Vec<MiddlewareQuery>,
+    _data: PhantomData<Data>,
+}
+
+impl<'a, Data: crate::Data> CreateManyReturning<'a, Data> {
+    fn new<Actions: ModelTypes>(
+        create_many: CreateMany<'a, Actions>,
+        nested_selections: Vec<Selection>,
+    ) -> Self {
+        let queries = create_many
+            .set_params
+            .into_iter()
+            .map(|fields| {
+                let arguments: Vec<SelectionArgument> = vec![(
+                    "data".to_string(),
+                    PrismaValue::Object(merge_fields(fields.into_iter().map(Into::into).collect()))
+                        .into(),
+                )];
+
+                let operation = Operation::Write(Selection::new(
+                    format!("{}{}", ModelWriteOperation::Create.name(), Actions::MODEL),
+                    None,
+                    arguments,
+                    nested_selections.clone(),
+                ));
+
+                MiddlewareQuery::new(
+                    operation,
+                    Some((
+                        Actions::MODEL,
+                        ModelOperation::Write(ModelWriteOperation::Create),
+                    )),
+                )
+            })
+            .collect();
+
+        Self {
+            client: create_many.client,
+            queries,
+            _data: PhantomData,
+        }
+    }
+
+    pub async fn exec(self) -> super::Result<Vec<Data>> {
+        if self.queries.is_empty() {
+            return Ok(vec![]);
+        }
+
+        self.client
+            .execute_all(self.queries, BatchMode::Transactional)
+            .await?
+            .into_iter()
+            .map(|value| {
+                Data::deserialize(value?.into_deserializer())
+                    .map_err(|e| e.to_string())
+                    .map_err(QueryError::Deserialize)
+            })
+            .collect()
+    }
+}
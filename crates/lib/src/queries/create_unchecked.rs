@@ -7,6 +7,7 @@ use crate::{
     UncheckedSetQuery, WithQuery,
 };
 
+#[derive(Clone)]
 pub struct CreateUnchecked<'a, Actions: ModelTypes> {
     client: &'a PrismaClientInternals,
     pub set_params: Vec<Actions::UncheckedSet>,
@@ -51,6 +52,7 @@ impl<'a, Actions: ModelTypes> CreateUnchecked<'a, Actions> {
         Select::new(
             self.client,
             Operation::Write(Self::to_selection(self.set_params, select.to_selections())),
+            Some((Actions::MODEL, Self::TYPE)),
         )
     }
 
@@ -61,6 +63,7 @@ impl<'a, Actions: ModelTypes> CreateUnchecked<'a, Actions> {
         Include::new(
             self.client,
             Operation::Write(Self::to_selection(self.set_params, include.to_selections())),
+            Some((Actions::MODEL, Self::TYPE)),
         )
     }
 
@@ -89,6 +92,10 @@ impl<'a, Actions: ModelTypes> Query<'a> for CreateUnchecked<'a, Actions> {
             self.client,
         )
     }
+
+    fn model_operation(&self) -> Option<(&'static str, ModelOperation)> {
+        Some((Actions::MODEL, Self::TYPE))
+    }
 }
 
 impl<'a, Actions: ModelTypes> ModelQuery<'a> for CreateUnchecked<'a, Actions> {
@@ -6,6 +6,7 @@ use crate::{
     PrismaClientInternals, Query, QueryConvert, Select, SelectType, WhereInput, WithQuery,
 };
 
+#[derive(Clone)]
 pub struct Delete<'a, Actions: ModelTypes> {
     client: &'a PrismaClientInternals,
     pub where_param: Actions::Where,
@@ -50,6 +51,7 @@ impl<'a, Actions: ModelTypes> Delete<'a, Actions> {
         Select::new(
             self.client,
             Operation::Write(Self::to_selection(self.where_param, select.to_selections())),
+            Some((Actions::MODEL, Self::TYPE)),
         )
     }
 
@@ -60,6 +62,7 @@ impl<'a, Actions: ModelTypes> Delete<'a, Actions> {
         Include::new(
             self.client,
             Operation::Write(Self::to_selection(self.where_param, select.to_selections())),
+            Some((Actions::MODEL, Self::TYPE)),
         )
     }
 
@@ -88,6 +91,10 @@ impl<'a, Actions: ModelTypes> Query<'a> for Delete<'a, Actions> {
             self.client,
         )
     }
+
+    fn model_operation(&self) -> Option<(&'static str, ModelOperation)> {
+        Some((Actions::MODEL, Self::TYPE))
+    }
 }
 
 impl<'a, Actions: ModelTypes> ModelQuery<'a> for Delete<'a, Actions> {
@@ -6,6 +6,7 @@ use crate::{
 };
 use prisma_models::PrismaValue;
 
+#[derive(Clone)]
 pub struct DeleteMany<'a, Actions: ModelTypes> {
     client: &'a PrismaClientInternals,
     pub where_params: Vec<Actions::Where>,
@@ -61,6 +62,10 @@ impl<'a, Actions: ModelTypes> Query<'a> for DeleteMany<'a, Actions> {
             self.client,
         )
     }
+
+    fn model_operation(&self) -> Option<(&'static str, ModelOperation)> {
+        Some((Actions::MODEL, Self::TYPE))
+    }
 }
 
 impl<'a, Actions: ModelTypes> ModelQuery<'a> for DeleteMany<'a, Actions> {
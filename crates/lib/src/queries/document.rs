@@ -0,0 +1,150 @@
+use std::fmt::{self, Display, Formatter, Write};
+
+use prisma_models::PrismaValue;
+use query_core::{ArgumentValue, Operation, Selection};
+
+/// Displays an operation as the Prisma query document that is sent to the query engine:
+///
+/// ```text
+/// query {
+///   findManyPost(where: { title: { contains: "prisma" } }, take: 10) {
+///     id
+///     title
+///   }
+/// }
+/// ```
+///
+/// Useful for logging operations from middleware. Queries can be rendered with
+/// [`Query::to_query_string`](super::Query::to_query_string).
+pub struct QueryDocument<'a>(pub &'a Operation);
+
+impl Display for QueryDocument<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let (keyword, selection) = match self.0 {
+            Operation::Read(selection) => ("query", selection),
+            Operation::Write(selection) => ("mutation", selection),
+        };
+
+        writeln!(f, "{keyword} {{")?;
+        write_selection(f, selection, 1)?;
+        f.write_char('}')
+    }
+}
+
+fn write_selection(f: &mut Formatter<'_>, selection: &Selection, depth: usize) -> fmt::Result {
+    let indent = "  ".repeat(depth);
+
+    f.write_str(&indent)?;
+
+    if let Some(alias) = selection.alias() {
+        write!(f, "{alias}: ")?;
+    }
+
+    f.write_str(selection.name())?;
+
+    let arguments = selection.arguments();
+
+    if !arguments.is_empty() {
+        f.write_char('(')?;
+
+        for (i, (name, value)) in arguments.iter().enumerate() {
+            if i > 0 {
+                f.write_str(", ")?;
+            }
+
+            write!(f, "{name}: ")?;
+            write_argument(f, value)?;
+        }
+
+        f.write_char(')')?;
+    }
+
+    let nested_selections = selection.nested_selections();
+
+    if !nested_selections.is_empty() {
+        f.write_str(" {\n")?;
+
+        for nested in nested_selections {
+            write_selection(f, nested, depth + 1)?;
+        }
+
+        write!(f, "{indent}}}")?;
+    }
+
+    f.write_char('\n')
+}
+
+fn write_argument(f: &mut Formatter<'_>, value: &ArgumentValue) -> fmt::Result {
+    match value {
+        ArgumentValue::Scalar(value) => write_value(f, value),
+        ArgumentValue::Object(object) | ArgumentValue::FieldRef(object) => write_object(
+            f,
+            object.iter().map(|(k, v)| (k.as_str(), v)),
+            write_argument,
+        ),
+        ArgumentValue::List(values) => write_list(f, values, write_argument),
+    }
+}
+
+fn write_value(f: &mut Formatter<'_>, value: &PrismaValue) -> fmt::Result {
+    match value {
+        PrismaValue::String(s) | PrismaValue::Json(s) | PrismaValue::Xml(s) => write_string(f, s),
+        PrismaValue::Enum(e) => f.write_str(e),
+        PrismaValue::Boolean(b) => write!(f, "{b}"),
+        PrismaValue::Int(i) | PrismaValue::BigInt(i) => write!(f, "{i}"),
+        PrismaValue::Float(d) => write!(f, "{d}"),
+        PrismaValue::Uuid(u) => write!(f, "\"{u}\""),
+        PrismaValue::DateTime(d) => write!(f, "\"{}\"", d.to_rfc3339()),
+        PrismaValue::Bytes(b) => write!(f, "\"{}\"", base64::encode(b)),
+        PrismaValue::Null => f.write_str("null"),
+        PrismaValue::List(values) => write_list(f, values, write_value),
+        PrismaValue::Object(entries) => {
+            write_object(f, entries.iter().map(|(k, v)| (k.as_str(), v)), write_value)
+        }
+    }
+}
+
+fn write_string(f: &mut Formatter<'_>, s: &str) -> fmt::Result {
+    f.write_str(&serde_json::to_string(s).map_err(|_| fmt::Error)?)
+}
+
+fn write_list<T>(
+    f: &mut Formatter<'_>,
+    values: &[T],
+    write: fn(&mut Formatter<'_>, &T) -> fmt::Result,
+) -> fmt::Result {
+    f.write_char('[')?;
+
+    for (i, value) in values.iter().enumerate() {
+        if i > 0 {
+            f.write_str(", ")?;
+        }
+
+        write(f, value)?;
+    }
+
+    f.write_char(']')
+}
+
+fn write_object<'v, T: 'v>(
+    f: &mut Formatter<'_>,
+    entries: impl ExactSizeIterator<Item = (&'v str, &'v T)>,
+    write: fn(&mut Formatter<'_>, &T) -> fmt::Result,
+) -> fmt::Result {
+    if entries.len() == 0 {
+        return f.write_str("{}");
+    }
+
+    f.write_str("{ ")?;
+
+    for (i, (key, value)) in entries.enumerate() {
+        if i > 0 {
+            f.write_str(", ")?;
+        }
+
+        write!(f, "{key}: ")?;
+        write(f, value)?;
+    }
+
+    f.write_str(" }")
+}
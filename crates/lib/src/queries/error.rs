@@ -1,6 +1,14 @@
 use serde::Serialize;
+use serde_json::Value;
 use thiserror::Error;
-use user_facing_errors::UserFacingError;
+use user_facing_errors::{
+    common::DatabaseOperationTimeout,
+    query_engine::{
+        ForeignKeyViolation, NullConstraintViolation, PoolTimeout, RecordRequiredButNotFound,
+        RelationViolation, TransactionWriteConflict, UniqueKeyViolation,
+    },
+    KnownError, UserFacingError,
+};
 
 #[derive(Debug, Error, Serialize)]
 pub enum QueryError {
@@ -12,6 +20,35 @@ pub enum QueryError {
 
     #[error("Error deserializing query result into return type: {0}")]
     Deserialize(String),
+
+    #[error("Error in query middleware: {0}")]
+    Middleware(String),
+
+    #[error("Error in mock client: {0}")]
+    Mock(String),
+
+    #[error("Nested transactions are not supported by the {0} connector")]
+    NestedTransaction(String),
+
+    #[error("Raw queries are not supported by the {0} connector")]
+    UnsupportedRawQuery(String),
+
+    #[error(
+        "Raw query has {placeholders} placeholder(s) but {parameters} parameter(s) were provided"
+    )]
+    RawQueryParameters {
+        placeholders: usize,
+        parameters: usize,
+    },
+
+    #[error("The client has been disconnected")]
+    Disconnected,
+
+    #[error("No {model} record found for {operation}")]
+    NotFound {
+        model: &'static str,
+        operation: &'static str,
+    },
 }
 
 impl QueryError {
@@ -24,6 +61,113 @@ impl QueryError {
             _ => false,
         }
     }
+
+    /// Classifies the error, extracting any metadata the query engine provided about it.
+    pub fn kind(&self) -> PrismaErrorKind {
+        match self {
+            Self::Execute(error) => match error.as_known() {
+                Some(error) => PrismaErrorKind::from_known(error),
+                None => PrismaErrorKind::Other {
+                    code: None,
+                    message: error.message().to_string(),
+                },
+            },
+            Self::NotFound { model, .. } => PrismaErrorKind::RecordNotFound {
+                model: Some(model.to_string()),
+                cause: self.to_string(),
+            },
+            _ => PrismaErrorKind::Other {
+                code: None,
+                message: self.to_string(),
+            },
+        }
+    }
+}
+
+/// The kind of a [`QueryError`], as returned by [`QueryError::kind`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum PrismaErrorKind {
+    /// P2002
+    UniqueConstraintViolation {
+        /// Only available if provided by the query engine
+        model: Option<String>,
+        /// Fields of the violated constraint, or the constraint's name
+        /// if the database doesn't report its fields
+        fields: Vec<String>,
+    },
+    /// P2003
+    ForeignKeyViolation { field: Option<String> },
+    /// P2011
+    NullConstraintViolation { fields: Vec<String> },
+    /// P2014
+    RelationViolation {
+        relation: String,
+        model_a: String,
+        model_b: String,
+    },
+    /// P2025, or [`QueryError::NotFound`] from an `_or_throw` query
+    RecordNotFound {
+        model: Option<String>,
+        cause: String,
+    },
+    /// P1008 and P2024
+    Timeout,
+    /// P2034
+    TransactionConflict,
+    /// Any other error. `code` is only present for errors from the query engine that have one.
+    Other {
+        code: Option<String>,
+        message: String,
+    },
+}
+
+impl PrismaErrorKind {
+    fn from_known(error: &KnownError) -> Self {
+        let meta = |key: &str| error.meta.get(key);
+        let string = |key: &str| meta(key).and_then(Value::as_str).map(ToString::to_string);
+
+        match error.error_code.as_ref() {
+            UniqueKeyViolation::ERROR_CODE => Self::UniqueConstraintViolation {
+                model: string("modelName"),
+                fields: strings(meta("target")),
+            },
+            ForeignKeyViolation::ERROR_CODE => Self::ForeignKeyViolation {
+                field: string("field_name"),
+            },
+            NullConstraintViolation::ERROR_CODE => Self::NullConstraintViolation {
+                fields: strings(meta("constraint")),
+            },
+            RelationViolation::ERROR_CODE => Self::RelationViolation {
+                relation: string("relation_name").unwrap_or_default(),
+                model_a: string("model_a_name").unwrap_or_default(),
+                model_b: string("model_b_name").unwrap_or_default(),
+            },
+            RecordRequiredButNotFound::ERROR_CODE => Self::RecordNotFound {
+                model: string("modelName"),
+                cause: string("cause").unwrap_or_else(|| error.message.clone()),
+            },
+            DatabaseOperationTimeout::ERROR_CODE | PoolTimeout::ERROR_CODE => Self::Timeout,
+            TransactionWriteConflict::ERROR_CODE => Self::TransactionConflict,
+            code => Self::Other {
+                code: Some(code.to_string()),
+                message: error.message.clone(),
+            },
+        }
+    }
+}
+
+/// Constraints are reported as either a list of fields or the constraint's name.
+fn strings(value: Option<&Value>) -> Vec<String> {
+    match value {
+        Some(Value::Array(values)) => values
+            .iter()
+            .filter_map(Value::as_str)
+            .map(ToString::to_string)
+            .collect(),
+        Some(Value::String(value)) => vec![value.clone()],
+        _ => vec![],
+    }
 }
 
 pub type Result<T> = std::result::Result<T, QueryError>;
@@ -31,10 +175,15 @@ pub type Result<T> = std::result::Result<T, QueryError>;
 #[cfg(feature = "rspc")]
 impl From<QueryError> for rspc::Error {
     fn from(err: QueryError) -> Self {
-        rspc::Error::with_cause(
-            rspc::ErrorCode::InternalServerError,
-            "Internal server error occurred while completing database operation!".into(),
-            err,
-        )
+        match err {
+            QueryError::NotFound { .. } => {
+                rspc::Error::with_cause(rspc::ErrorCode::NotFound, err.to_string(), err)
+            }
+            _ => rspc::Error::with_cause(
+                rspc::ErrorCode::InternalServerError,
+                "Internal server error occurred while completing database operation!".into(),
+                err,
+            ),
+        }
     }
 }
@@ -1,23 +1,21 @@
 use prisma_models::PrismaValue;
 use query_core::{Operation, Selection};
-use serde_json::Value;
 
 use crate::{raw::Raw, PrismaClientInternals, Query, QueryConvert};
 
+#[derive(Clone)]
 pub struct ExecuteRaw<'a> {
     client: &'a PrismaClientInternals,
-    sql: String,
-    params: Vec<Value>,
+    query: Raw,
+    database: &'static str,
 }
 
 impl<'a> ExecuteRaw<'a> {
     pub fn new(client: &'a PrismaClientInternals, query: Raw, database: &'static str) -> Self {
-        let (sql, params) = query.convert(database);
-
         Self {
             client,
-            sql,
-            params,
+            query,
+            database,
         }
     }
 
@@ -37,15 +35,21 @@ impl<'a> QueryConvert for ExecuteRaw<'a> {
 
 impl<'a> Query<'a> for ExecuteRaw<'a> {
     fn graphql(self) -> (Operation, &'a PrismaClientInternals) {
+        // Queries that can't be converted are rejected by `validate` before being executed
+        let (sql, params) = self
+            .query
+            .convert(self.database)
+            .unwrap_or_else(|_| (self.query.query, self.query.values));
+
         (
             Operation::Write(Selection::new(
                 "executeRaw".to_string(),
                 None,
                 [
-                    ("query".to_string(), PrismaValue::String(self.sql).into()),
+                    ("query".to_string(), PrismaValue::String(sql).into()),
                     (
                         "parameters".to_string(),
-                        PrismaValue::String(serde_json::to_string(&self.params).unwrap()).into(),
+                        PrismaValue::String(serde_json::to_string(&params).unwrap()).into(),
                     ),
                 ],
                 [],
@@ -53,4 +57,8 @@ impl<'a> Query<'a> for ExecuteRaw<'a> {
             self.client,
         )
     }
+
+    fn validate(&self) -> super::Result<()> {
+        self.query.convert(self.database).map(|_| ())
+    }
 }
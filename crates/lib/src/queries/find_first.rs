@@ -2,19 +2,21 @@ use prisma_models::PrismaValue;
 use query_core::{Operation, Selection};
 
 use crate::{
-    merge_fields, Include, IncludeType, ModelOperation, ModelQuery, ModelReadOperation, ModelTypes,
-    OrderByQuery, PaginatedQuery, PrismaClientInternals, Query, QueryConvert, Select, SelectType,
-    WhereInput, WhereQuery, WithQuery,
+    distinct_fields, merge_fields, Include, IncludeType, ModelOperation, ModelQuery,
+    ModelReadOperation, ModelTypes, OrThrow, OrderByQuery, PaginatedQuery, PrismaClientInternals,
+    Query, QueryConvert, Select, SelectType, WhereInput, WhereQuery, WithQuery,
 };
 
-use super::SerializedWhereInput;
+use super::{or_throw::not_found, SerializedWhereInput};
 
+#[derive(Clone)]
 pub struct FindFirst<'a, Actions: ModelTypes> {
     client: &'a PrismaClientInternals,
     pub where_params: Vec<Actions::Where>,
     pub with_params: Vec<Actions::With>,
     pub order_by_params: Vec<Actions::OrderBy>,
     pub cursor_params: Vec<Actions::Cursor>,
+    pub distinct_params: Vec<Actions::ScalarField>,
     pub skip: Option<i64>,
     pub take: Option<i64>,
 }
@@ -27,6 +29,7 @@ impl<'a, Actions: ModelTypes> FindFirst<'a, Actions> {
             with_params: vec![],
             order_by_params: vec![],
             cursor_params: vec![],
+            distinct_params: vec![],
             skip: None,
             take: None,
         }
@@ -47,6 +50,11 @@ impl<'a, Actions: ModelTypes> FindFirst<'a, Actions> {
         self
     }
 
+    pub fn distinct(mut self, fields: Vec<Actions::ScalarField>) -> Self {
+        self.distinct_params.extend(fields);
+        self
+    }
+
     pub fn skip(mut self, skip: i64) -> Self {
         self.skip = Some(skip);
         self
@@ -61,6 +69,7 @@ impl<'a, Actions: ModelTypes> FindFirst<'a, Actions> {
         where_params: Vec<Actions::Where>,
         order_by_params: Vec<Actions::OrderBy>,
         cursor_params: Vec<Actions::Cursor>,
+        distinct_params: Vec<Actions::ScalarField>,
         skip: Option<i64>,
         take: Option<i64>,
         nested_selections: impl IntoIterator<Item = Selection>,
@@ -106,6 +115,12 @@ impl<'a, Actions: ModelTypes> FindFirst<'a, Actions> {
                         .into(),
                     )
                 }),
+                (!distinct_params.is_empty()).then(|| {
+                    (
+                        "distinct".to_string(),
+                        distinct_fields(distinct_params).into(),
+                    )
+                }),
                 skip.map(|skip| ("skip".to_string(), PrismaValue::Int(skip as i64).into())),
                 take.map(|take| ("take".to_string(), PrismaValue::Int(take as i64).into())),
             ]
@@ -125,10 +140,12 @@ impl<'a, Actions: ModelTypes> FindFirst<'a, Actions> {
                 self.where_params,
                 self.order_by_params,
                 self.cursor_params,
+                self.distinct_params,
                 self.skip,
                 self.take,
                 select.to_selections(),
             )),
+            Some((Actions::MODEL, Self::TYPE)),
         )
     }
 
@@ -142,10 +159,12 @@ impl<'a, Actions: ModelTypes> FindFirst<'a, Actions> {
                 self.where_params,
                 self.order_by_params,
                 self.cursor_params,
+                self.distinct_params,
                 self.skip,
                 self.take,
                 include.to_selections(),
             )),
+            Some((Actions::MODEL, Self::TYPE)),
         )
     }
 
@@ -174,6 +193,7 @@ impl<'a, Actions: ModelTypes> Query<'a> for FindFirst<'a, Actions> {
                 self.where_params,
                 self.order_by_params,
                 self.cursor_params,
+                self.distinct_params,
                 self.skip,
                 self.take,
                 scalar_selections,
@@ -181,6 +201,10 @@ impl<'a, Actions: ModelTypes> Query<'a> for FindFirst<'a, Actions> {
             self.client,
         )
     }
+
+    fn model_operation(&self) -> Option<(&'static str, ModelOperation)> {
+        Some((Actions::MODEL, Self::TYPE))
+    }
 }
 
 impl<'a, Actions: ModelTypes> ModelQuery<'a> for FindFirst<'a, Actions> {
@@ -220,3 +244,125 @@ impl<'a, Actions: ModelTypes> PaginatedQuery<'a> for FindFirst<'a, Actions> {
         self.take = Some(take);
     }
 }
+
+#[derive(Clone)]
+pub struct FindFirstOrThrow<'a, Actions: ModelTypes> {
+    inner: FindFirst<'a, Actions>,
+}
+
+impl<'a, Actions: ModelTypes> FindFirstOrThrow<'a, Actions> {
+    pub fn new(client: &'a PrismaClientInternals, where_params: Vec<Actions::Where>) -> Self {
+        Self {
+            inner: FindFirst::new(client, where_params),
+        }
+    }
+
+    pub fn with(mut self, param: impl Into<Actions::With>) -> Self {
+        self.inner = self.inner.with(param);
+        self
+    }
+
+    pub fn order_by(mut self, param: Actions::OrderBy) -> Self {
+        self.inner = self.inner.order_by(param);
+        self
+    }
+
+    pub fn cursor(mut self, param: Actions::Cursor) -> Self {
+        self.inner = self.inner.cursor(param);
+        self
+    }
+
+    pub fn distinct(mut self, fields: Vec<Actions::ScalarField>) -> Self {
+        self.inner = self.inner.distinct(fields);
+        self
+    }
+
+    pub fn skip(mut self, skip: i64) -> Self {
+        self.inner = self.inner.skip(skip);
+        self
+    }
+
+    pub fn take(mut self, take: i64) -> Self {
+        self.inner = self.inner.take(take);
+        self
+    }
+
+    pub fn select<S: SelectType<ModelData = Actions::Data>>(
+        self,
+        select: S,
+    ) -> OrThrow<'a, Self, S::Data> {
+        let (operation, client) = self.inner.select(select).graphql();
+
+        OrThrow::new(client, operation)
+    }
+
+    pub fn include<I: IncludeType<ModelData = Actions::Data>>(
+        self,
+        include: I,
+    ) -> OrThrow<'a, Self, I::Data> {
+        let (operation, client) = self.inner.include(include).graphql();
+
+        OrThrow::new(client, operation)
+    }
+
+    pub async fn exec(self) -> super::Result<Actions::Data> {
+        super::exec(self).await
+    }
+}
+
+impl<'a, Actions: ModelTypes> QueryConvert for FindFirstOrThrow<'a, Actions> {
+    type RawType = Option<Actions::Data>;
+    type ReturnValue = Actions::Data;
+
+    fn convert(raw: Self::RawType) -> super::Result<Self::ReturnValue> {
+        raw.ok_or_else(not_found::<Self>)
+    }
+}
+
+impl<'a, Actions: ModelTypes> Query<'a> for FindFirstOrThrow<'a, Actions> {
+    fn graphql(self) -> (Operation, &'a PrismaClientInternals) {
+        self.inner.graphql()
+    }
+
+    fn model_operation(&self) -> Option<(&'static str, ModelOperation)> {
+        Some((Actions::MODEL, Self::TYPE))
+    }
+}
+
+impl<'a, Actions: ModelTypes> ModelQuery<'a> for FindFirstOrThrow<'a, Actions> {
+    type Types = Actions;
+
+    const TYPE: ModelOperation = ModelOperation::Read(ModelReadOperation::FindFirstOrThrow);
+}
+
+impl<'a, Actions: ModelTypes> WhereQuery<'a> for FindFirstOrThrow<'a, Actions> {
+    fn add_where(&mut self, param: Actions::Where) {
+        self.inner.add_where(param);
+    }
+}
+
+impl<'a, Actions: ModelTypes> WithQuery<'a> for FindFirstOrThrow<'a, Actions> {
+    fn add_with(&mut self, param: impl Into<Actions::With>) {
+        self.inner.add_with(param);
+    }
+}
+
+impl<'a, Actions: ModelTypes> OrderByQuery<'a> for FindFirstOrThrow<'a, Actions> {
+    fn add_order_by(&mut self, param: Actions::OrderBy) {
+        self.inner.add_order_by(param);
+    }
+}
+
+impl<'a, Actions: ModelTypes> PaginatedQuery<'a> for FindFirstOrThrow<'a, Actions> {
+    fn add_cursor(&mut self, param: Actions::Cursor) {
+        self.inner.add_cursor(param);
+    }
+
+    fn set_skip(&mut self, skip: i64) {
+        self.inner.set_skip(skip);
+    }
+
+    fn set_take(&mut self, take: i64) {
+        self.inner.set_take(take);
+    }
+}
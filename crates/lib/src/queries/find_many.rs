@@ -1,20 +1,26 @@
+use bigdecimal::{BigDecimal, FromPrimitive};
+use futures::{stream, Stream, StreamExt};
 use prisma_models::PrismaValue;
 use query_core::{ArgumentValue, Operation, Selection};
+use serde::{de::IntoDeserializer, Deserialize};
 
 use crate::{
-    merge_fields, Include, IncludeType, ModelOperation, ModelQuery, ModelReadOperation, ModelTypes,
-    OrderByQuery, PaginatedQuery, PrismaClientInternals, Query, QueryConvert, Select, SelectType,
-    WhereInput, WhereQuery, WithQuery,
+    distinct_fields, merge_fields, Include, IncludeType, MiddlewareQuery, ModelOperation,
+    ModelQuery, ModelReadOperation, ModelTypes, OrderByQuery, PaginatedQuery,
+    PrismaClientInternals, Query, QueryConvert, QueryError, Select, SelectType, WhereInput,
+    WhereQuery, WithQuery,
 };
 
-use super::SerializedWhereInput;
+use super::{query, SerializedWhereInput};
 
+#[derive(Clone)]
 pub struct FindMany<'a, Actions: ModelTypes> {
     client: &'a PrismaClientInternals,
     pub where_params: Vec<Actions::Where>,
     pub with_params: Vec<Actions::With>,
     pub order_by_params: Vec<Actions::OrderBy>,
     pub cursor_params: Vec<Actions::Cursor>,
+    pub distinct_params: Vec<Actions::ScalarField>,
     pub skip: Option<i64>,
     pub take: Option<i64>,
 }
@@ -27,6 +33,7 @@ impl<'a, Actions: ModelTypes> FindMany<'a, Actions> {
             with_params: vec![],
             order_by_params: vec![],
             cursor_params: vec![],
+            distinct_params: vec![],
             skip: None,
             take: None,
         }
@@ -47,6 +54,11 @@ impl<'a, Actions: ModelTypes> FindMany<'a, Actions> {
         self
     }
 
+    pub fn distinct(mut self, fields: Vec<Actions::ScalarField>) -> Self {
+        self.distinct_params.extend(fields);
+        self
+    }
+
     pub fn skip(mut self, skip: i64) -> Self {
         self.skip = Some(skip);
         self
@@ -57,10 +69,24 @@ impl<'a, Actions: ModelTypes> FindMany<'a, Actions> {
         self
     }
 
+    /// Adds the where, order by and pagination params from arguments parsed from untrusted input.
+    #[cfg(feature = "serde-filters")]
+    pub fn with_args(
+        mut self,
+        args: crate::filters::FindManyArgs<Actions::Where, Actions::OrderBy>,
+    ) -> Self {
+        self.where_params.extend(args.where_params);
+        self.order_by_params.extend(args.order_by_params);
+        self.skip = args.skip.or(self.skip);
+        self.take = args.take.or(self.take);
+        self
+    }
+
     fn to_selection(
         where_params: Vec<Actions::Where>,
         order_by_params: Vec<Actions::OrderBy>,
         cursor_params: Vec<Actions::Cursor>,
+        distinct_params: Vec<Actions::ScalarField>,
         skip: Option<i64>,
         take: Option<i64>,
         nested_selections: impl IntoIterator<Item = Selection>,
@@ -106,6 +132,12 @@ impl<'a, Actions: ModelTypes> FindMany<'a, Actions> {
                         .into(),
                     )
                 }),
+                (!distinct_params.is_empty()).then(|| {
+                    (
+                        "distinct".to_string(),
+                        distinct_fields(distinct_params).into(),
+                    )
+                }),
                 skip.map(|skip| ("skip".to_string(), PrismaValue::Int(skip as i64).into())),
                 take.map(|take| ("take".to_string(), PrismaValue::Int(take as i64).into())),
             ]
@@ -125,10 +157,12 @@ impl<'a, Actions: ModelTypes> FindMany<'a, Actions> {
                 self.where_params,
                 self.order_by_params,
                 self.cursor_params,
+                self.distinct_params,
                 self.skip,
                 self.take,
                 select.to_selections(),
             )),
+            Some((Actions::MODEL, Self::TYPE)),
         )
     }
 
@@ -142,16 +176,300 @@ impl<'a, Actions: ModelTypes> FindMany<'a, Actions> {
                 self.where_params,
                 self.order_by_params,
                 self.cursor_params,
+                self.distinct_params,
                 self.skip,
                 self.take,
                 include.to_selections(),
             )),
+            Some((Actions::MODEL, Self::TYPE)),
         )
     }
 
     pub async fn exec(self) -> super::Result<Vec<Actions::Data>> {
         super::exec(self).await
     }
+
+    /// Streams matching records, fetching `batch_size` records at a time
+    /// using cursor pagination ordered by the model's primary key.
+    ///
+    /// Any `order_by`, `cursor` and `skip` arguments are applied before the primary key,
+    /// and `take` limits the total number of records streamed.
+    /// The stream yields a single error if `batch_size` isn't greater than 0, if `take` is negative,
+    /// or if `distinct` was used, since records that are only duplicates of records
+    /// in other pages wouldn't be removed.
+    pub fn stream(
+        mut self,
+        batch_size: i64,
+    ) -> impl Stream<Item = super::Result<Actions::Data>> + 'a
+    where
+        Actions: 'a,
+    {
+        let mut scalar_selections = Actions::scalar_selections();
+
+        scalar_selections.extend(
+            std::mem::take(&mut self.with_params)
+                .into_iter()
+                .map(Into::into),
+        );
+
+        self.paginate(batch_size, scalar_selections)
+    }
+
+    /// Same as [`FindMany::stream`], but yields the selected data of each record.
+    pub fn stream_select<S: SelectType<ModelData = Actions::Data>>(
+        self,
+        select: S,
+        batch_size: i64,
+    ) -> impl Stream<Item = super::Result<S::Data>> + 'a
+    where
+        Actions: 'a,
+    {
+        self.paginate(batch_size, select.to_selections())
+    }
+
+    /// Same as [`FindMany::stream`], but yields the included data of each record.
+    pub fn stream_include<I: IncludeType<ModelData = Actions::Data>>(
+        self,
+        include: I,
+        batch_size: i64,
+    ) -> impl Stream<Item = super::Result<I::Data>> + 'a
+    where
+        Actions: 'a,
+    {
+        self.paginate(batch_size, include.to_selections())
+    }
+
+    fn paginate<Data: query::Data>(
+        self,
+        batch_size: i64,
+        mut nested_selections: Vec<Selection>,
+    ) -> impl Stream<Item = super::Result<Data>> + 'a
+    where
+        Actions: 'a,
+    {
+        let Self {
+            client,
+            where_params,
+            order_by_params,
+            cursor_params,
+            distinct_params,
+            skip,
+            take,
+            ..
+        } = self;
+
+        let where_arg = (!where_params.is_empty()).then(|| {
+            PrismaValue::Object(merge_fields(
+                where_params
+                    .into_iter()
+                    .map(WhereInput::serialize)
+                    .map(|s| (s.field, s.value.into()))
+                    .collect(),
+            ))
+        });
+
+        let mut order_by = order_by_params
+            .into_iter()
+            .map(|p| PrismaValue::Object(vec![p.into()]))
+            .collect::<Vec<_>>();
+        order_by.extend(Actions::PRIMARY_KEY.iter().map(|field| {
+            PrismaValue::Object(vec![(
+                field.to_string(),
+                PrismaValue::String("asc".to_string()),
+            )])
+        }));
+
+        // primary key fields are selected under an alias so that the cursor
+        // is available regardless of what the caller selected
+        nested_selections.extend(
+            Actions::PRIMARY_KEY
+                .iter()
+                .map(|field| Selection::new(*field, Some(cursor_alias(field)), [], [])),
+        );
+
+        let invalid = if Actions::PRIMARY_KEY.is_empty() {
+            Some(format!(
+                "Model {} has no primary key or unique fields to stream by",
+                Actions::MODEL
+            ))
+        } else if batch_size <= 0 {
+            Some(format!(
+                "Stream batch size must be greater than 0, got {batch_size}"
+            ))
+        } else if let Some(take) = take.filter(|take| *take < 0) {
+            Some(format!("Stream take can't be negative, got {take}"))
+        } else if !distinct_params.is_empty() {
+            Some("distinct can't be used when streaming".to_string())
+        } else {
+            None
+        };
+
+        let initial = StreamPage {
+            cursor: (!cursor_params.is_empty()).then(|| {
+                PrismaValue::Object(
+                    cursor_params
+                        .into_iter()
+                        .map(Into::into)
+                        .map(WhereInput::serialize)
+                        .map(SerializedWhereInput::transform_equals)
+                        .collect(),
+                )
+            }),
+            skip,
+            remaining: take,
+            done: invalid.is_some(),
+        };
+
+        let pages = stream::unfold(initial, move |page| {
+            let where_arg = where_arg.clone();
+            let order_by = order_by.clone();
+            let nested_selections = nested_selections.clone();
+
+            async move {
+                if page.done || page.remaining.map(|r| r <= 0).unwrap_or(false) {
+                    return None;
+                }
+
+                let take = page
+                    .remaining
+                    .map(|r| r.min(batch_size))
+                    .unwrap_or(batch_size);
+
+                let selection = Self::base_selection(
+                    [
+                        where_arg.map(|w| ("where".to_string(), w.into())),
+                        Some(("orderBy".to_string(), PrismaValue::List(order_by).into())),
+                        page.cursor.map(|c| ("cursor".to_string(), c.into())),
+                        page.skip
+                            .map(|skip| ("skip".to_string(), PrismaValue::Int(skip).into())),
+                        Some(("take".to_string(), PrismaValue::Int(take).into())),
+                    ]
+                    .into_iter()
+                    .flatten(),
+                    nested_selections,
+                );
+
+                let query = MiddlewareQuery::new(
+                    Operation::Read(selection),
+                    Some((Actions::MODEL, Self::TYPE)),
+                );
+
+                let items = match client.execute(query).await {
+                    Ok(serde_value::Value::Seq(items)) => items,
+                    Ok(_) => {
+                        return Some((
+                            vec![Err(QueryError::Deserialize(
+                                "Expected a list of records".to_string(),
+                            ))],
+                            StreamPage::finished(),
+                        ))
+                    }
+                    Err(e) => return Some((vec![Err(e)], StreamPage::finished())),
+                };
+
+                let next = match items.last().map(next_cursor::<Actions>) {
+                    Some(Ok(cursor)) => StreamPage {
+                        cursor: Some(cursor),
+                        skip: Some(1),
+                        remaining: page.remaining.map(|r| r - items.len() as i64),
+                        done: (items.len() as i64) < take,
+                    },
+                    Some(Err(e)) => return Some((vec![Err(e)], StreamPage::finished())),
+                    None => StreamPage::finished(),
+                };
+
+                let results = items
+                    .into_iter()
+                    .map(|item| {
+                        Data::deserialize(item.into_deserializer())
+                            .map_err(|e| e.to_string())
+                            .map_err(QueryError::Deserialize)
+                    })
+                    .collect::<Vec<_>>();
+
+                Some((results, next))
+            }
+        });
+
+        stream::iter(invalid.map(|e| Err(QueryError::Serialize(e))))
+            .chain(pages.flat_map(stream::iter))
+    }
+}
+
+struct StreamPage {
+    cursor: Option<PrismaValue>,
+    skip: Option<i64>,
+    remaining: Option<i64>,
+    done: bool,
+}
+
+impl StreamPage {
+    fn finished() -> Self {
+        Self {
+            cursor: None,
+            skip: None,
+            remaining: None,
+            done: true,
+        }
+    }
+}
+
+fn cursor_alias(field: &str) -> String {
+    format!("_cursor_{field}")
+}
+
+/// Builds a cursor pointing at `record` from its aliased primary key fields
+fn next_cursor<Actions: ModelTypes>(record: &serde_value::Value) -> super::Result<PrismaValue> {
+    let record = match record {
+        serde_value::Value::Map(record) => record,
+        _ => return Err(QueryError::Deserialize("Expected a record".to_string())),
+    };
+
+    let fields = Actions::PRIMARY_KEY
+        .iter()
+        .map(|field| {
+            record
+                .get(&serde_value::Value::String(cursor_alias(field)))
+                .cloned()
+                .and_then(cursor_value)
+                .map(|value| (field.to_string(), value))
+                .ok_or_else(|| {
+                    QueryError::Deserialize(format!(
+                        "Missing primary key field '{field}' in streamed record"
+                    ))
+                })
+        })
+        .collect::<super::Result<Vec<_>>>()?;
+
+    Ok(match &fields[..] {
+        [_] => PrismaValue::Object(fields),
+        _ => PrismaValue::Object(vec![(
+            Actions::PRIMARY_KEY_NAME.to_string(),
+            PrismaValue::Object(fields),
+        )]),
+    })
+}
+
+fn cursor_value(value: serde_value::Value) -> Option<PrismaValue> {
+    use serde_value::Value;
+
+    Some(match value {
+        Value::String(v) => PrismaValue::String(v),
+        Value::Bool(v) => PrismaValue::Boolean(v),
+        Value::I8(v) => PrismaValue::Int(v as i64),
+        Value::I16(v) => PrismaValue::Int(v as i64),
+        Value::I32(v) => PrismaValue::Int(v as i64),
+        Value::I64(v) => PrismaValue::Int(v),
+        Value::U8(v) => PrismaValue::Int(v as i64),
+        Value::U16(v) => PrismaValue::Int(v as i64),
+        Value::U32(v) => PrismaValue::Int(v as i64),
+        Value::U64(v) => PrismaValue::Int(v as i64),
+        Value::F32(v) => PrismaValue::Float(BigDecimal::from_f32(v)?),
+        Value::F64(v) => PrismaValue::Float(BigDecimal::from_f64(v)?),
+        Value::Bytes(v) => PrismaValue::Bytes(v),
+        Value::Option(Some(v)) | Value::Newtype(v) => cursor_value(*v)?,
+        _ => return None,
+    })
 }
 
 impl<'a, Actions: ModelTypes> QueryConvert for FindMany<'a, Actions> {
@@ -174,6 +492,7 @@ impl<'a, Actions: ModelTypes> Query<'a> for FindMany<'a, Actions> {
                 self.where_params,
                 self.order_by_params,
                 self.cursor_params,
+                self.distinct_params,
                 self.skip,
                 self.take,
                 scalar_selections,
@@ -181,6 +500,10 @@ impl<'a, Actions: ModelTypes> Query<'a> for FindMany<'a, Actions> {
             self.client,
         )
     }
+
+    fn model_operation(&self) -> Option<(&'static str, ModelOperation)> {
+        Some((Actions::MODEL, Self::TYPE))
+    }
 }
 
 impl<'a, Actions: ModelTypes> ModelQuery<'a> for FindMany<'a, Actions> {
@@ -221,12 +544,13 @@ impl<'a, Actions: ModelTypes> PaginatedQuery<'a> for FindMany<'a, Actions> {
     }
 }
 
-#[derive(Clone)]
+#[derive(Clone, Debug)]
 pub struct ManyArgs<Actions: ModelTypes> {
     pub where_params: Vec<Actions::Where>,
     pub with_params: Vec<Actions::With>,
     pub order_by_params: Vec<Actions::OrderBy>,
     pub cursor_params: Vec<Actions::Cursor>,
+    pub distinct_params: Vec<Actions::ScalarField>,
     pub skip: Option<i64>,
     pub take: Option<i64>,
 }
@@ -238,6 +562,7 @@ impl<Actions: ModelTypes> ManyArgs<Actions> {
             with_params: vec![],
             order_by_params: vec![],
             cursor_params: vec![],
+            distinct_params: vec![],
             skip: None,
             take: None,
         }
@@ -258,6 +583,11 @@ impl<Actions: ModelTypes> ManyArgs<Actions> {
         self
     }
 
+    pub fn distinct(mut self, fields: Vec<Actions::ScalarField>) -> Self {
+        self.distinct_params.extend(fields);
+        self
+    }
+
     pub fn skip(mut self, skip: i64) -> Self {
         self.skip = Some(skip);
         self
@@ -309,6 +639,12 @@ impl<Actions: ModelTypes> ManyArgs<Actions> {
                     .into(),
                 )
             }),
+            (!self.distinct_params.is_empty()).then(|| {
+                (
+                    "distinct".to_string(),
+                    distinct_fields(self.distinct_params).into(),
+                )
+            }),
             self.skip
                 .map(|skip| ("skip".to_string(), PrismaValue::Int(skip).into())),
             self.take
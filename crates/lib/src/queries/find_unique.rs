@@ -4,10 +4,13 @@ use prisma_models::PrismaValue;
 use query_core::{Operation, Selection};
 
 use crate::{
-    Include, IncludeType, ModelOperation, ModelQuery, ModelReadOperation, ModelTypes,
+    Include, IncludeType, ModelOperation, ModelQuery, ModelReadOperation, ModelTypes, OrThrow,
     PrismaClientInternals, Query, QueryConvert, Select, SelectType, WhereInput, WithQuery,
 };
 
+use super::or_throw::not_found;
+
+#[derive(Clone)]
 pub struct FindUnique<'a, Actions: ModelTypes> {
     client: &'a PrismaClientInternals,
     pub where_param: Actions::Where,
@@ -50,6 +53,7 @@ impl<'a, Actions: ModelTypes> FindUnique<'a, Actions> {
         Select::new(
             self.client,
             Operation::Read(Self::to_selection(self.where_param, select.to_selections())),
+            Some((Actions::MODEL, Self::TYPE)),
         )
     }
 
@@ -63,6 +67,7 @@ impl<'a, Actions: ModelTypes> FindUnique<'a, Actions> {
                 self.where_param,
                 include.to_selections(),
             )),
+            Some((Actions::MODEL, Self::TYPE)),
         )
     }
 
@@ -91,6 +96,10 @@ impl<'a, Actions: ModelTypes> Query<'a> for FindUnique<'a, Actions> {
             self.client,
         )
     }
+
+    fn model_operation(&self) -> Option<(&'static str, ModelOperation)> {
+        Some((Actions::MODEL, Self::TYPE))
+    }
 }
 
 impl<'a, Actions: ModelTypes> ModelQuery<'a> for FindUnique<'a, Actions> {
@@ -106,6 +115,77 @@ impl<'a, Actions: ModelTypes> WithQuery<'a> for FindUnique<'a, Actions> {
 }
 
 #[derive(Clone)]
+pub struct FindUniqueOrThrow<'a, Actions: ModelTypes> {
+    inner: FindUnique<'a, Actions>,
+}
+
+impl<'a, Actions: ModelTypes> FindUniqueOrThrow<'a, Actions> {
+    pub fn new(client: &'a PrismaClientInternals, where_param: Actions::Where) -> Self {
+        Self {
+            inner: FindUnique::new(client, where_param),
+        }
+    }
+
+    pub fn with(mut self, param: impl Into<Actions::With>) -> Self {
+        self.inner = self.inner.with(param);
+        self
+    }
+
+    pub fn select<S: SelectType<ModelData = Actions::Data>>(
+        self,
+        select: S,
+    ) -> OrThrow<'a, Self, S::Data> {
+        let (operation, client) = self.inner.select(select).graphql();
+
+        OrThrow::new(client, operation)
+    }
+
+    pub fn include<I: IncludeType<ModelData = Actions::Data>>(
+        self,
+        include: I,
+    ) -> OrThrow<'a, Self, I::Data> {
+        let (operation, client) = self.inner.include(include).graphql();
+
+        OrThrow::new(client, operation)
+    }
+
+    pub async fn exec(self) -> super::Result<Actions::Data> {
+        super::exec(self).await
+    }
+}
+
+impl<'a, Actions: ModelTypes> QueryConvert for FindUniqueOrThrow<'a, Actions> {
+    type RawType = Option<Actions::Data>;
+    type ReturnValue = Actions::Data;
+
+    fn convert(raw: Self::RawType) -> super::Result<Self::ReturnValue> {
+        raw.ok_or_else(not_found::<Self>)
+    }
+}
+
+impl<'a, Actions: ModelTypes> Query<'a> for FindUniqueOrThrow<'a, Actions> {
+    fn graphql(self) -> (Operation, &'a PrismaClientInternals) {
+        self.inner.graphql()
+    }
+
+    fn model_operation(&self) -> Option<(&'static str, ModelOperation)> {
+        Some((Actions::MODEL, Self::TYPE))
+    }
+}
+
+impl<'a, Actions: ModelTypes> ModelQuery<'a> for FindUniqueOrThrow<'a, Actions> {
+    type Types = Actions;
+
+    const TYPE: ModelOperation = ModelOperation::Read(ModelReadOperation::FindUniqueOrThrow);
+}
+
+impl<'a, Actions: ModelTypes> WithQuery<'a> for FindUniqueOrThrow<'a, Actions> {
+    fn add_with(&mut self, param: impl Into<Actions::With>) {
+        self.inner.add_with(param);
+    }
+}
+
+#[derive(Clone, Debug)]
 pub struct UniqueArgs<Actions>
 where
     Actions: ModelTypes,
@@ -0,0 +1,165 @@
+use prisma_models::PrismaValue;
+use query_core::{Operation, Selection};
+
+use crate::{
+    aggregate_selections, merge_fields, sel, ModelOperation, ModelQuery, ModelReadOperation,
+    ModelTypes, PrismaClientInternals, Query, QueryConvert, WhereInput, WhereQuery,
+};
+
+#[derive(Clone)]
+pub struct GroupBy<'a, Actions: ModelTypes> {
+    client: &'a PrismaClientInternals,
+    pub by: Vec<Actions::ScalarField>,
+    pub where_params: Vec<Actions::Where>,
+    pub having_params: Vec<Actions::Having>,
+    pub aggregate_params: Vec<Actions::Aggregate>,
+    pub order_by_params: Vec<Actions::OrderByWithAggregation>,
+    pub skip: Option<i64>,
+    pub take: Option<i64>,
+}
+
+impl<'a, Actions: ModelTypes> GroupBy<'a, Actions> {
+    pub fn new(
+        client: &'a PrismaClientInternals,
+        by: Vec<Actions::ScalarField>,
+        where_params: Vec<Actions::Where>,
+    ) -> Self {
+        Self {
+            client,
+            by,
+            where_params,
+            having_params: vec![],
+            aggregate_params: vec![],
+            order_by_params: vec![],
+            skip: None,
+            take: None,
+        }
+    }
+
+    /// Filters groups by aggregates or by scalar field filters.
+    /// Relation filters are accepted here but rejected by the query engine when executed.
+    pub fn having(mut self, param: Actions::Having) -> Self {
+        self.having_params.push(param);
+        self
+    }
+
+    pub fn aggregate(mut self, param: Actions::Aggregate) -> Self {
+        self.aggregate_params.push(param);
+        self
+    }
+
+    pub fn order_by(mut self, param: Actions::OrderByWithAggregation) -> Self {
+        self.order_by_params.push(param);
+        self
+    }
+
+    pub fn skip(mut self, skip: i64) -> Self {
+        self.skip = Some(skip);
+        self
+    }
+
+    pub fn take(mut self, take: i64) -> Self {
+        self.take = Some(take);
+        self
+    }
+
+    pub async fn exec(self) -> super::Result<Vec<Actions::GroupByData>> {
+        super::exec(self).await
+    }
+}
+
+impl<'a, Actions: ModelTypes> QueryConvert for GroupBy<'a, Actions> {
+    type RawType = Vec<Actions::GroupByData>;
+    type ReturnValue = Self::RawType;
+
+    fn convert(raw: Self::RawType) -> super::Result<Self::ReturnValue> {
+        Ok(raw)
+    }
+}
+
+impl<'a, Actions: ModelTypes> Query<'a> for GroupBy<'a, Actions> {
+    fn graphql(self) -> (Operation, &'a PrismaClientInternals) {
+        let by = self.by.into_iter().map(Into::into).collect::<Vec<String>>();
+
+        let mut nested_selections = by
+            .iter()
+            .map(|field| sel(field))
+            .collect::<Vec<Selection>>();
+        nested_selections.extend(aggregate_selections(
+            self.aggregate_params.into_iter().map(Into::into),
+        ));
+
+        (
+            Operation::Read(Self::base_selection(
+                [
+                    Some((
+                        "by".to_string(),
+                        PrismaValue::List(by.into_iter().map(PrismaValue::Enum).collect()).into(),
+                    )),
+                    (!self.where_params.is_empty()).then(|| {
+                        (
+                            "where".to_string(),
+                            PrismaValue::Object(merge_fields(
+                                self.where_params
+                                    .into_iter()
+                                    .map(WhereInput::serialize)
+                                    .map(|s| (s.field, s.value.into()))
+                                    .collect(),
+                            ))
+                            .into(),
+                        )
+                    }),
+                    (!self.having_params.is_empty()).then(|| {
+                        (
+                            "having".to_string(),
+                            PrismaValue::Object(merge_fields(
+                                self.having_params
+                                    .into_iter()
+                                    .map(WhereInput::serialize)
+                                    .map(|s| (s.field, s.value.into()))
+                                    .collect(),
+                            ))
+                            .into(),
+                        )
+                    }),
+                    (!self.order_by_params.is_empty()).then(|| {
+                        (
+                            "orderBy".to_string(),
+                            PrismaValue::List(
+                                self.order_by_params
+                                    .into_iter()
+                                    .map(|p| PrismaValue::Object(vec![p.into()]))
+                                    .collect(),
+                            )
+                            .into(),
+                        )
+                    }),
+                    self.skip
+                        .map(|skip| ("skip".to_string(), PrismaValue::Int(skip as i64).into())),
+                    self.take
+                        .map(|take| ("take".to_string(), PrismaValue::Int(take as i64).into())),
+                ]
+                .into_iter()
+                .flatten(),
+                nested_selections,
+            )),
+            self.client,
+        )
+    }
+
+    fn model_operation(&self) -> Option<(&'static str, ModelOperation)> {
+        Some((Actions::MODEL, Self::TYPE))
+    }
+}
+
+impl<'a, Actions: ModelTypes> ModelQuery<'a> for GroupBy<'a, Actions> {
+    type Types = Actions;
+
+    const TYPE: ModelOperation = ModelOperation::Read(ModelReadOperation::GroupBy);
+}
+
+impl<'a, Actions: ModelTypes> WhereQuery<'a> for GroupBy<'a, Actions> {
+    fn add_where(&mut self, param: Actions::Where) {
+        self.where_params.push(param);
+    }
+}
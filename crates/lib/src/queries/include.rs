@@ -1,7 +1,7 @@
 use query_core::{Operation, Selection};
 use std::marker::PhantomData;
 
-use crate::{PrismaClientInternals, Query, QueryConvert};
+use crate::{ModelOperation, PrismaClientInternals, Query, QueryConvert};
 
 use super::query;
 
@@ -16,14 +16,31 @@ pub trait IncludeType {
 pub struct Include<'a, Data> {
     operation: Operation,
     client: &'a PrismaClientInternals,
+    model_operation: Option<(&'static str, ModelOperation)>,
     _data: PhantomData<Data>,
 }
 
+impl<'a, Data> Clone for Include<'a, Data> {
+    fn clone(&self) -> Self {
+        Self {
+            operation: self.operation.clone(),
+            client: self.client,
+            model_operation: self.model_operation,
+            _data: PhantomData,
+        }
+    }
+}
+
 impl<'a, Data: query::Data> Include<'a, Data> {
-    pub fn new(client: &'a PrismaClientInternals, operation: Operation) -> Self {
+    pub fn new(
+        client: &'a PrismaClientInternals,
+        operation: Operation,
+        model_operation: Option<(&'static str, ModelOperation)>,
+    ) -> Self {
         Self {
             client,
             operation,
+            model_operation,
             _data: PhantomData {},
         }
     }
@@ -46,4 +63,8 @@ impl<'a, Data: query::Data> Query<'a> for Include<'a, Data> {
     fn graphql(self) -> (Operation, &'a PrismaClientInternals) {
         (self.operation, self.client)
     }
+
+    fn model_operation(&self) -> Option<(&'static str, ModelOperation)> {
+        self.model_operation
+    }
 }
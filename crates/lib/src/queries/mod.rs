@@ -1,3 +1,4 @@
+mod aggregate;
 mod batch;
 mod count;
 mod create;
@@ -5,13 +6,16 @@ mod create_many;
 mod create_unchecked;
 mod delete;
 mod delete_many;
+mod document;
 mod error;
 mod execute_raw;
 mod find_first;
 mod find_many;
 mod find_unique;
+mod group_by;
 mod include;
 mod mongo_raw;
+mod or_throw;
 mod query;
 mod query_raw;
 mod select;
@@ -20,6 +24,7 @@ mod update_many;
 mod update_unchecked;
 mod upsert;
 
+pub use aggregate::*;
 pub use batch::*;
 pub use count::*;
 pub use create::*;
@@ -27,13 +32,16 @@ pub use create_many::*;
 pub use create_unchecked::*;
 pub use delete::*;
 pub use delete_many::*;
+pub use document::*;
 pub use error::*;
 pub use execute_raw::*;
 pub use find_first::*;
 pub use find_many::*;
 pub use find_unique::*;
+pub use group_by::*;
 pub use include::*;
 pub use mongo_raw::*;
+pub use or_throw::OrThrow;
 pub use query::*;
 pub use query_raw::*;
 pub use select::*;
@@ -42,13 +50,12 @@ pub use update_many::*;
 pub use update_unchecked::*;
 pub use upsert::*;
 
-use futures::FutureExt;
 pub use query_core::{schema::QuerySchemaRef, Operation, Selection};
 use serde::de::IntoDeserializer;
 use serde::Deserialize;
 use std::future::Future;
 
-use crate::ExecutionEngine;
+use crate::MiddlewareQuery;
 
 pub enum SerializedWhereValue {
     Object(Vec<(String, prisma_models::PrismaValue)>),
@@ -113,20 +120,23 @@ impl Into<(String, prisma_models::PrismaValue)> for SerializedWhereInput {
 pub fn exec<'a, Q: Query<'a> + 'a>(
     query: Q,
 ) -> impl Future<Output = Result<<Q as QueryConvert>::ReturnValue>> + 'a {
-    let (op, client) = query.graphql();
+    let model_operation = query.model_operation();
+    let operation = query.validate().map(|_| query.graphql());
 
-    client.execute(op).map(|value| {
-        let value = value?;
+    async move {
+        let (op, client) = operation?;
+        let value = client
+            .execute(MiddlewareQuery::new(op, model_operation))
+            .await?;
 
-        Ok(match client.engine {
-            ExecutionEngine::Real { .. } => Q::RawType::deserialize(value.into_deserializer())
+        Ok(match client.engine.is_mock() {
+            false => Q::RawType::deserialize(value.into_deserializer())
                 .map_err(|e| e.to_string())
                 .map_err(QueryError::Deserialize)
                 .and_then(Q::convert)?,
-            #[cfg(feature = "mocking")]
-            ExecutionEngine::Mock(_) => Q::ReturnValue::deserialize(value.into_deserializer())
+            true => Q::ReturnValue::deserialize(value.into_deserializer())
                 .map_err(|e| e.to_string())
                 .map_err(QueryError::Deserialize)?,
         })
-    })
+    }
 }
@@ -15,6 +15,17 @@ where
     command: Value,
     _data: PhantomData<Data>,
 }
+
+impl<'a, Data: DeserializeOwned> Clone for RunCommandRaw<'a, Data> {
+    fn clone(&self) -> Self {
+        Self {
+            client: self.client,
+            command: self.command.clone(),
+            _data: PhantomData,
+        }
+    }
+}
+
 impl<'a, Data> RunCommandRaw<'a, Data>
 where
     Data: DeserializeOwned + 'static,
@@ -76,6 +87,18 @@ pub struct FindRaw<'a, Types, Data> {
     options: Option<Value>,
     _data: PhantomData<(Data, Types)>,
 }
+
+impl<'a, Types, Data> Clone for FindRaw<'a, Types, Data> {
+    fn clone(&self) -> Self {
+        Self {
+            client: self.client,
+            filter: self.filter.clone(),
+            options: self.options.clone(),
+            _data: PhantomData,
+        }
+    }
+}
+
 impl<'a, Types, Data> FindRaw<'a, Types, Data>
 where
     Types: ModelTypes,
@@ -163,6 +186,18 @@ pub struct AggregateRaw<'a, Types, Data> {
     options: Option<Value>,
     _data: PhantomData<(Data, Types)>,
 }
+
+impl<'a, Types, Data> Clone for AggregateRaw<'a, Types, Data> {
+    fn clone(&self) -> Self {
+        Self {
+            client: self.client,
+            pipeline: self.pipeline.clone(),
+            options: self.options.clone(),
+            _data: PhantomData,
+        }
+    }
+}
+
 impl<'a, Types, Data> AggregateRaw<'a, Types, Data>
 where
     Types: ModelTypes,
@@ -0,0 +1,68 @@
+use std::marker::PhantomData;
+
+use query_core::Operation;
+
+use crate::{
+    ModelOperation, ModelQuery, ModelTypes, PrismaClientInternals, Query, QueryConvert, QueryError,
+};
+
+use super::query;
+
+/// Result of calling `select` or `include` on an `*_or_throw` query.
+/// Fails with [`QueryError::NotFound`] instead of returning `None`,
+/// attributing the error to the model and operation of `Q`.
+pub struct OrThrow<'a, Q: ModelQuery<'a>, Data> {
+    operation: Operation,
+    client: &'a PrismaClientInternals,
+    _data: PhantomData<(Q, Data)>,
+}
+
+impl<'a, Q: ModelQuery<'a>, Data> Clone for OrThrow<'a, Q, Data> {
+    fn clone(&self) -> Self {
+        Self {
+            operation: self.operation.clone(),
+            client: self.client,
+            _data: PhantomData,
+        }
+    }
+}
+
+impl<'a, Q: ModelQuery<'a>, Data: query::Data> OrThrow<'a, Q, Data> {
+    pub fn new(client: &'a PrismaClientInternals, operation: Operation) -> Self {
+        Self {
+            client,
+            operation,
+            _data: PhantomData {},
+        }
+    }
+
+    pub async fn exec(self) -> super::Result<Data> {
+        super::exec(self).await
+    }
+}
+
+impl<'a, Q: ModelQuery<'a>, Data: query::Data> QueryConvert for OrThrow<'a, Q, Data> {
+    type RawType = Option<Data>;
+    type ReturnValue = Data;
+
+    fn convert(raw: Self::RawType) -> super::Result<Self::ReturnValue> {
+        raw.ok_or_else(not_found::<Q>)
+    }
+}
+
+impl<'a, Q: ModelQuery<'a>, Data: query::Data> Query<'a> for OrThrow<'a, Q, Data> {
+    fn graphql(self) -> (Operation, &'a PrismaClientInternals) {
+        (self.operation, self.client)
+    }
+
+    fn model_operation(&self) -> Option<(&'static str, ModelOperation)> {
+        Some((<Q::Types as ModelTypes>::MODEL, Q::TYPE))
+    }
+}
+
+pub(crate) fn not_found<'a, Q: ModelQuery<'a>>() -> QueryError {
+    QueryError::NotFound {
+        model: <Q::Types as ModelTypes>::MODEL,
+        operation: Q::TYPE.name(),
+    }
+}
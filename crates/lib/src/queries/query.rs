@@ -15,6 +15,29 @@ pub trait QueryConvert {
 
 pub trait Query<'a>: QueryConvert {
     fn graphql(self) -> (Operation, &'a PrismaClientInternals);
+
+    /// Checked before the query is sent to the engine, whether on its own or in a batch.
+    /// Used by queries that can be built in an unsendable state, such as raw queries.
+    fn validate(&self) -> super::Result<()> {
+        Ok(())
+    }
+
+    /// The model and operation the query performs, which middleware sees on each query.
+    /// `None` for queries that don't operate on a model, such as raw queries.
+    fn model_operation(&self) -> Option<(&'static str, ModelOperation)> {
+        None
+    }
+
+    /// Renders the Prisma query document that executing this query would send,
+    /// leaving the query available to be executed afterwards.
+    fn to_query_string(&self) -> super::Result<String>
+    where
+        Self: Clone,
+    {
+        self.validate()?;
+
+        Ok(super::QueryDocument(&self.clone().graphql().0).to_string())
+    }
 }
 
 pub trait ModelTypes {
@@ -25,8 +48,20 @@ pub trait ModelTypes {
     type With: Into<Selection>;
     type OrderBy: Into<(String, PrismaValue)>;
     type Cursor: Into<Self::Where>;
+    type Aggregate: Into<(String, Selection)>;
+    type AggregateData: Data;
+    type ScalarField: Into<String>;
+    type Having: WhereInput;
+    type OrderByWithAggregation: Into<(String, PrismaValue)>;
+    type GroupByData: Data;
 
     const MODEL: &'static str;
+    /// Fields of the model's primary key, used for ordering and cursors when streaming.
+    /// Falls back to the first unique criteria if the model has no primary key.
+    const PRIMARY_KEY: &'static [&'static str];
+    /// Name of the compound selector for [`PRIMARY_KEY`](Self::PRIMARY_KEY) in unique filters,
+    /// which is the `name` argument of its `@@id` or `@@unique` if there is one.
+    const PRIMARY_KEY_NAME: &'static str;
 
     fn scalar_selections() -> Vec<Selection>;
 }
@@ -34,18 +69,25 @@ pub trait ModelTypes {
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum ModelReadOperation {
     FindUnique,
+    FindUniqueOrThrow,
     FindFirst,
+    FindFirstOrThrow,
     FindMany,
     Count,
+    Aggregate,
+    GroupBy,
 }
 
 impl ModelReadOperation {
     pub fn name(&self) -> &'static str {
         match self {
             Self::FindUnique => "findUnique",
+            Self::FindUniqueOrThrow => "findUniqueOrThrow",
             Self::FindFirst => "findFirst",
+            Self::FindFirstOrThrow => "findFirstOrThrow",
             Self::FindMany => "findMany",
-            Self::Count => "aggregate",
+            Self::Count | Self::Aggregate => "aggregate",
+            Self::GroupBy => "groupBy",
         }
     }
 }
@@ -3,7 +3,6 @@ use std::{collections::HashMap, marker::PhantomData};
 use prisma_models::PrismaValue;
 use query_core::{Operation, Selection};
 use serde::de::DeserializeOwned;
-use serde_json::Value;
 
 use crate::{
     raw::{Raw, RawOperationData, RawPrismaValue},
@@ -15,22 +14,31 @@ where
     Data: DeserializeOwned,
 {
     client: &'a PrismaClientInternals,
-    sql: String,
-    params: Vec<Value>,
+    query: Raw,
+    database: &'static str,
     _data: PhantomData<Data>,
 }
 
+impl<'a, Data: DeserializeOwned> Clone for QueryRaw<'a, Data> {
+    fn clone(&self) -> Self {
+        Self {
+            client: self.client,
+            query: self.query.clone(),
+            database: self.database,
+            _data: PhantomData,
+        }
+    }
+}
+
 impl<'a, Data> QueryRaw<'a, Data>
 where
     Data: DeserializeOwned + 'static,
 {
     pub fn new(client: &'a PrismaClientInternals, query: Raw, database: &'static str) -> Self {
-        let (sql, params) = query.convert(database);
-
         Self {
             client,
-            sql,
-            params,
+            query,
+            database,
             _data: PhantomData,
         }
     }
@@ -40,10 +48,16 @@ where
             .into_iter()
             .map(|row| {
                 row.into_iter()
-                    .map(|(column_name, cell)| (column_name, cell.into()))
-                    .collect()
+                    .map(|(column_name, cell)| {
+                        let value = RawPrismaValue::try_from(cell).map_err(|e| {
+                            QueryError::Deserialize(format!("column '{column_name}': {e}"))
+                        })?;
+
+                        Ok((column_name, value))
+                    })
+                    .collect::<super::Result<_>>()
             })
-            .collect();
+            .collect::<super::Result<_>>()?;
 
         typed_data
             .into_iter()
@@ -82,15 +96,21 @@ where
     Data: DeserializeOwned + 'static,
 {
     fn graphql(self) -> (Operation, &'a PrismaClientInternals) {
+        // Queries that can't be converted are rejected by `validate` before being executed
+        let (sql, params) = self
+            .query
+            .convert(self.database)
+            .unwrap_or_else(|_| (self.query.query, self.query.values));
+
         (
             Operation::Write(Selection::new(
                 "queryRaw".to_string(),
                 None,
                 [
-                    ("query".to_string(), PrismaValue::String(self.sql).into()),
+                    ("query".to_string(), PrismaValue::String(sql).into()),
                     (
                         "parameters".to_string(),
-                        PrismaValue::String(serde_json::to_string(&self.params).unwrap()).into(),
+                        PrismaValue::String(serde_json::to_string(&params).unwrap()).into(),
                     ),
                 ],
                 [],
@@ -98,4 +118,8 @@ where
             self.client,
         )
     }
+
+    fn validate(&self) -> super::Result<()> {
+        self.query.convert(self.database).map(|_| ())
+    }
 }
@@ -3,7 +3,7 @@ use std::marker::PhantomData;
 use query_core::{Operation, Selection};
 use serde::de::DeserializeOwned;
 
-use crate::{Data, PrismaClientInternals, Query, QueryConvert};
+use crate::{Data, ModelOperation, PrismaClientInternals, Query, QueryConvert};
 
 pub trait SelectType {
     // TODO: ModelActions
@@ -16,14 +16,31 @@ pub trait SelectType {
 pub struct Select<'a, Data: DeserializeOwned> {
     operation: Operation,
     client: &'a PrismaClientInternals,
+    model_operation: Option<(&'static str, ModelOperation)>,
     _data: PhantomData<Data>,
 }
 
+impl<'a, Data: DeserializeOwned> Clone for Select<'a, Data> {
+    fn clone(&self) -> Self {
+        Self {
+            operation: self.operation.clone(),
+            client: self.client,
+            model_operation: self.model_operation,
+            _data: PhantomData,
+        }
+    }
+}
+
 impl<'a, Data: crate::Data> Select<'a, Data> {
-    pub fn new(client: &'a PrismaClientInternals, operation: Operation) -> Self {
+    pub fn new(
+        client: &'a PrismaClientInternals,
+        operation: Operation,
+        model_operation: Option<(&'static str, ModelOperation)>,
+    ) -> Self {
         Self {
             client,
             operation,
+            model_operation,
             _data: PhantomData {},
         }
     }
@@ -46,4 +63,8 @@ impl<'a, Data: DeserializeOwned + 'static> Query<'a> for Select<'a, Data> {
     fn graphql(self) -> (Operation, &'a PrismaClientInternals) {
         (self.operation, self.client)
     }
+
+    fn model_operation(&self) -> Option<(&'static str, ModelOperation)> {
+        self.model_operation
+    }
 }
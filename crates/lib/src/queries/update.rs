@@ -7,6 +7,7 @@ use crate::{
     WhereInput, WithQuery,
 };
 
+#[derive(Clone)]
 pub struct Update<'a, Actions: ModelTypes> {
     client: &'a PrismaClientInternals,
     pub where_param: Actions::Where,
@@ -68,6 +69,7 @@ impl<'a, Actions: ModelTypes> Update<'a, Actions> {
                 self.set_params,
                 select.to_selections(),
             )),
+            Some((Actions::MODEL, Self::TYPE)),
         )
     }
 
@@ -82,6 +84,7 @@ impl<'a, Actions: ModelTypes> Update<'a, Actions> {
                 self.set_params,
                 include.to_selections(),
             )),
+            Some((Actions::MODEL, Self::TYPE)),
         )
     }
 
@@ -114,6 +117,10 @@ impl<'a, Actions: ModelTypes> Query<'a> for Update<'a, Actions> {
             self.client,
         )
     }
+
+    fn model_operation(&self) -> Option<(&'static str, ModelOperation)> {
+        Some((Actions::MODEL, Self::TYPE))
+    }
 }
 
 impl<'a, Actions: ModelTypes> ModelQuery<'a> for Update<'a, Actions> {
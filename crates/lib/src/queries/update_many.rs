@@ -6,6 +6,7 @@ use crate::{
     PrismaClientInternals, Query, QueryConvert, SetQuery, WhereInput, WhereQuery,
 };
 
+#[derive(Clone)]
 pub struct UpdateMany<'a, Actions: ModelTypes> {
     client: &'a PrismaClientInternals,
     pub where_params: Vec<Actions::Where>,
@@ -76,6 +77,10 @@ impl<'a, Actions: ModelTypes> Query<'a> for UpdateMany<'a, Actions> {
             self.client,
         )
     }
+
+    fn model_operation(&self) -> Option<(&'static str, ModelOperation)> {
+        Some((Actions::MODEL, Self::TYPE))
+    }
 }
 
 impl<'a, Actions: ModelTypes> ModelQuery<'a> for UpdateMany<'a, Actions> {
@@ -7,6 +7,7 @@ use crate::{
     UncheckedSetQuery, WhereInput, WithQuery,
 };
 
+#[derive(Clone)]
 pub struct UpdateUnchecked<'a, Actions: ModelTypes> {
     client: &'a PrismaClientInternals,
     pub where_param: Actions::Where,
@@ -68,6 +69,7 @@ impl<'a, Actions: ModelTypes> UpdateUnchecked<'a, Actions> {
                 self.set_params,
                 select.to_selections(),
             )),
+            Some((Actions::MODEL, Self::TYPE)),
         )
     }
 
@@ -82,6 +84,7 @@ impl<'a, Actions: ModelTypes> UpdateUnchecked<'a, Actions> {
                 self.set_params,
                 include.to_selections(),
             )),
+            Some((Actions::MODEL, Self::TYPE)),
         )
     }
 
@@ -114,6 +117,10 @@ impl<'a, Actions: ModelTypes> Query<'a> for UpdateUnchecked<'a, Actions> {
             self.client,
         )
     }
+
+    fn model_operation(&self) -> Option<(&'static str, ModelOperation)> {
+        Some((Actions::MODEL, Self::TYPE))
+    }
 }
 
 impl<'a, Actions: ModelTypes> ModelQuery<'a> for UpdateUnchecked<'a, Actions> {
@@ -6,6 +6,7 @@ use crate::{
     PrismaClientInternals, Query, QueryConvert, Select, SelectType, WhereInput, WithQuery,
 };
 
+#[derive(Clone)]
 pub struct Upsert<'a, Actions: ModelTypes> {
     client: &'a PrismaClientInternals,
     pub where_param: Actions::Where,
@@ -72,6 +73,7 @@ impl<'a, Actions: ModelTypes> Upsert<'a, Actions> {
                 self.update_params,
                 select.to_selections(),
             )),
+            Some((Actions::MODEL, Self::TYPE)),
         )
     }
 
@@ -87,6 +89,7 @@ impl<'a, Actions: ModelTypes> Upsert<'a, Actions> {
                 self.update_params,
                 select.to_selections(),
             )),
+            Some((Actions::MODEL, Self::TYPE)),
         )
     }
 
@@ -120,6 +123,10 @@ impl<'a, Actions: ModelTypes> Query<'a> for Upsert<'a, Actions> {
             self.client,
         )
     }
+
+    fn model_operation(&self) -> Option<(&'static str, ModelOperation)> {
+        Some((Actions::MODEL, Self::TYPE))
+    }
 }
 
 impl<'a, Actions: ModelTypes> ModelQuery<'a> for Upsert<'a, Actions> {
@@ -1,20 +1,29 @@
-use std::collections::HashMap;
+use std::{
+    collections::HashMap,
+    fmt::{self, Display},
+    str::FromStr,
+};
 
 use chrono::SecondsFormat;
 use prisma_models::PrismaValue;
 use serde::{Deserialize, Serialize, Serializer};
 use serde_json::{json, Value};
 
+use crate::QueryError;
+
+pub use prisma_client_rust_macros::raw_typed;
+
 #[macro_export]
 macro_rules! raw {
     ($e: expr) => {
-        $crate::Raw::new($e, vec![]);
+        $crate::Raw::new($e, vec![])
     };
     ($e: expr, $($params:expr),+) => {
         $crate::Raw::new($e, vec![$($params),+])
     };
 }
 
+#[derive(Clone)]
 pub struct Raw {
     pub(crate) query: String,
     pub values: Vec<Value>,
@@ -37,25 +46,30 @@ impl Raw {
         }
     }
 
-    pub fn convert(self, database: &'static str) -> (String, Vec<Value>) {
-        let Self { mut query, values } = self;
+    /// Replaces each `{}` with the parameter syntax of `database`.
+    pub fn convert(&self, database: &str) -> Result<(String, Vec<Value>), QueryError> {
+        let variable_indicator: fn(usize) -> String = match database {
+            "postgresql" | "cockroachdb" => |i| format!("${i}"),
+            "sqlite" | "mysql" => |_| "?".to_string(),
+            "sqlserver" => |i| format!("@P{i}"),
+            _ => return Err(QueryError::UnsupportedRawQuery(database.to_string())),
+        };
 
-        for i in 1..=values.len() {
-            let variable_indicator = match database {
-                "postgresql" | "cockroachdb" => format!("${i}"),
-                "sqlite" | "mysql" => "?".to_string(),
-                _ => panic!("Raw queries are not supported with database '{database}'"),
-            };
-
-            query = query.replacen("{}", &variable_indicator, 1);
+        let placeholders = self.query.matches("{}").count();
+        if placeholders != self.values.len() {
+            return Err(QueryError::RawQueryParameters {
+                placeholders,
+                parameters: self.values.len(),
+            });
         }
 
-        // TODO: do this at compile time
-        if query.contains("{}") {
-            panic!("Raw query has not been given enough parameters");
+        let mut query = self.query.clone();
+
+        for i in 1..=self.values.len() {
+            query = query.replacen("{}", &variable_indicator(i), 1);
         }
 
-        (query, values)
+        Ok((query, self.values.clone()))
     }
 }
 
@@ -69,52 +83,123 @@ pub struct RawTypedJson {
     value: serde_json::Value,
 }
 
-impl From<RawTypedJson> for RawPrismaValue {
-    fn from(json: RawTypedJson) -> Self {
-        use serde_json::Value::*;
-
-        match (json.typ.as_str(), json.value) {
-            ("int", Number(n)) => RawPrismaValue::Int(n.as_i64().unwrap() as i32),
-            ("bigint", String(s)) => RawPrismaValue::BigInt(s.parse().unwrap()),
-            ("float", Number(n)) => RawPrismaValue::Float(n.as_f64().unwrap() as f32),
-            ("double", Number(n)) => RawPrismaValue::Double(n.as_f64().unwrap()),
-            ("string", String(s)) => RawPrismaValue::String(s),
-            ("enum", String(s)) => RawPrismaValue::Enum(s),
-            ("bytes", String(b64)) => RawPrismaValue::Bytes(base64::decode(b64).unwrap()),
-            ("bool", Bool(b)) => RawPrismaValue::Bool(b),
-            ("char", String(s)) => RawPrismaValue::Char(s.chars().next().unwrap()),
-            ("decimal", Number(n)) => RawPrismaValue::Decimal(
-                bigdecimal::BigDecimal::try_from(n.as_f64().unwrap()).unwrap(),
-            ),
-            ("json", v) => RawPrismaValue::Json(v),
-            ("xml", String(s)) => RawPrismaValue::Xml(s),
-            ("uuid", String(s)) => {
-                RawPrismaValue::Uuid(uuid::Uuid::from_slice(s.as_bytes()).unwrap())
-            }
-            ("datetime", String(s)) => {
-                RawPrismaValue::DateTime(chrono::DateTime::parse_from_rfc3339(&s).unwrap().into())
-            }
-            ("date", String(s)) => {
-                RawPrismaValue::Date(serde_json::from_slice(s.as_bytes()).unwrap())
-            }
-            ("time", String(s)) => {
-                RawPrismaValue::Time(serde_json::from_slice(s.as_bytes()).unwrap())
-            }
-            ("array", Array(arr)) => RawPrismaValue::Array(
-                arr.into_iter()
-                    .map(serde_json::from_value::<RawTypedJson>)
-                    .map(Result::unwrap)
-                    .map(Into::into)
-                    .collect(),
-            ),
-            ("null", _) => RawPrismaValue::Null,
-            _ => unreachable!("Invalid value for raw type {}", &json.typ),
+impl TryFrom<RawTypedJson> for RawPrismaValue {
+    type Error = RawTypedJsonError;
+
+    fn try_from(json: RawTypedJson) -> Result<Self, Self::Error> {
+        decode(&json.typ, json.value)
+    }
+}
+
+fn decode(typ: &str, value: Value) -> Result<RawPrismaValue, RawTypedJsonError> {
+    let invalid = |value: Value, reason: Option<String>| RawTypedJsonError {
+        typ: typ.to_string(),
+        value,
+        reason,
+    };
+
+    match (typ, value) {
+        (_, Value::Null) | ("null", _) => Ok(RawPrismaValue::Null),
+        // Arrays are either a list of typed values, or a list of plain values sharing one type
+        ("array", Value::Array(values)) => values
+            .into_iter()
+            .map(|value| {
+                serde_json::from_value::<RawTypedJson>(value.clone())
+                    .map_err(|e| invalid(value, Some(e.to_string())))
+                    .and_then(RawPrismaValue::try_from)
+            })
+            .collect::<Result<_, _>>()
+            .map(RawPrismaValue::Array),
+        (typ, Value::Array(values)) if typ.ends_with("-array") => {
+            let typ = typ.trim_end_matches("-array");
+
+            values
+                .into_iter()
+                .map(|value| decode(typ, value))
+                .collect::<Result<_, _>>()
+                .map(RawPrismaValue::Array)
+        }
+        (typ, value) => decode_scalar(typ, &value).map_err(|reason| invalid(value, reason)),
+    }
+}
+
+fn decode_scalar(typ: &str, value: &Value) -> Result<RawPrismaValue, Option<String>> {
+    use serde_json::Value::*;
+
+    match (typ, value) {
+        ("int", Number(n)) => n
+            .as_i64()
+            .and_then(|n| i32::try_from(n).ok())
+            .map(RawPrismaValue::Int)
+            .ok_or_else(|| Some("out of range for i32".to_string())),
+        ("bigint", String(s)) => parse(s).map(RawPrismaValue::BigInt),
+        ("bigint", Number(n)) => n
+            .as_i64()
+            .map(RawPrismaValue::BigInt)
+            .ok_or_else(|| Some("out of range for i64".to_string())),
+        ("float", Number(n)) => Ok(RawPrismaValue::Float(n.as_f64().unwrap_or_default() as f32)),
+        ("double", Number(n)) => Ok(RawPrismaValue::Double(n.as_f64().unwrap_or_default())),
+        ("string" | "inet" | "money", String(s)) => Ok(RawPrismaValue::String(s.clone())),
+        ("enum", String(s)) => Ok(RawPrismaValue::Enum(s.clone())),
+        ("bytes", String(b64)) => base64::decode(b64)
+            .map(RawPrismaValue::Bytes)
+            .map_err(|e| Some(e.to_string())),
+        ("bool", Bool(b)) => Ok(RawPrismaValue::Bool(*b)),
+        ("char", String(s)) => s
+            .chars()
+            .next()
+            .map(RawPrismaValue::Char)
+            .ok_or_else(|| Some("empty string".to_string())),
+        // Numbers have already been read as f64, so only decimals that arrive as strings,
+        // such as SQL Server's decimal, numeric and money columns, keep their full precision
+        ("decimal", Number(n)) => parse(&n.to_string()).map(RawPrismaValue::Decimal),
+        ("decimal", String(s)) => parse(s).map(RawPrismaValue::Decimal),
+        ("json", v) => Ok(RawPrismaValue::Json(v.clone())),
+        ("xml", String(s)) => Ok(RawPrismaValue::Xml(s.clone())),
+        ("uuid", String(s)) => parse(s).map(RawPrismaValue::Uuid),
+        ("datetime", String(s)) => chrono::DateTime::parse_from_rfc3339(s)
+            .map(|dt| RawPrismaValue::DateTime(dt.into()))
+            .map_err(|e| Some(e.to_string())),
+        ("date", String(s)) => parse(s).map(RawPrismaValue::Date),
+        // SQL Server's time columns have up to 7 fractional digits
+        ("time", String(s)) => parse(s).map(RawPrismaValue::Time),
+        _ => Err(None),
+    }
+}
+
+fn parse<T>(s: &str) -> Result<T, Option<String>>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    s.parse().map_err(|e: T::Err| Some(e.to_string()))
+}
+
+/// A raw query returned a value that doesn't match its reported type.
+#[derive(Debug)]
+pub(crate) struct RawTypedJsonError {
+    typ: String,
+    value: Value,
+    reason: Option<String>,
+}
+
+impl Display for RawTypedJsonError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "invalid value {} for raw type '{}'",
+            self.value, self.typ
+        )?;
+
+        match &self.reason {
+            Some(reason) => write!(f, ": {reason}"),
+            None => Ok(()),
         }
     }
 }
 
 // See quaint::ast::Value & IntoTypedJsonExtension
-#[derive(Serialize)]
+#[derive(Debug, PartialEq, Serialize)]
 #[serde(untagged)]
 pub(crate) enum RawPrismaValue {
     Int(i32),
@@ -144,3 +229,176 @@ where
 {
     Option::<()>::None.serialize(serializer)
 }
+
+#[cfg(test)]
+mod tests {
+    use chrono::{NaiveDate, NaiveTime};
+    use serde_json::json;
+
+    use super::*;
+    use crate::QueryRaw;
+
+    fn typed_json(typ: &str, value: Value) -> RawTypedJson {
+        serde_json::from_value(json!({ "prisma__type": typ, "prisma__value": value })).unwrap()
+    }
+
+    #[test]
+    fn scalars() {
+        let cases = [
+            ("int", json!(5), RawPrismaValue::Int(5)),
+            (
+                "bigint",
+                json!("9007199254740993"),
+                RawPrismaValue::BigInt(9007199254740993),
+            ),
+            ("bigint", json!(42), RawPrismaValue::BigInt(42)),
+            ("float", json!(1.5), RawPrismaValue::Float(1.5)),
+            ("double", json!(2.25), RawPrismaValue::Double(2.25)),
+            (
+                "string",
+                json!("a"),
+                RawPrismaValue::String("a".to_string()),
+            ),
+            (
+                "enum",
+                json!("USER"),
+                RawPrismaValue::Enum("USER".to_string()),
+            ),
+            (
+                "inet",
+                json!("10.0.0.1/32"),
+                RawPrismaValue::String("10.0.0.1/32".to_string()),
+            ),
+            (
+                "money",
+                json!("$1.50"),
+                RawPrismaValue::String("$1.50".to_string()),
+            ),
+            ("bytes", json!("AQI="), RawPrismaValue::Bytes(vec![1, 2])),
+            ("bool", json!(true), RawPrismaValue::Bool(true)),
+            ("char", json!("c"), RawPrismaValue::Char('c')),
+            (
+                "json",
+                json!({ "a": [1] }),
+                RawPrismaValue::Json(json!({ "a": [1] })),
+            ),
+            (
+                "xml",
+                json!("<a/>"),
+                RawPrismaValue::Xml("<a/>".to_string()),
+            ),
+            (
+                "datetime",
+                json!("2023-01-02T03:04:05.000+00:00"),
+                RawPrismaValue::DateTime("2023-01-02T03:04:05Z".parse().unwrap()),
+            ),
+            (
+                "date",
+                json!("2023-01-02"),
+                RawPrismaValue::Date(NaiveDate::from_ymd_opt(2023, 1, 2).unwrap()),
+            ),
+            (
+                "time",
+                json!("12:34:56.1234567"),
+                RawPrismaValue::Time(
+                    NaiveTime::from_hms_nano_opt(12, 34, 56, 123_456_700).unwrap(),
+                ),
+            ),
+            ("null", Value::Null, RawPrismaValue::Null),
+            ("int", Value::Null, RawPrismaValue::Null),
+        ];
+
+        for (typ, value, expected) in cases {
+            assert_eq!(
+                RawPrismaValue::try_from(typed_json(typ, value)).unwrap(),
+                expected,
+                "{typ}"
+            );
+        }
+    }
+
+    #[test]
+    fn decimals_and_uuids() {
+        let decimal = "12345678901234567890.123456789";
+        assert_eq!(
+            RawPrismaValue::try_from(typed_json("decimal", json!(decimal))).unwrap(),
+            RawPrismaValue::Decimal(decimal.parse().unwrap())
+        );
+        assert_eq!(
+            RawPrismaValue::try_from(typed_json("decimal", json!(1.5))).unwrap(),
+            RawPrismaValue::Decimal("1.5".parse().unwrap())
+        );
+
+        let uuid = "67e55044-10b1-426f-9247-bb680e5fe0c8";
+        assert_eq!(
+            RawPrismaValue::try_from(typed_json("uuid", json!(uuid))).unwrap(),
+            RawPrismaValue::Uuid(uuid::Uuid::parse_str(uuid).unwrap())
+        );
+    }
+
+    #[test]
+    fn arrays() {
+        assert_eq!(
+            RawPrismaValue::try_from(typed_json("bigint-array", json!(["1", "2"]))).unwrap(),
+            RawPrismaValue::Array(vec![RawPrismaValue::BigInt(1), RawPrismaValue::BigInt(2)])
+        );
+        assert_eq!(
+            RawPrismaValue::try_from(typed_json(
+                "array",
+                json!([
+                    { "prisma__type": "int", "prisma__value": 1 },
+                    { "prisma__type": "null", "prisma__value": null }
+                ])
+            ))
+            .unwrap(),
+            RawPrismaValue::Array(vec![RawPrismaValue::Int(1), RawPrismaValue::Null])
+        );
+        assert_eq!(
+            RawPrismaValue::try_from(typed_json("int-array", Value::Null)).unwrap(),
+            RawPrismaValue::Null
+        );
+    }
+
+    #[test]
+    fn errors() {
+        let error = |typ: &str, value: Value| {
+            RawPrismaValue::try_from(typed_json(typ, value))
+                .unwrap_err()
+                .to_string()
+        };
+
+        assert_eq!(
+            error("int", json!("5")),
+            "invalid value \"5\" for raw type 'int'"
+        );
+        assert_eq!(
+            error("int", json!(i64::MAX)),
+            "invalid value 9223372036854775807 for raw type 'int': out of range for i32"
+        );
+        assert!(error("uuid", json!("not-a-uuid"))
+            .starts_with("invalid value \"not-a-uuid\" for raw type 'uuid': "));
+        assert!(error("bigint-array", json!(["1", "two"]))
+            .starts_with("invalid value \"two\" for raw type 'bigint': "));
+        assert_eq!(
+            error("geometry", json!("POINT(0 0)")),
+            "invalid value \"POINT(0 0)\" for raw type 'geometry'"
+        );
+    }
+
+    #[test]
+    fn column_name_in_error() {
+        let rows = vec![HashMap::from([(
+            "id".to_string(),
+            typed_json("uuid", json!("not-a-uuid")),
+        )])];
+
+        match QueryRaw::<Value>::convert(rows) {
+            Err(QueryError::Deserialize(message)) => assert!(
+                message
+                    .starts_with("column 'id': invalid value \"not-a-uuid\" for raw type 'uuid'"),
+                "{message}"
+            ),
+            result => panic!("expected a deserialize error, got {result:?}"),
+        }
+    }
+}
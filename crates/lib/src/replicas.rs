@@ -0,0 +1,142 @@
+use std::{
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
+    time::{Duration, Instant},
+};
+
+use query_core::{
+    protocol::EngineProtocol, schema::QuerySchema, CoreError, Operation, ResponseData,
+};
+use tokio::sync::RwLock;
+
+use crate::Executor;
+
+/// How long a replica that failed to respond is skipped for before being tried again.
+const UNHEALTHY_COOLDOWN: Duration = Duration::from_secs(30);
+
+struct Replica {
+    /// `None` once the client has been disconnected
+    executor: RwLock<Option<Executor>>,
+    url: String,
+    unhealthy_until: Mutex<Option<Instant>>,
+}
+
+impl Replica {
+    fn is_healthy(&self) -> bool {
+        let mut unhealthy_until = self.unhealthy_until.lock().unwrap();
+
+        match *unhealthy_until {
+            Some(until) if until > Instant::now() => false,
+            Some(_) => {
+                *unhealthy_until = None;
+                true
+            }
+            None => true,
+        }
+    }
+
+    fn mark_unhealthy(&self) {
+        *self.unhealthy_until.lock().unwrap() = Some(Instant::now() + UNHEALTHY_COOLDOWN);
+    }
+}
+
+/// Read replicas that reads made outside of transactions are load balanced across.
+pub struct ReadReplicas {
+    replicas: Vec<Replica>,
+    next: AtomicUsize,
+}
+
+impl ReadReplicas {
+    pub(crate) fn new(replicas: Vec<(Executor, String)>) -> Self {
+        Self {
+            replicas: replicas
+                .into_iter()
+                .map(|(executor, url)| Replica {
+                    executor: RwLock::new(Some(executor)),
+                    url,
+                    unhealthy_until: Mutex::new(None),
+                })
+                .collect(),
+            next: AtomicUsize::new(0),
+        }
+    }
+
+    pub fn urls(&self) -> impl Iterator<Item = &str> {
+        self.replicas.iter().map(|r| r.url.as_str())
+    }
+
+    /// Excludes replicas that can't be connected to from reads until they have had time to recover.
+    pub(crate) async fn check(&self) {
+        for replica in &self.replicas {
+            if let Some(executor) = &*replica.executor.read().await {
+                let connection = executor.primary_connector().get_connection().await;
+
+                if connection.is_err() {
+                    replica.mark_unhealthy();
+                }
+            }
+        }
+    }
+
+    pub(crate) async fn disconnect(&self) {
+        for replica in &self.replicas {
+            replica.executor.write().await.take();
+        }
+    }
+
+    /// The next healthy replica in round robin order.
+    fn pick(&self) -> Option<&Replica> {
+        let start = self.next.fetch_add(1, Ordering::Relaxed);
+
+        (0..self.replicas.len())
+            .map(|i| &self.replicas[(start + i) % self.replicas.len()])
+            .find(|replica| replica.is_healthy())
+    }
+
+    /// Executes a read on a replica, falling back to `primary` if there are no healthy replicas
+    /// or the chosen replica can't be reached.
+    pub(crate) async fn execute(
+        &self,
+        primary: &Executor,
+        query_schema: Arc<QuerySchema>,
+        op: Operation,
+    ) -> Result<ResponseData, CoreError> {
+        if let Some(replica) = self.pick() {
+            if let Some(executor) = &*replica.executor.read().await {
+                let result = executor
+                    .execute(
+                        None,
+                        op.clone(),
+                        query_schema.clone(),
+                        None,
+                        EngineProtocol::Graphql,
+                    )
+                    .await;
+
+                match result {
+                    Err(e) if is_connection_error(&e) => replica.mark_unhealthy(),
+                    result => return result,
+                }
+            }
+        }
+
+        primary
+            .execute(None, op, query_schema, None, EngineProtocol::Graphql)
+            .await
+    }
+}
+
+/// Errors with a `P1` code, such as being unable to reach or authenticate with the database,
+/// indicate a problem with the replica rather than the query.
+fn is_connection_error(error: &CoreError) -> bool {
+    match error {
+        CoreError::ConnectorError(e) => e
+            .user_facing_error
+            .as_ref()
+            .map(|e| e.error_code.starts_with("P1"))
+            .unwrap_or(false),
+        _ => false,
+    }
+}
@@ -21,3 +21,51 @@ pub mod double_option {
         }
     }
 }
+
+/// Serialization for generated params, used when logging which params produced a query.
+pub mod params {
+    use serde::{ser::SerializeMap, Serialize, Serializer};
+
+    use crate::prisma_value::PrismaValue;
+
+    /// Serializes a param as the `{ field: value }` object it is sent to Prisma as.
+    pub fn serialize_param<S: Serializer>(
+        (field, value): (String, prisma_models::PrismaValue),
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(1))?;
+        map.serialize_entry(&field, &Value(&value))?;
+        map.end()
+    }
+
+    /// Serializes a param that isn't attached to a field, such as a filter, as its value.
+    pub fn serialize_value<S: Serializer>(
+        value: prisma_models::PrismaValue,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        Value(&value).serialize(serializer)
+    }
+
+    /// Serializes objects as maps rather than lists of entries.
+    struct Value<'a>(&'a prisma_models::PrismaValue);
+
+    impl Serialize for Value<'_> {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            match self.0 {
+                prisma_models::PrismaValue::Object(entries) => {
+                    let mut map = serializer.serialize_map(Some(entries.len()))?;
+
+                    for (key, value) in entries {
+                        map.serialize_entry(key, &Value(value))?;
+                    }
+
+                    map.end()
+                }
+                prisma_models::PrismaValue::List(values) => {
+                    serializer.collect_seq(values.iter().map(Value))
+                }
+                value => PrismaValue::from(value.clone()).serialize(serializer),
+            }
+        }
+    }
+}
@@ -0,0 +1,250 @@
+//! Spans and metrics for requests sent to the query engine,
+//! enabled by the `tracing` and `metrics` features respectively.
+//! Without either feature everything in here does nothing.
+
+#![cfg_attr(
+    not(any(feature = "tracing", feature = "metrics")),
+    allow(unused_variables, dead_code)
+)]
+
+use std::future::Future;
+#[cfg(any(feature = "tracing", feature = "metrics"))]
+use std::time::Instant;
+
+use query_core::TxId;
+
+use crate::{MiddlewareRequest, MiddlewareResult, QueryError};
+
+/// Code identifying the kind of error, using Prisma's error codes where possible.
+pub(crate) fn error_code(error: &QueryError) -> String {
+    match error {
+        QueryError::Execute(error) => error
+            .as_known()
+            .map(|e| e.error_code.to_string())
+            .unwrap_or_else(|| "unknown".to_string()),
+        QueryError::Serialize(_) => "serialize".to_string(),
+        QueryError::Deserialize(_) => "deserialize".to_string(),
+        QueryError::Middleware(_) => "middleware".to_string(),
+        QueryError::Mock(_) => "mock".to_string(),
+        QueryError::NestedTransaction(_) => "nested_transaction".to_string(),
+        QueryError::UnsupportedRawQuery(_) => "unsupported_raw_query".to_string(),
+        QueryError::RawQueryParameters { .. } => "raw_query_parameters".to_string(),
+        QueryError::Disconnected => "disconnected".to_string(),
+        QueryError::NotFound { .. } => "not_found".to_string(),
+    }
+}
+
+/// Number of records in a query result.
+#[cfg(any(feature = "tracing", feature = "metrics"))]
+fn row_count(value: &serde_value::Value) -> usize {
+    use serde_value::Value;
+
+    match value {
+        Value::Seq(items) => items.len(),
+        Value::Unit | Value::Option(None) => 0,
+        Value::Option(Some(value)) | Value::Newtype(value) => row_count(value),
+        _ => 1,
+    }
+}
+
+/// Comma separated list of the distinct values produced for each query in a request.
+#[cfg(any(feature = "tracing", feature = "metrics"))]
+fn describe<'a>(values: impl Iterator<Item = &'a str>) -> String {
+    let mut seen = Vec::<&str>::new();
+
+    for value in values {
+        if !seen.contains(&value) {
+            seen.push(value);
+        }
+    }
+
+    seen.join(",")
+}
+
+pub(crate) struct QueryTelemetry {
+    #[cfg(feature = "tracing")]
+    span: tracing::Span,
+    #[cfg(any(feature = "tracing", feature = "metrics"))]
+    start: Instant,
+    #[cfg(feature = "metrics")]
+    model: String,
+    #[cfg(feature = "metrics")]
+    operation: String,
+}
+
+impl QueryTelemetry {
+    pub fn start(request: &MiddlewareRequest) -> Self {
+        #[cfg(any(feature = "tracing", feature = "metrics"))]
+        let model = describe(request.queries.iter().map(|q| q.model().unwrap_or("")));
+        #[cfg(feature = "tracing")]
+        let tx_id = request.tx_id().map(ToString::to_string);
+        #[cfg(any(feature = "tracing", feature = "metrics"))]
+        let operation = describe(request.queries.iter().map(|q| match q.action() {
+            Some(action) => action.name(),
+            None => q.name(),
+        }));
+
+        Self {
+            #[cfg(feature = "tracing")]
+            span: tracing::info_span!(
+                "prisma.query",
+                model = model.as_str(),
+                operation = operation.as_str(),
+                batch = request.batch.is_some(),
+                queries = request.queries.len(),
+                tx_id = tx_id.as_deref(),
+                duration_ms = tracing::field::Empty,
+                rows = tracing::field::Empty,
+                error_code = tracing::field::Empty,
+            ),
+            #[cfg(any(feature = "tracing", feature = "metrics"))]
+            start: Instant::now(),
+            #[cfg(feature = "metrics")]
+            model,
+            #[cfg(feature = "metrics")]
+            operation,
+        }
+    }
+
+    pub fn instrument<F: Future>(&self, future: F) -> impl Future<Output = F::Output> {
+        #[cfg(feature = "tracing")]
+        return tracing::Instrument::instrument(future, self.span.clone());
+
+        #[cfg(not(feature = "tracing"))]
+        future
+    }
+
+    pub fn finish(self, result: &MiddlewareResult) {
+        #[cfg(any(feature = "tracing", feature = "metrics"))]
+        {
+            let duration = self.start.elapsed();
+
+            let (rows, error) = match result {
+                Ok(results) => {
+                    results
+                        .iter()
+                        .fold((0, None), |(rows, error), result| match result {
+                            Ok(value) => (rows + row_count(value), error),
+                            Err(e) => (rows, error.or(Some(e))),
+                        })
+                }
+                Err(e) => (0, Some(e)),
+            };
+            let error_code = error.map(error_code);
+
+            #[cfg(feature = "tracing")]
+            {
+                self.span
+                    .record("duration_ms", &(duration.as_secs_f64() * 1000.0));
+                self.span.record("rows", &rows);
+
+                if let Some(error_code) = &error_code {
+                    self.span.record("error_code", &error_code.as_str());
+                }
+            }
+
+            #[cfg(feature = "metrics")]
+            {
+                metrics::histogram!(
+                    "prisma_query_duration_seconds",
+                    duration.as_secs_f64(),
+                    "model" => self.model.clone(),
+                    "operation" => self.operation.clone()
+                );
+
+                if let Some(error_code) = error_code {
+                    metrics::increment_counter!(
+                        "prisma_query_errors_total",
+                        "model" => self.model,
+                        "operation" => self.operation,
+                        "error_code" => error_code
+                    );
+                }
+            }
+        }
+    }
+}
+
+pub(crate) struct TransactionTelemetry {
+    #[cfg(feature = "tracing")]
+    span: tracing::Span,
+    #[cfg(any(feature = "tracing", feature = "metrics"))]
+    start: Instant,
+}
+
+impl TransactionTelemetry {
+    pub fn start() -> Self {
+        Self {
+            #[cfg(feature = "tracing")]
+            span: tracing::info_span!(
+                "prisma.transaction",
+                tx_id = tracing::field::Empty,
+                attempt = tracing::field::Empty,
+                duration_ms = tracing::field::Empty,
+                committed = tracing::field::Empty,
+                error_code = tracing::field::Empty,
+            ),
+            #[cfg(any(feature = "tracing", feature = "metrics"))]
+            start: Instant::now(),
+        }
+    }
+
+    pub fn instrument<F: Future>(&self, future: F) -> impl Future<Output = F::Output> {
+        #[cfg(feature = "tracing")]
+        return tracing::Instrument::instrument(future, self.span.clone());
+
+        #[cfg(not(feature = "tracing"))]
+        future
+    }
+
+    pub fn tx_id(&self, tx_id: &TxId) {
+        #[cfg(feature = "tracing")]
+        self.span.record("tx_id", &tx_id.to_string().as_str());
+    }
+
+    /// Records which attempt of a transaction created with `with_retries` is being made, starting at 1.
+    pub fn attempt(&self, attempt: u32) {
+        #[cfg(feature = "tracing")]
+        self.span.record("attempt", &attempt);
+
+        #[cfg(feature = "metrics")]
+        if attempt > 1 {
+            metrics::increment_counter!("prisma_transaction_retries_total");
+        }
+    }
+
+    /// Records an error from starting or committing the transaction, passing it through.
+    pub fn error(&self, error: QueryError) -> QueryError {
+        #[cfg(feature = "tracing")]
+        self.span.record("error_code", &error_code(&error).as_str());
+
+        #[cfg(feature = "metrics")]
+        metrics::increment_counter!(
+            "prisma_transaction_errors_total",
+            "error_code" => error_code(&error)
+        );
+
+        error
+    }
+
+    pub fn finish(self, committed: bool) {
+        #[cfg(any(feature = "tracing", feature = "metrics"))]
+        {
+            let duration = self.start.elapsed();
+
+            #[cfg(feature = "tracing")]
+            {
+                self.span
+                    .record("duration_ms", &(duration.as_secs_f64() * 1000.0));
+                self.span.record("committed", &committed);
+            }
+
+            #[cfg(feature = "metrics")]
+            metrics::histogram!(
+                "prisma_transaction_duration_seconds",
+                duration.as_secs_f64(),
+                "committed" => committed.to_string()
+            );
+        }
+    }
+}
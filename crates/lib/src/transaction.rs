@@ -1,8 +1,18 @@
-use std::{future::Future, marker::PhantomData};
+use std::{
+    future::Future,
+    marker::PhantomData,
+    sync::atomic::{AtomicUsize, Ordering},
+    time::Duration,
+};
 
-use query_core::{protocol::EngineProtocol, TransactionOptions, TxId};
+use prisma_models::PrismaValue;
+use query_core::{protocol::EngineProtocol, Operation, Selection, TransactionOptions, TxId};
+use user_facing_errors::query_engine::TransactionWriteConflict;
 
-use crate::{ExecutionEngine, PrismaClient, PrismaClientInternals, QueryError};
+use crate::{
+    telemetry::TransactionTelemetry, ExecutionEngine, ExecutorConnector, PrismaClient,
+    PrismaClientInternals, QueryError,
+};
 
 pub struct TransactionBuilder<'a, TClient> {
     client: &'a TClient,
@@ -38,102 +48,408 @@ impl<'a, TClient: PrismaClient> TransactionBuilder<'a, TClient> {
         }
     }
 
+    /// Re-runs the transaction according to `policy` when it fails with a retryable error,
+    /// such as a write conflict or deadlock.
+    pub fn with_retries(self, policy: RetryPolicy) -> RetryingTransactionBuilder<'a, TClient> {
+        RetryingTransactionBuilder {
+            builder: self,
+            policy,
+        }
+    }
+
     pub async fn run<TErr, TRet, TFut, TFn>(self, tx: TFn) -> Result<TRet, TErr>
     where
         TFut: Future<Output = Result<TRet, TErr>>,
         TFn: FnOnce(TClient) -> TFut,
         TErr: From<crate::QueryError>,
     {
-        match &self.internals.engine {
-            ExecutionEngine::Real { connector, .. } => {
-                let new_tx_id = connector
-                    .executor
-                    .start_tx(
-                        connector.query_schema.clone(),
-                        EngineProtocol::Graphql,
-                        TransactionOptions::new(self.max_wait, self.timeout, self.isolation_level),
-                    )
+        let telemetry = TransactionTelemetry::start();
+
+        let result = telemetry.instrument(self.run_inner(tx, &telemetry)).await;
+
+        telemetry.finish(result.is_ok());
+
+        result
+    }
+
+    async fn run_inner<TErr, TRet, TFut, TFn>(
+        &self,
+        tx: TFn,
+        telemetry: &TransactionTelemetry,
+    ) -> Result<TRet, TErr>
+    where
+        TFut: Future<Output = Result<TRet, TErr>>,
+        TFn: FnOnce(TClient) -> TFut,
+        TErr: From<crate::QueryError>,
+    {
+        let (tx_id, savepoint) = self.start().await.map_err(|e| telemetry.error(e))?;
+
+        telemetry.tx_id(&tx_id);
+
+        let engine = &self.internals.engine;
+
+        match tx(self.client.with_tx_id(Some(tx_id.clone()))).await {
+            result @ Ok(_) => {
+                commit(engine, tx_id, savepoint)
                     .await
-                    .map_err(|e| QueryError::Execute(e.into()))?;
+                    .map_err(|e| telemetry.error(e))?;
 
-                match tx(self.client.with_tx_id(Some(new_tx_id.clone()))).await {
-                    result @ Ok(_) => {
-                        connector
-                            .executor
-                            .commit_tx(new_tx_id)
-                            .await
-                            .map_err(|e| QueryError::Execute(e.into()))?;
-
-                        result
-                    }
-                    err @ Err(_) => {
-                        connector.executor.rollback_tx(new_tx_id).await.ok();
-
-                        err
-                    }
-                }
+                result
+            }
+            err @ Err(_) => {
+                rollback(engine, tx_id, savepoint).await;
+
+                err
             }
-            _ => tx(self.client.with_tx_id(None)).await,
         }
     }
 
     pub async fn begin(self) -> super::Result<(TransactionController<TClient>, TClient)> {
+        let (tx_id, savepoint) = self.start().await?;
+
+        Ok((
+            TransactionController::new(tx_id.clone(), savepoint),
+            self.client.with_tx_id(Some(tx_id)),
+        ))
+    }
+
+    /// Starts a new transaction, or creates a savepoint if the client is already inside a transaction.
+    async fn start(&self) -> super::Result<(TxId, Option<Savepoint>)> {
         Ok(match &self.internals.engine {
-            ExecutionEngine::Real { connector, .. } => {
-                let new_tx_id = connector
-                    .executor
+            ExecutionEngine::Real {
+                connector,
+                tx_id: Some(tx_id),
+                ..
+            } => {
+                let savepoint = Savepoint::new();
+
+                savepoint
+                    .execute(connector, tx_id, SavepointStatement::Create)
+                    .await?;
+
+                (tx_id.clone(), Some(savepoint))
+            }
+            ExecutionEngine::Real {
+                connector,
+                tx_id: None,
+                ..
+            } => {
+                let tx_id = connector
+                    .executor()
+                    .await?
                     .start_tx(
                         connector.query_schema.clone(),
                         EngineProtocol::Graphql,
-                        TransactionOptions::new(self.max_wait, self.timeout, self.isolation_level),
+                        TransactionOptions::new(
+                            self.max_wait,
+                            self.timeout,
+                            self.isolation_level.clone(),
+                        ),
                     )
                     .await
                     .map_err(|e| QueryError::Execute(e.into()))?;
 
-                (
-                    TransactionController::new(new_tx_id.clone()),
-                    self.client.with_tx_id(Some(new_tx_id)),
-                )
+                (tx_id, None)
             }
-            _ => (
-                TransactionController::new("".to_string().into()),
-                self.client.with_tx_id(None),
+            #[cfg(feature = "mocking")]
+            ExecutionEngine::Mock { store, tx_id } => {
+                (tx_id.clone().unwrap_or_else(|| store.new_tx_id()), None)
+            }
+            #[cfg(feature = "in-memory")]
+            ExecutionEngine::InMemory {
+                store,
+                tx_id: Some(tx_id),
+            } => {
+                store.savepoint(tx_id)?;
+
+                (tx_id.clone(), Some(Savepoint::new()))
+            }
+            #[cfg(feature = "in-memory")]
+            ExecutionEngine::InMemory { store, tx_id: None } => (store.begin(), None),
+        })
+    }
+}
+
+/// A [`TransactionBuilder`] that re-runs its transaction when it fails with a retryable error,
+/// created with [`TransactionBuilder::with_retries`].
+pub struct RetryingTransactionBuilder<'a, TClient> {
+    builder: TransactionBuilder<'a, TClient>,
+    policy: RetryPolicy,
+}
+
+impl<'a, TClient: PrismaClient> RetryingTransactionBuilder<'a, TClient> {
+    /// Runs the transaction, calling `tx` again for each retry.
+    /// Returns the transaction's result along with the number of attempts it took, including the first.
+    ///
+    /// Transactions nested inside another transaction aren't retried,
+    /// since a conflict will have aborted the outer transaction too.
+    pub async fn run<TErr, TRet, TFut, TFn>(self, mut tx: TFn) -> Result<(TRet, u32), TErr>
+    where
+        TFut: Future<Output = Result<TRet, TErr>>,
+        TFn: FnMut(TClient) -> TFut,
+        TErr: From<crate::QueryError> + AsQueryError,
+    {
+        let nested = self.builder.internals.engine.tx_id().is_some();
+
+        let mut attempt = 1;
+
+        loop {
+            let telemetry = TransactionTelemetry::start();
+
+            telemetry.attempt(attempt);
+
+            let result = telemetry
+                .instrument(self.builder.run_inner(&mut tx, &telemetry))
+                .await;
+
+            telemetry.finish(result.is_ok());
+
+            match result {
+                Err(e) if !nested && self.policy.should_retry(attempt, e.as_query_error()) => {
+                    tokio::time::sleep(self.policy.backoff(attempt)).await;
+
+                    attempt += 1;
+                }
+                result => return result.map(|ret| (ret, attempt)),
+            }
+        }
+    }
+}
+
+/// Configures how many times and how often a transaction is retried.
+///
+/// By default transactions are attempted up to 3 times,
+/// waiting 50ms before the first retry and doubling the wait for each retry after it, up to 1s.
+/// Only write conflicts and deadlocks (P2034) are retried unless configured otherwise.
+#[derive(Clone, Copy, Debug)]
+pub struct RetryPolicy {
+    max_attempts: u32,
+    initial_backoff: Duration,
+    max_backoff: Duration,
+    retryable: fn(&QueryError) -> bool,
+}
+
+impl RetryPolicy {
+    /// A policy that runs a transaction at most `max_attempts` times, including the first attempt.
+    pub fn new(max_attempts: u32) -> Self {
+        Self {
+            max_attempts,
+            ..Default::default()
+        }
+    }
+
+    pub fn with_backoff(self, initial_backoff: Duration, max_backoff: Duration) -> Self {
+        Self {
+            initial_backoff,
+            max_backoff,
+            ..self
+        }
+    }
+
+    /// Replaces the check used to decide whether an error is retryable.
+    pub fn with_retryable(self, retryable: fn(&QueryError) -> bool) -> Self {
+        Self { retryable, ..self }
+    }
+
+    fn should_retry(&self, attempt: u32, error: Option<&QueryError>) -> bool {
+        attempt < self.max_attempts && error.map(self.retryable).unwrap_or(false)
+    }
+
+    /// Time to wait after the given attempt before starting the next one.
+    fn backoff(&self, attempt: u32) -> Duration {
+        self.initial_backoff
+            .saturating_mul(2u32.saturating_pow(attempt - 1))
+            .min(self.max_backoff)
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            initial_backoff: Duration::from_millis(50),
+            max_backoff: Duration::from_secs(1),
+            retryable: |error| error.is_prisma_error::<TransactionWriteConflict>(),
+        }
+    }
+}
+
+/// Errors returned from transactions that may have been caused by a [`QueryError`],
+/// allowing [`RetryPolicy`] to check whether they are retryable.
+pub trait AsQueryError {
+    fn as_query_error(&self) -> Option<&QueryError>;
+}
+
+impl AsQueryError for QueryError {
+    fn as_query_error(&self) -> Option<&QueryError> {
+        Some(self)
+    }
+}
+
+async fn commit(
+    engine: &ExecutionEngine,
+    tx_id: TxId,
+    savepoint: Option<Savepoint>,
+) -> super::Result<()> {
+    match engine {
+        ExecutionEngine::Real { connector, .. } => match savepoint {
+            Some(savepoint) => {
+                savepoint
+                    .execute(connector, &tx_id, SavepointStatement::Release)
+                    .await
+            }
+            None => connector
+                .executor()
+                .await?
+                .commit_tx(tx_id)
+                .await
+                .map_err(|e| QueryError::Execute(e.into())),
+        },
+        #[cfg(feature = "mocking")]
+        ExecutionEngine::Mock { .. } => Ok(()),
+        #[cfg(feature = "in-memory")]
+        ExecutionEngine::InMemory { store, .. } => match savepoint {
+            Some(_) => store.release_savepoint(&tx_id),
+            None => store.commit(&tx_id),
+        },
+    }
+}
+
+/// Rolls back a transaction or savepoint, ignoring errors since the transaction has already failed.
+async fn rollback(engine: &ExecutionEngine, tx_id: TxId, savepoint: Option<Savepoint>) {
+    match engine {
+        ExecutionEngine::Real { connector, .. } => match savepoint {
+            Some(savepoint) => {
+                savepoint
+                    .execute(connector, &tx_id, SavepointStatement::RollbackTo)
+                    .await
+                    .ok();
+            }
+            None => {
+                if let Ok(executor) = connector.executor().await {
+                    executor.rollback_tx(tx_id).await.ok();
+                }
+            }
+        },
+        #[cfg(feature = "mocking")]
+        ExecutionEngine::Mock { .. } => {}
+        #[cfg(feature = "in-memory")]
+        ExecutionEngine::InMemory { store, .. } => match savepoint {
+            Some(_) => {
+                store.rollback_to_savepoint(&tx_id).ok();
+            }
+            None => store.rollback(&tx_id),
+        },
+    }
+}
+
+static SAVEPOINT_COUNT: AtomicUsize = AtomicUsize::new(0);
+
+/// A savepoint inside an existing transaction, used to run nested transactions.
+struct Savepoint {
+    name: String,
+}
+
+enum SavepointStatement {
+    Create,
+    Release,
+    RollbackTo,
+}
+
+impl Savepoint {
+    fn new() -> Self {
+        Self {
+            name: format!(
+                "prisma_client_rust_{}",
+                SAVEPOINT_COUNT.fetch_add(1, Ordering::Relaxed)
             ),
+        }
+    }
+
+    fn sql(&self, provider: &str, statement: SavepointStatement) -> super::Result<Option<String>> {
+        let name = &self.name;
+
+        Ok(match (provider, statement) {
+            ("sqlserver", SavepointStatement::Create) => Some(format!("SAVE TRANSACTION {name}")),
+            // SQL Server doesn't release savepoints, they only go away when the transaction ends
+            ("sqlserver", SavepointStatement::Release) => None,
+            ("sqlserver", SavepointStatement::RollbackTo) => {
+                Some(format!("ROLLBACK TRANSACTION {name}"))
+            }
+            ("postgresql" | "cockroachdb" | "mysql" | "sqlite", statement) => {
+                Some(match statement {
+                    SavepointStatement::Create => format!("SAVEPOINT {name}"),
+                    SavepointStatement::Release => format!("RELEASE SAVEPOINT {name}"),
+                    SavepointStatement::RollbackTo => format!("ROLLBACK TO SAVEPOINT {name}"),
+                })
+            }
+            (provider, _) => return Err(QueryError::NestedTransaction(provider.to_string())),
         })
     }
+
+    async fn execute(
+        &self,
+        connector: &ExecutorConnector,
+        tx_id: &TxId,
+        statement: SavepointStatement,
+    ) -> super::Result<()> {
+        let sql = match self.sql(connector.provider, statement)? {
+            Some(sql) => sql,
+            None => return Ok(()),
+        };
+
+        // Executed directly rather than through the client so that middleware doesn't see it
+        connector
+            .executor()
+            .await?
+            .execute(
+                Some(tx_id.clone()),
+                Operation::Write(Selection::new(
+                    "executeRaw".to_string(),
+                    None,
+                    [
+                        ("query".to_string(), PrismaValue::String(sql).into()),
+                        (
+                            "parameters".to_string(),
+                            PrismaValue::String("[]".to_string()).into(),
+                        ),
+                    ],
+                    [],
+                )),
+                connector.query_schema.clone(),
+                None,
+                EngineProtocol::Graphql,
+            )
+            .await
+            .map_err(|e| QueryError::Execute(e.into()))?;
+
+        Ok(())
+    }
 }
 
 pub struct TransactionController<TClient> {
     tx_id: TxId,
+    savepoint: Option<Savepoint>,
     _client: PhantomData<TClient>,
 }
 
 impl<TClient: PrismaClient> TransactionController<TClient> {
-    fn new(tx_id: TxId) -> Self {
+    fn new(tx_id: TxId, savepoint: Option<Savepoint>) -> Self {
         Self {
             tx_id,
+            savepoint,
             _client: Default::default(),
         }
     }
 
     pub async fn commit(self, client: TClient) -> super::Result<()> {
-        Ok(match &client.internals().engine {
-            ExecutionEngine::Real { connector, .. } => connector
-                .executor
-                .commit_tx(self.tx_id)
-                .await
-                .map_err(|e| QueryError::Execute(e.into()))?,
-            _ => {}
-        })
+        commit(&client.internals().engine, self.tx_id, self.savepoint).await
     }
 
     pub async fn rollback(self, client: TClient) -> super::Result<()> {
-        Ok(match &client.internals().engine {
-            ExecutionEngine::Real { connector, .. } => {
-                connector.executor.rollback_tx(self.tx_id).await.ok();
-            }
-            _ => {}
-        })
+        rollback(&client.internals().engine, self.tx_id, self.savepoint).await;
+
+        Ok(())
     }
 }
 
@@ -1,4 +1,5 @@
 mod partial_unchecked;
+mod raw_typed;
 
 #[proc_macro]
 pub fn to_pascal_case(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
@@ -10,3 +11,13 @@ pub fn to_pascal_case(input: proc_macro::TokenStream) -> proc_macro::TokenStream
 pub fn partial_unchecked(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     partial_unchecked::proc_macro(input)
 }
+
+/// Creates a raw query like `raw!`, checking at compile time that the number of `{}`
+/// placeholders in the query matches the number of parameters.
+///
+/// Can also declare a struct to deserialize rows into,
+/// with fields marked `#[flatten]` being filled from the row's columns.
+#[proc_macro]
+pub fn raw_typed(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    raw_typed::proc_macro(input)
+}
@@ -0,0 +1,100 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{
+    parse::{Parse, ParseStream},
+    parse_macro_input, parse_quote,
+    punctuated::Punctuated,
+    Expr, Fields, ItemStruct, LitStr, Token,
+};
+
+enum RawTypedInput {
+    /// `"SELECT * FROM Post WHERE id = {}", PrismaValue::String(id)`
+    Query {
+        sql: LitStr,
+        params: Punctuated<Expr, Token![,]>,
+    },
+    /// `struct PostWithAuthor { #[flatten] post: post::Data, author_name: String }`
+    Row(ItemStruct),
+}
+
+impl Parse for RawTypedInput {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        if input.peek(LitStr) {
+            let sql = input.parse()?;
+
+            let params = if input.is_empty() {
+                Punctuated::new()
+            } else {
+                input.parse::<Token![,]>()?;
+                Punctuated::parse_terminated(input)?
+            };
+
+            Ok(Self::Query { sql, params })
+        } else if input.peek(Token![struct]) || input.peek(Token![pub]) || input.peek(Token![#]) {
+            Ok(Self::Row(input.parse()?))
+        } else {
+            Err(input.error(
+                "expected a string literal query or a row struct. \
+                Use raw! for queries that aren't string literals",
+            ))
+        }
+    }
+}
+
+pub fn proc_macro(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    match parse_macro_input!(input as RawTypedInput) {
+        RawTypedInput::Query { sql, params } => query(sql, params),
+        RawTypedInput::Row(item) => row(item),
+    }
+    .unwrap_or_else(syn::Error::into_compile_error)
+    .into()
+}
+
+fn query(sql: LitStr, params: Punctuated<Expr, Token![,]>) -> syn::Result<TokenStream> {
+    let placeholders = sql.value().matches("{}").count();
+
+    if placeholders != params.len() {
+        return Err(syn::Error::new(
+            sql.span(),
+            format!(
+                "raw query has {placeholders} placeholder(s) but {} argument(s) were provided",
+                params.len()
+            ),
+        ));
+    }
+
+    let params = params.iter();
+
+    Ok(quote!(::prisma_client_rust::Raw::new(#sql, vec![#(#params),*])))
+}
+
+/// Fields marked with `#[flatten]`, such as `post: post::Data` or `author: Option<user::Data>`,
+/// are filled from the row's columns so that models can be selected alongside other values.
+///
+/// Columns are matched by name only, so a column can only be read by one flattened field.
+fn row(mut item: ItemStruct) -> syn::Result<TokenStream> {
+    let fields = match &mut item.fields {
+        Fields::Named(fields) => fields,
+        fields => {
+            return Err(syn::Error::new_spanned(
+                fields,
+                "row structs must have named fields",
+            ))
+        }
+    };
+
+    for field in fields.named.iter_mut() {
+        let attrs_len = field.attrs.len();
+
+        field.attrs.retain(|attr| !attr.path.is_ident("flatten"));
+
+        if field.attrs.len() != attrs_len {
+            field.attrs.push(parse_quote!(#[serde(flatten)]));
+        }
+    }
+
+    Ok(quote! {
+        #[derive(::serde::Deserialize)]
+        #item
+    })
+}
@@ -0,0 +1,120 @@
+use crate::db::*;
+use crate::utils::*;
+
+async fn create_posts(client: &PrismaClient) -> TestResult {
+    client
+        .post()
+        .create_many(vec![
+            post::create_unchecked("A".to_string(), true, vec![post::views::set(1)]),
+            post::create_unchecked("B".to_string(), true, vec![post::views::set(2)]),
+            post::create_unchecked("C".to_string(), false, vec![post::views::set(6)]),
+        ])
+        .exec()
+        .await?;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn basic() -> TestResult {
+    let client = client().await;
+
+    create_posts(&client).await?;
+
+    let data = client
+        .post()
+        .aggregate(
+            vec![],
+            vec![
+                post::count_all(),
+                post::views::avg(),
+                post::views::sum(),
+                post::views::min(),
+                post::title::max(),
+            ],
+        )
+        .exec()
+        .await?;
+
+    assert_eq!(data._count.unwrap()._all, Some(3));
+    assert_eq!(data._avg.unwrap().views, Some(3.0));
+    assert_eq!(data._sum.unwrap().views, Some(9));
+    assert_eq!(data._min.unwrap().views, Some(1));
+    assert_eq!(data._max.unwrap().title, Some("C".to_string()));
+
+    cleanup(client).await
+}
+
+#[tokio::test]
+async fn unselected() -> TestResult {
+    let client = client().await;
+
+    create_posts(&client).await?;
+
+    let data = client
+        .post()
+        .aggregate(vec![], vec![post::views::count()])
+        .exec()
+        .await?;
+
+    let count = data._count.unwrap();
+    assert_eq!(count.views, Some(3));
+    assert_eq!(count._all, None);
+    assert!(data._sum.is_none());
+
+    cleanup(client).await
+}
+
+#[tokio::test]
+async fn where_() -> TestResult {
+    let client = client().await;
+
+    create_posts(&client).await?;
+
+    let data = client
+        .post()
+        .aggregate(
+            vec![post::published::equals(true)],
+            vec![post::views::sum()],
+        )
+        .exec()
+        .await?;
+
+    assert_eq!(data._sum.unwrap().views, Some(3));
+
+    cleanup(client).await
+}
+
+#[tokio::test]
+async fn no_results() -> TestResult {
+    let client = client().await;
+
+    let data = client
+        .post()
+        .aggregate(vec![], vec![post::views::avg()])
+        .exec()
+        .await?;
+
+    assert_eq!(data._avg.unwrap().views, None);
+
+    cleanup(client).await
+}
+
+#[tokio::test]
+async fn take() -> TestResult {
+    let client = client().await;
+
+    create_posts(&client).await?;
+
+    let data = client
+        .post()
+        .aggregate(vec![], vec![post::count_all()])
+        .order_by(post::views::order(SortOrder::Asc))
+        .take(2)
+        .exec()
+        .await?;
+
+    assert_eq!(data._count.unwrap()._all, Some(2));
+
+    cleanup(client).await
+}
@@ -1,5 +1,5 @@
 #![allow(unused_must_use)]
-use prisma_client_rust::prisma_errors::query_engine::UniqueKeyViolation;
+use prisma_client_rust::{prisma_errors::query_engine::UniqueKeyViolation, BatchMode};
 
 use crate::db::*;
 use crate::utils::*;
@@ -373,3 +373,116 @@ async fn allows_empty() -> TestResult {
 
     cleanup(client).await
 }
+
+#[tokio::test]
+async fn independent() -> TestResult {
+    let client = client().await;
+
+    let error = client
+        ._batch_with_mode(
+            (
+                client.user().create(
+                    "Brendan".to_string(),
+                    vec![user::id::set("abc".to_string())],
+                ),
+                client.user().create(
+                    "Brendan 2".to_string(),
+                    vec![user::id::set("abc".to_string())],
+                ),
+            ),
+            BatchMode::Independent,
+        )
+        .await
+        .unwrap_err();
+
+    assert!(error.is_prisma_error::<UniqueKeyViolation>());
+    assert_eq!(client.user().count(vec![]).exec().await?, 1);
+
+    cleanup(client).await
+}
+
+#[tokio::test]
+async fn transactional_rolls_back() -> TestResult {
+    let client = client().await;
+
+    client
+        ._batch_with_mode(
+            (
+                client.user().create(
+                    "Brendan".to_string(),
+                    vec![user::id::set("abc".to_string())],
+                ),
+                client.user().create(
+                    "Brendan 2".to_string(),
+                    vec![user::id::set("abc".to_string())],
+                ),
+            ),
+            BatchMode::Transactional,
+        )
+        .await
+        .unwrap_err();
+
+    assert_eq!(client.user().count(vec![]).exec().await?, 0);
+
+    cleanup(client).await
+}
+
+#[tokio::test]
+async fn settled() -> TestResult {
+    let client = client().await;
+
+    let (first, second, count) = client
+        ._batch_settled(
+            (
+                client.user().create(
+                    "Brendan".to_string(),
+                    vec![user::id::set("abc".to_string())],
+                ),
+                client.user().create(
+                    "Brendan 2".to_string(),
+                    vec![user::id::set("abc".to_string())],
+                ),
+                client.user().count(vec![]),
+            ),
+            BatchMode::Independent,
+        )
+        .await?;
+
+    assert_eq!(first?.name, "Brendan");
+    assert!(second.unwrap_err().is_prisma_error::<UniqueKeyViolation>());
+    assert_eq!(count?, 1);
+
+    cleanup(client).await
+}
+
+#[tokio::test]
+async fn settled_vec() -> TestResult {
+    let client = client().await;
+
+    let results = client
+        ._batch_settled(
+            vec![
+                vec![client.user().create(
+                    "Brendan".to_string(),
+                    vec![user::id::set("abc".to_string())],
+                )],
+                vec![
+                    client.user().create(
+                        "Brendan 2".to_string(),
+                        vec![user::id::set("abc".to_string())],
+                    ),
+                    client.user().create("Oscar".to_string(), vec![]),
+                ],
+            ],
+            BatchMode::Independent,
+        )
+        .await?;
+
+    assert_eq!(results.len(), 2);
+    assert!(results[0][0].is_ok());
+    assert!(results[1][0].is_err());
+    assert!(results[1][1].is_ok());
+    assert_eq!(client.user().count(vec![]).exec().await?, 2);
+
+    cleanup(client).await
+}
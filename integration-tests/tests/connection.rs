@@ -0,0 +1,80 @@
+use std::time::Duration;
+
+use prisma_client_rust::{ConnectionOptions, NewClientError, QueryError};
+
+use crate::db::*;
+use crate::utils::*;
+
+#[tokio::test]
+async fn options_applied() -> TestResult {
+    let client = PrismaClient::_builder()
+        .connection_limit(2)
+        .socket_timeout(Duration::from_secs(5))
+        .build()
+        .await
+        .unwrap();
+
+    assert_eq!(
+        client._connection_options(),
+        ConnectionOptions {
+            connection_limit: Some(2),
+            socket_timeout: Some(Duration::from_secs(5)),
+            ..Default::default()
+        }
+    );
+    client.user().count(vec![]).exec().await?;
+
+    cleanup(client).await
+}
+
+#[tokio::test]
+async fn unsupported_option() -> TestResult {
+    let result = PrismaClient::_builder()
+        .connect_timeout(Duration::from_secs(5))
+        .build()
+        .await;
+
+    assert!(matches!(result, Err(NewClientError::ConnectionOption(_))));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn fractional_timeout() -> TestResult {
+    let result = PrismaClient::_builder()
+        .socket_timeout(Duration::from_millis(1500))
+        .build()
+        .await;
+
+    assert!(matches!(result, Err(NewClientError::ConnectionOption(_))));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn health_check() -> TestResult {
+    let client = client().await;
+
+    let health = client._health_check().await?;
+
+    assert!(health.latency < Duration::from_secs(5));
+
+    cleanup(client).await
+}
+
+#[tokio::test]
+async fn disconnect() -> TestResult {
+    let client = client().await;
+
+    client._disconnect().await;
+
+    let result = client.user().count(vec![]).exec().await;
+
+    assert!(matches!(result, Err(QueryError::Disconnected)));
+    assert!(matches!(
+        client._health_check().await,
+        Err(QueryError::Disconnected)
+    ));
+
+    Ok(())
+}
@@ -1,3 +1,5 @@
+use prisma_client_rust::PrismaErrorKind;
+
 use crate::db::*;
 use crate::utils::*;
 
@@ -65,6 +67,37 @@ async fn unique_violation() -> TestResult {
     cleanup(client).await
 }
 
+#[tokio::test]
+async fn unique_violation_kind() -> TestResult {
+    let client = client().await;
+
+    client
+        .user()
+        .create(
+            "Brendan".to_string(),
+            vec![user::id::set("user-1".to_string())],
+        )
+        .exec()
+        .await?;
+
+    let error = client
+        .user()
+        .create(
+            "Brendan".to_string(),
+            vec![user::id::set("user-1".to_string())],
+        )
+        .exec()
+        .await
+        .unwrap_err();
+
+    assert!(matches!(
+        error.kind(),
+        PrismaErrorKind::UniqueConstraintViolation { fields, .. } if fields == vec!["id"]
+    ));
+
+    cleanup(client).await
+}
+
 #[tokio::test]
 async fn set_none() -> TestResult {
     let client = client().await;
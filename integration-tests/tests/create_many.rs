@@ -51,3 +51,66 @@ async fn skip_duplicates() -> TestResult {
 
     cleanup(client).await
 }
+
+#[tokio::test]
+async fn returning() -> TestResult {
+    let client = client().await;
+
+    let data = (0..10)
+        .map(|i| post::create_unchecked(format!("Post {i}"), i % 2 == 0, vec![]))
+        .collect();
+
+    let posts = client.post().create_many(data).returning().exec().await?;
+
+    assert_eq!(posts.len(), 10);
+    assert_eq!(posts[3].title, "Post 3");
+    assert!(!posts[3].published);
+    assert_eq!(posts[3].views, 0);
+
+    assert_eq!(client.post().count(vec![]).exec().await?, 10);
+
+    cleanup(client).await
+}
+
+#[tokio::test]
+async fn returning_select() -> TestResult {
+    let client = client().await;
+
+    let data = vec![
+        post::create_unchecked("Post 1".to_string(), true, vec![]),
+        post::create_unchecked("Post 2".to_string(), false, vec![]),
+    ];
+
+    let posts = client
+        .post()
+        .create_many(data)
+        .select(post::select!({ id title }))
+        .exec()
+        .await?;
+
+    assert_eq!(posts.len(), 2);
+    assert_eq!(posts[1].title, "Post 2");
+
+    cleanup(client).await
+}
+
+#[tokio::test]
+async fn returning_rolls_back() -> TestResult {
+    let client = client().await;
+
+    let data = vec![
+        post::create_unchecked(
+            "Post".to_string(),
+            true,
+            vec![post::id::set("0".to_string())],
+        );
+        2
+    ];
+
+    let result = client.post().create_many(data).returning().exec().await;
+
+    assert!(result.is_err());
+    assert_eq!(client.post().count(vec![]).exec().await?, 0);
+
+    cleanup(client).await
+}
@@ -0,0 +1,107 @@
+use prisma_client_rust::{Query, QueryDocument};
+use serde_json::json;
+
+use crate::{db::*, utils::*};
+
+#[tokio::test]
+async fn debug_params() -> TestResult {
+    assert_eq!(
+        format!("{:?}", post::title::contains("rust".to_string())),
+        r#"Title(Contains("rust"))"#
+    );
+
+    let set: post::SetParam = post::views::set(5);
+    assert_eq!(format!("{set:?}"), "Views(Set(5))");
+
+    let order: post::OrderByWithRelationParam = post::views::order(SortOrder::Desc);
+    assert_eq!(format!("{order:?}"), "Views(Desc)");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn serialize_params() -> TestResult {
+    assert_eq!(
+        serde_json::to_value(post::title::contains("rust".to_string())).unwrap(),
+        json!({ "title": { "contains": "rust" } })
+    );
+
+    assert_eq!(
+        serde_json::to_value(vec![
+            post::views::gt(5),
+            post::desc::equals(None),
+            post::author::is(vec![user::name::equals("Brendan".to_string())]),
+        ])
+        .unwrap(),
+        json!([
+            { "views": { "gt": 5 } },
+            { "desc": { "equals": null } },
+            { "author": { "is": { "name": "Brendan" } } }
+        ])
+    );
+
+    let unique: post::UniqueWhereParam = post::id::equals("1".to_string());
+    assert_eq!(
+        serde_json::to_value(unique).unwrap(),
+        json!({ "id": { "equals": "1" } })
+    );
+
+    let set: post::SetParam = post::views::increment(1);
+    assert_eq!(
+        serde_json::to_value(set).unwrap(),
+        json!({ "views": { "increment": 1 } })
+    );
+
+    let order: post::OrderByWithRelationParam = post::views::order(SortOrder::Desc);
+    assert_eq!(
+        serde_json::to_value(order).unwrap(),
+        json!({ "views": "desc" })
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn query_string() -> TestResult {
+    let (client, _mock) = PrismaClient::_mock();
+
+    let query = client
+        .post()
+        .find_many(vec![post::title::contains("rust".to_string())])
+        .take(5);
+    let rendered = query.to_query_string()?;
+
+    assert!(rendered.starts_with(
+        "query {\n  findManyPost(where: { title: { contains: \"rust\" } }, take: 5) {\n    id\n"
+    ));
+    assert!(rendered.ends_with("\n  }\n}"));
+
+    // The query is still usable after being rendered
+    assert_eq!(QueryDocument(&query.graphql().0).to_string(), rendered);
+
+    let query = client
+        .post()
+        .update(
+            post::id::equals("1".to_string()),
+            vec![post::views::increment(1)],
+        )
+        .select(post::select!({ id }))
+        .to_query_string()?;
+
+    assert_eq!(
+        query,
+        "mutation {\n  updateOnePost(where: { id: \"1\" }, data: { views: { increment: 1 } }) {\n    id\n  }\n}"
+    );
+
+    let (operation, _) = client
+        .post()
+        .delete_many(vec![post::published::equals(false)])
+        .graphql();
+
+    assert_eq!(
+        QueryDocument(&operation).to_string(),
+        "mutation {\n  deleteManyPost(where: { published: { equals: false } }) {\n    count\n  }\n}"
+    );
+
+    Ok(())
+}
@@ -0,0 +1,181 @@
+use prisma_client_rust::filters::{FilterError, FindManyArgs, Params};
+use serde_json::json;
+
+use crate::{db::*, utils::*};
+
+async fn create_posts(client: &PrismaClient) -> TestResult {
+    let user = client
+        .user()
+        .create("Brendan".to_string(), vec![])
+        .exec()
+        .await?;
+
+    client
+        .post()
+        .create(
+            "Post 1".to_string(),
+            true,
+            vec![
+                post::views::set(10),
+                post::author::connect(user::id::equals(user.id.clone())),
+            ],
+        )
+        .exec()
+        .await?;
+
+    client
+        .post()
+        .create("Post 2".to_string(), false, vec![post::views::set(2)])
+        .exec()
+        .await?;
+
+    client
+        .post()
+        .create("Post 3".to_string(), true, vec![post::views::set(5)])
+        .exec()
+        .await?;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn scalar_filters() -> TestResult {
+    let client = client().await;
+
+    create_posts(&client).await?;
+
+    let Params(filters): Params<post::WhereParam> = serde_json::from_value(json!({
+        "title": { "startsWith": "Post" },
+        "views": { "gt": 3, "lte": 10 },
+        "published": true
+    }))
+    .unwrap();
+
+    let posts = client
+        .post()
+        .find_many(filters)
+        .order_by(post::views::order(SortOrder::Asc))
+        .exec()
+        .await?;
+
+    assert_eq!(
+        posts.iter().map(|p| p.title.as_str()).collect::<Vec<_>>(),
+        vec!["Post 3", "Post 1"]
+    );
+
+    cleanup(client).await
+}
+
+#[tokio::test]
+async fn operators() -> TestResult {
+    let client = client().await;
+
+    create_posts(&client).await?;
+
+    let Params(filters): Params<post::WhereParam> = serde_json::from_value(json!({
+        "OR": [{ "title": "Post 1" }, { "views": { "lt": 5 } }],
+        "NOT": { "published": true }
+    }))
+    .unwrap();
+
+    let posts = client.post().find_many(filters).exec().await?;
+
+    assert_eq!(posts.len(), 1);
+    assert_eq!(posts[0].title, "Post 2");
+
+    cleanup(client).await
+}
+
+#[tokio::test]
+async fn relation_filters() -> TestResult {
+    let client = client().await;
+
+    create_posts(&client).await?;
+
+    let Params(filters): Params<user::WhereParam> = serde_json::from_value(json!({
+        "posts": { "some": { "views": { "gte": 10 } } }
+    }))
+    .unwrap();
+
+    let users = client.user().find_many(filters).exec().await?;
+
+    assert_eq!(users.len(), 1);
+
+    let Params(filters): Params<post::WhereParam> =
+        serde_json::from_value(json!({ "author": null })).unwrap();
+
+    let posts = client.post().find_many(filters).exec().await?;
+
+    assert_eq!(posts.len(), 2);
+
+    cleanup(client).await
+}
+
+#[tokio::test]
+async fn query_string_values() -> TestResult {
+    let client = client().await;
+
+    create_posts(&client).await?;
+
+    // Values from a query string are all strings
+    let args: FindManyArgs<post::WhereParam, post::OrderByWithRelationParam> =
+        serde_json::from_value(json!({
+            "where": { "views": { "gt": "3" }, "published": "true" },
+            "orderBy": { "views": "desc" },
+            "take": "1"
+        }))
+        .unwrap();
+
+    let posts = client
+        .post()
+        .find_many(vec![])
+        .with_args(args)
+        .exec()
+        .await?;
+
+    assert_eq!(posts.len(), 1);
+    assert_eq!(posts[0].title, "Post 1");
+
+    cleanup(client).await
+}
+
+#[tokio::test]
+async fn invalid_filters() -> TestResult {
+    let error = prisma_client_rust::filters::from_json::<post::WhereParam>(json!({
+        "password": { "equals": "hunter2" }
+    }))
+    .err()
+    .unwrap();
+
+    assert_eq!(
+        error,
+        FilterError::UnknownField {
+            model: "Post",
+            field: "password".to_string()
+        }
+    );
+
+    let error = prisma_client_rust::filters::from_json::<post::WhereParam>(json!({
+        "views": { "gt": "lots" }
+    }))
+    .err()
+    .unwrap();
+
+    assert!(matches!(error, FilterError::InvalidValue { field, .. } if field == "views"));
+
+    let error = prisma_client_rust::filters::from_json::<user::WhereParam>(json!({
+        "posts": { "any": {} }
+    }))
+    .err()
+    .unwrap();
+
+    assert!(matches!(error, FilterError::InvalidValue { field, .. } if field == "posts"));
+
+    let result = serde_json::from_value::<
+        FindManyArgs<post::WhereParam, post::OrderByWithRelationParam>,
+    >(json!({ "cursor": { "id": "1" } }));
+
+    assert!(result.is_err());
+
+    Ok(())
+}
@@ -1,4 +1,4 @@
-use prisma_client_rust::{and, not, or};
+use prisma_client_rust::{and, not, or, QueryError};
 
 use crate::{db::*, utils::*};
 
@@ -356,3 +356,39 @@ async fn list_wrapper_query_transformation() -> TestResult {
 
     cleanup(client).await
 }
+
+#[tokio::test]
+async fn or_throw() -> TestResult {
+    let client = client().await;
+
+    client
+        .post()
+        .create("Post".to_string(), true, vec![])
+        .exec()
+        .await?;
+
+    let found = client
+        .post()
+        .find_first_or_throw(vec![post::published::equals(true)])
+        .with(post::author::fetch())
+        .exec()
+        .await?;
+    assert_eq!(found.title, "Post");
+    assert!(found.author().unwrap().is_none());
+
+    let error = client
+        .post()
+        .find_first_or_throw(vec![post::published::equals(false)])
+        .exec()
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        error,
+        QueryError::NotFound {
+            model: "Post",
+            operation: "findFirstOrThrow"
+        }
+    ));
+
+    cleanup(client).await
+}
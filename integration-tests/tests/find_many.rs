@@ -477,3 +477,58 @@ async fn select() -> TestResult {
 
     cleanup(client).await
 }
+
+#[tokio::test]
+async fn distinct() -> TestResult {
+    let client = client().await;
+
+    let user = client
+        .user()
+        .create("Brendan".to_string(), vec![])
+        .exec()
+        .await?;
+
+    for (title, published) in [("A", true), ("B", true), ("C", false)] {
+        client
+            .post()
+            .create(
+                title.to_string(),
+                published,
+                vec![post::author::connect(user::id::equals(user.id.clone()))],
+            )
+            .exec()
+            .await?;
+    }
+
+    let posts = client
+        .post()
+        .find_many(vec![])
+        .distinct(vec![post::ScalarField::Published])
+        .order_by(post::title::order(SortOrder::Asc))
+        .exec()
+        .await?;
+    assert_eq!(posts.len(), 2);
+    assert_eq!(posts[0].title, "A");
+    assert_eq!(posts[1].title, "C");
+
+    let post = client
+        .post()
+        .find_first(vec![post::published::equals(true)])
+        .distinct(vec![post::ScalarField::Published])
+        .order_by(post::title::order(SortOrder::Desc))
+        .exec()
+        .await?
+        .unwrap();
+    assert_eq!(post.title, "B");
+
+    let user = client
+        .user()
+        .find_unique(user::id::equals(user.id))
+        .with(user::posts::fetch(vec![]).distinct(vec![post::ScalarField::Published]))
+        .exec()
+        .await?
+        .unwrap();
+    assert_eq!(user.posts().unwrap().len(), 2);
+
+    cleanup(client).await
+}
@@ -1,3 +1,5 @@
+use prisma_client_rust::QueryError;
+
 use crate::{db::*, utils::*};
 
 #[tokio::test]
@@ -101,3 +103,46 @@ async fn no_match() -> TestResult {
 
     cleanup(client).await
 }
+
+#[tokio::test]
+async fn or_throw() -> TestResult {
+    let client = client().await;
+
+    let post = client
+        .post()
+        .create("My post title!".to_string(), false, vec![])
+        .exec()
+        .await?;
+
+    let found = client
+        .post()
+        .find_unique_or_throw(post::id::equals(post.id.clone()))
+        .exec()
+        .await?;
+    assert_eq!(found.id, post.id);
+
+    let selected = client
+        .post()
+        .find_unique_or_throw(post::id::equals(post.id.clone()))
+        .select(post::select!({ title }))
+        .exec()
+        .await?;
+    assert_eq!(selected.title, post.title);
+
+    let error = client
+        .post()
+        .find_unique_or_throw(post::id::equals("missing".to_string()))
+        .include(post::include!({ author }))
+        .exec()
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        error,
+        QueryError::NotFound {
+            model: "Post",
+            operation: "findUniqueOrThrow"
+        }
+    ));
+
+    cleanup(client).await
+}
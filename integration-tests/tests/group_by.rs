@@ -0,0 +1,161 @@
+use prisma_client_rust::and;
+
+use crate::db::*;
+use crate::utils::*;
+
+async fn create_posts(client: &PrismaClient) -> TestResult {
+    client
+        .post()
+        .create_many(vec![
+            post::create_unchecked("A".to_string(), true, vec![post::views::set(1)]),
+            post::create_unchecked("B".to_string(), true, vec![post::views::set(3)]),
+            post::create_unchecked("C".to_string(), false, vec![post::views::set(10)]),
+        ])
+        .exec()
+        .await?;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn basic() -> TestResult {
+    let client = client().await;
+
+    create_posts(&client).await?;
+
+    let groups = client
+        .post()
+        .group_by(vec![post::ScalarField::Published], vec![])
+        .aggregate(post::count_all())
+        .aggregate(post::views::sum())
+        .order_by(post::ScalarField::Published.order(SortOrder::Asc))
+        .exec()
+        .await?;
+
+    assert_eq!(groups.len(), 2);
+
+    assert_eq!(groups[0].published, Some(false));
+    assert_eq!(groups[0]._count.as_ref().unwrap()._all, Some(1));
+    assert_eq!(groups[0]._sum.as_ref().unwrap().views, Some(10));
+
+    assert_eq!(groups[1].published, Some(true));
+    assert_eq!(groups[1]._count.as_ref().unwrap()._all, Some(2));
+    assert_eq!(groups[1]._sum.as_ref().unwrap().views, Some(4));
+
+    assert_eq!(groups[1].title, None);
+
+    cleanup(client).await
+}
+
+#[tokio::test]
+async fn where_() -> TestResult {
+    let client = client().await;
+
+    create_posts(&client).await?;
+
+    let groups = client
+        .post()
+        .group_by(
+            vec![post::ScalarField::Published],
+            vec![post::views::gt(1)],
+        )
+        .aggregate(post::count_all())
+        .order_by(post::ScalarField::Published.order(SortOrder::Asc))
+        .exec()
+        .await?;
+
+    assert_eq!(groups.len(), 2);
+    assert_eq!(groups[1]._count.as_ref().unwrap()._all, Some(1));
+
+    cleanup(client).await
+}
+
+#[tokio::test]
+async fn having() -> TestResult {
+    let client = client().await;
+
+    create_posts(&client).await?;
+
+    let groups = client
+        .post()
+        .group_by(vec![post::ScalarField::Published], vec![])
+        .having(
+            post::views::avg().having(_prisma::read_filters::FloatFilter::Gt(5.0)),
+        )
+        .aggregate(post::views::avg())
+        .exec()
+        .await?;
+
+    assert_eq!(groups.len(), 1);
+    assert_eq!(groups[0].published, Some(false));
+    assert_eq!(groups[0]._avg.as_ref().unwrap().views, Some(10.0));
+
+    let groups = client
+        .post()
+        .group_by(vec![post::ScalarField::Published], vec![])
+        .having(and![
+            post::views::sum().having(_prisma::read_filters::IntFilter::Gte(4)),
+            post::published::equals(true).into()
+        ])
+        .exec()
+        .await?;
+
+    assert_eq!(groups.len(), 1);
+    assert_eq!(groups[0].published, Some(true));
+
+    cleanup(client).await
+}
+
+#[tokio::test]
+async fn order_by_aggregate() -> TestResult {
+    let client = client().await;
+
+    create_posts(&client).await?;
+
+    let groups = client
+        .post()
+        .group_by(vec![post::ScalarField::Title], vec![])
+        .order_by(post::views::max().order(SortOrder::Desc))
+        .take(2)
+        .exec()
+        .await?;
+
+    assert_eq!(groups.len(), 2);
+    assert_eq!(groups[0].title.as_deref(), Some("C"));
+    assert_eq!(groups[1].title.as_deref(), Some("B"));
+
+    cleanup(client).await
+}
+
+#[tokio::test]
+async fn count_all_order_and_having() -> TestResult {
+    let client = client().await;
+
+    create_posts(&client).await?;
+
+    let groups = client
+        .post()
+        .group_by(vec![post::ScalarField::Published], vec![])
+        .aggregate(post::count_all())
+        .order_by(post::count_all().order(SortOrder::Desc))
+        .exec()
+        .await?;
+
+    assert_eq!(groups.len(), 2);
+    assert_eq!(groups[0].published, Some(true));
+    assert_eq!(groups[1].published, Some(false));
+
+    let groups = client
+        .post()
+        .group_by(vec![post::ScalarField::Published], vec![])
+        .having(post::count_all().having(_prisma::read_filters::IntFilter::Gt(1)))
+        .aggregate(post::count_all())
+        .exec()
+        .await?;
+
+    assert_eq!(groups.len(), 1);
+    assert_eq!(groups[0].published, Some(true));
+    assert_eq!(groups[0]._count.as_ref().unwrap()._all, Some(2));
+
+    cleanup(client).await
+}
@@ -0,0 +1,290 @@
+use prisma_client_rust::{
+    prisma_errors::query_engine::UniqueKeyViolation, PrismaErrorKind, QueryError,
+};
+
+use crate::db::*;
+use crate::utils::*;
+
+#[tokio::test]
+async fn crud() -> TestResult {
+    let client = PrismaClient::_in_memory();
+
+    let user = client
+        .user()
+        .create(
+            "Brendan".to_string(),
+            vec![user::email::set(Some("brendan@example.com".to_string()))],
+        )
+        .exec()
+        .await?;
+
+    assert!(!user.id.is_empty());
+
+    let found = client
+        .user()
+        .find_unique(user::id::equals(user.id.clone()))
+        .exec()
+        .await?
+        .unwrap();
+
+    assert_eq!(found.name, "Brendan");
+
+    let updated = client
+        .user()
+        .update(
+            user::id::equals(user.id.clone()),
+            vec![user::name::set("Oscar".to_string())],
+        )
+        .exec()
+        .await?;
+
+    assert_eq!(updated.name, "Oscar");
+
+    let duplicate = client
+        .user()
+        .create(
+            "Brendan".to_string(),
+            vec![user::email::set(Some("brendan@example.com".to_string()))],
+        )
+        .exec()
+        .await;
+
+    let error = duplicate.unwrap_err();
+
+    assert!(error.is_prisma_error::<UniqueKeyViolation>());
+    assert_eq!(
+        error.kind(),
+        PrismaErrorKind::UniqueConstraintViolation {
+            model: None,
+            fields: vec!["email".to_string()],
+        }
+    );
+
+    client
+        .user()
+        .delete(user::id::equals(user.id.clone()))
+        .exec()
+        .await?;
+
+    assert_eq!(client.user().count(vec![]).exec().await?, 0);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn filters_and_pagination() -> TestResult {
+    let client = PrismaClient::_in_memory();
+
+    for (title, views) in [("Post 1", 10), ("Post 2", 30), ("Other", 20), ("Post 3", 0)] {
+        client
+            .post()
+            .create(title.to_string(), true, vec![post::views::set(views)])
+            .exec()
+            .await?;
+    }
+
+    let posts = client
+        .post()
+        .find_many(vec![
+            post::title::starts_with("Post".to_string()),
+            post::views::gt(0),
+        ])
+        .order_by(post::views::order(SortOrder::Desc))
+        .exec()
+        .await?;
+
+    assert_eq!(
+        posts.iter().map(|p| p.title.as_str()).collect::<Vec<_>>(),
+        vec!["Post 2", "Post 1"]
+    );
+
+    let posts = client
+        .post()
+        .find_many(vec![])
+        .order_by(post::views::order(SortOrder::Asc))
+        .skip(1)
+        .take(2)
+        .exec()
+        .await?;
+
+    assert_eq!(
+        posts.iter().map(|p| p.views).collect::<Vec<_>>(),
+        vec![10, 20]
+    );
+
+    let updated = client
+        .post()
+        .update_many(
+            vec![post::title::contains("Post".to_string())],
+            vec![post::views::increment(1)],
+        )
+        .exec()
+        .await?;
+
+    assert_eq!(updated, 3);
+
+    let post = client
+        .post()
+        .find_first(vec![post::title::equals("Post 3".to_string())])
+        .exec()
+        .await?
+        .unwrap();
+
+    assert_eq!(post.views, 1);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn relations() -> TestResult {
+    let client = PrismaClient::_in_memory();
+
+    let user = client
+        .user()
+        .create(
+            "Brendan".to_string(),
+            vec![
+                user::posts::create(vec![
+                    user::posts::Create {
+                        title: "Post 1".to_string(),
+                        published: true,
+                        _params: vec![],
+                    },
+                    user::posts::Create {
+                        title: "Post 2".to_string(),
+                        published: false,
+                        _params: vec![],
+                    },
+                ]),
+                user::profile::create(user::profile::Create {
+                    bio: "Bio".to_string(),
+                    country: "Australia".to_string(),
+                    _params: vec![],
+                }),
+            ],
+        )
+        .include(user::include!({
+            posts(vec![post::published::equals(true)])
+            profile
+        }))
+        .exec()
+        .await?;
+
+    assert_eq!(user.posts.len(), 1);
+    assert_eq!(user.profile.unwrap().bio, "Bio");
+
+    let category = client
+        .category()
+        .create(
+            "Category".to_string(),
+            vec![category::posts::connect(vec![post::title_author_id(
+                "Post 1".to_string(),
+                user.id.clone(),
+            )])],
+        )
+        .exec()
+        .await?;
+
+    let posts = client
+        .post()
+        .find_many(vec![post::categories::some(vec![category::id::equals(
+            category.id,
+        )])])
+        .with(post::author::fetch())
+        .exec()
+        .await?;
+
+    assert_eq!(posts.len(), 1);
+    assert_eq!(posts[0].author().unwrap().unwrap().name, "Brendan");
+
+    client.profile().delete_many(vec![]).exec().await?;
+
+    // Deleting the author disconnects their posts, since the relation is optional
+    client
+        .user()
+        .delete(user::id::equals(user.id.clone()))
+        .exec()
+        .await?;
+
+    let posts = client
+        .post()
+        .find_many(vec![post::author_id::equals(None)])
+        .exec()
+        .await?;
+
+    assert_eq!(posts.len(), 2);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn transaction_rolls_back() -> TestResult {
+    let client = PrismaClient::_in_memory();
+
+    let result: Result<(), QueryError> = client
+        ._transaction()
+        .run(|client| async move {
+            client
+                .user()
+                .create("Brendan".to_string(), vec![])
+                .exec()
+                .await?;
+
+            client
+                .user()
+                .update(user::id::equals("missing".to_string()), vec![])
+                .exec()
+                .await
+                .map(|_| ())
+        })
+        .await;
+
+    assert!(result.is_err());
+    assert_eq!(client.user().count(vec![]).exec().await?, 0);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn transaction_keeps_outside_writes() -> TestResult {
+    let client = PrismaClient::_in_memory();
+
+    let (tx, tx_client) = client._transaction().begin().await?;
+
+    tx_client
+        .user()
+        .create("Inside".to_string(), vec![])
+        .exec()
+        .await?;
+
+    client
+        .user()
+        .create("Outside".to_string(), vec![])
+        .exec()
+        .await?;
+
+    assert_eq!(client.user().count(vec![]).exec().await?, 1);
+
+    tx.commit(tx_client).await?;
+
+    assert_eq!(client.user().count(vec![]).exec().await?, 2);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn separate_clients() -> TestResult {
+    let first = PrismaClient::_in_memory();
+    let second = PrismaClient::_in_memory();
+
+    first
+        .user()
+        .create("Brendan".to_string(), vec![])
+        .exec()
+        .await?;
+
+    assert_eq!(first.user().count(vec![]).exec().await?, 1);
+    assert_eq!(second.user().count(vec![]).exec().await?, 0);
+
+    Ok(())
+}
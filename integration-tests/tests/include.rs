@@ -60,6 +60,34 @@ async fn many_relation_args() -> TestResult {
     cleanup(client).await
 }
 
+#[tokio::test]
+async fn relation_count() -> TestResult {
+    let client = client().await;
+
+    let user = client
+        .user()
+        .create(
+            "Brendan".to_string(),
+            vec![user::posts::create(vec![user::posts::Create {
+                title: "Post".to_string(),
+                published: true,
+                _params: vec![],
+            }])],
+        )
+        .include(user::include!({
+            posts: select { title }
+            _count { posts favourite_posts }
+        }))
+        .exec()
+        .await?;
+
+    assert_eq!(user.name, "Brendan");
+    assert_eq!(user.posts.len() as i64, user._count.posts);
+    assert_eq!(user._count.favourite_posts, 0);
+
+    cleanup(client).await
+}
+
 #[tokio::test]
 async fn arguments() -> TestResult {
     let client = client().await;
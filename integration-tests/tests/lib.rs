@@ -21,22 +21,32 @@ async fn aaaa_run_migrations() -> TestResult {
     cleanup(client).await
 }
 
+mod aggregate;
 mod batch;
 // mod callbacks;
+mod connection;
 mod count;
 mod create;
 mod create_many;
+mod debug;
 mod delete;
 mod delete_many;
+mod filters;
 mod find_first;
 mod find_many;
 mod find_unique;
+mod group_by;
+mod in_memory;
 mod include;
+mod middleware;
 mod mock;
+mod nested_writes;
 mod partial;
 mod raw;
+mod replicas;
 mod select;
 mod specta;
+mod stream;
 mod update;
 mod upsert;
 mod with;
@@ -0,0 +1,266 @@
+use std::sync::{Arc, Mutex};
+
+use futures::future::{BoxFuture, FutureExt};
+use prisma_client_rust::{
+    serde_value, MiddlewareRequest, MiddlewareResult, ModelOperation, ModelReadOperation,
+    ModelWriteOperation, Next, QueryError, QueryMiddleware,
+};
+
+use crate::{db::*, utils::*};
+
+#[derive(Debug, PartialEq)]
+struct Call {
+    model: Option<String>,
+    action: Option<ModelOperation>,
+    batch: bool,
+    transaction: bool,
+}
+
+#[derive(Clone, Default)]
+struct Recorder(Arc<Mutex<Vec<Call>>>);
+
+impl QueryMiddleware for Recorder {
+    fn handle<'a>(
+        &'a self,
+        request: MiddlewareRequest,
+        next: Next<'a>,
+    ) -> BoxFuture<'a, MiddlewareResult> {
+        self.0
+            .lock()
+            .unwrap()
+            .extend(request.queries.iter().map(|q| Call {
+                model: q.model().map(str::to_string),
+                action: q.action(),
+                batch: request.batch.is_some(),
+                transaction: request.tx_id().is_some(),
+            }));
+
+        next.run(request)
+    }
+}
+
+fn call(model: &str, action: ModelOperation, batch: bool, transaction: bool) -> Call {
+    Call {
+        model: Some(model.to_string()),
+        action: Some(action),
+        batch,
+        transaction,
+    }
+}
+
+#[tokio::test]
+async fn observe() -> TestResult {
+    let client = client().await;
+
+    let recorder = Recorder::default();
+    let middleware_client = PrismaClient::_builder()
+        .with_middleware(recorder.clone())
+        .build()
+        .await
+        .unwrap();
+
+    middleware_client.post().find_many(vec![]).exec().await?;
+
+    middleware_client
+        ._batch((
+            middleware_client
+                .user()
+                .create("Brendan".to_string(), vec![]),
+            middleware_client.user().count(vec![]),
+        ))
+        .await?;
+
+    middleware_client
+        ._transaction()
+        .run(|tx| async move {
+            tx.user()
+                .find_first(vec![])
+                .exec()
+                .await
+                .map(|user| user.unwrap().id)
+        })
+        .await?;
+
+    assert_eq!(
+        *recorder.0.lock().unwrap(),
+        vec![
+            call(
+                "Post",
+                ModelOperation::Read(ModelReadOperation::FindMany),
+                false,
+                false
+            ),
+            call(
+                "User",
+                ModelOperation::Write(ModelWriteOperation::Create),
+                true,
+                false
+            ),
+            call(
+                "User",
+                ModelOperation::Read(ModelReadOperation::Count),
+                true,
+                false
+            ),
+            call(
+                "User",
+                ModelOperation::Read(ModelReadOperation::FindFirst),
+                false,
+                true
+            ),
+        ]
+    );
+
+    cleanup(client).await
+}
+
+#[tokio::test]
+async fn observe_or_throw() -> TestResult {
+    let client = client().await;
+
+    let recorder = Recorder::default();
+    let middleware_client = PrismaClient::_builder()
+        .with_middleware(recorder.clone())
+        .build()
+        .await
+        .unwrap();
+
+    let user = middleware_client
+        .user()
+        .create("Brendan".to_string(), vec![])
+        .exec()
+        .await?;
+
+    middleware_client
+        .user()
+        .find_unique_or_throw(user::id::equals(user.id))
+        .exec()
+        .await?;
+
+    middleware_client
+        .user()
+        .find_first_or_throw(vec![])
+        .select(user::select!({ id }))
+        .exec()
+        .await?;
+
+    assert_eq!(
+        recorder.0.lock().unwrap()[1..],
+        [
+            call(
+                "User",
+                ModelOperation::Read(ModelReadOperation::FindUniqueOrThrow),
+                false,
+                false
+            ),
+            call(
+                "User",
+                ModelOperation::Read(ModelReadOperation::FindFirstOrThrow),
+                false,
+                false
+            ),
+        ]
+    );
+
+    cleanup(client).await
+}
+
+struct HidePosts;
+
+impl QueryMiddleware for HidePosts {
+    fn handle<'a>(
+        &'a self,
+        request: MiddlewareRequest,
+        next: Next<'a>,
+    ) -> BoxFuture<'a, MiddlewareResult> {
+        async move {
+            let find_many = Some(ModelOperation::Read(ModelReadOperation::FindMany));
+
+            if request
+                .queries
+                .iter()
+                .all(|q| q.model() == Some("Post") && q.action() == find_many)
+            {
+                return Ok(request
+                    .queries
+                    .iter()
+                    .map(|_| Ok(serde_value::Value::Seq(vec![])))
+                    .collect());
+            }
+
+            next.run(request).await
+        }
+        .boxed()
+    }
+}
+
+#[tokio::test]
+async fn short_circuit() -> TestResult {
+    let client = client().await;
+
+    let middleware_client = PrismaClient::_builder()
+        .with_middleware(HidePosts)
+        .build()
+        .await
+        .unwrap();
+
+    middleware_client
+        .post()
+        .create("Post".to_string(), true, vec![])
+        .exec()
+        .await?;
+
+    assert!(middleware_client
+        .post()
+        .find_many(vec![])
+        .exec()
+        .await?
+        .is_empty());
+    assert_eq!(client.post().find_many(vec![]).exec().await?.len(), 1);
+
+    cleanup(client).await
+}
+
+struct ReadOnly;
+
+impl QueryMiddleware for ReadOnly {
+    fn handle<'a>(
+        &'a self,
+        request: MiddlewareRequest,
+        next: Next<'a>,
+    ) -> BoxFuture<'a, MiddlewareResult> {
+        if request
+            .queries
+            .iter()
+            .any(|q| matches!(q.action(), Some(ModelOperation::Write(_))))
+        {
+            return async { Err(QueryError::Middleware("Client is read only".to_string())) }
+                .boxed();
+        }
+
+        next.run(request)
+    }
+}
+
+#[tokio::test]
+async fn reject() -> TestResult {
+    let client = client().await;
+
+    let middleware_client = PrismaClient::_builder()
+        .with_middleware(Recorder::default())
+        .with_middleware(ReadOnly)
+        .build()
+        .await
+        .unwrap();
+
+    let result = middleware_client
+        .user()
+        .create("Brendan".to_string(), vec![])
+        .exec()
+        .await;
+
+    assert!(matches!(result, Err(QueryError::Middleware(_))));
+    assert_eq!(client.user().count(vec![]).exec().await?, 0);
+
+    cleanup(client).await
+}
@@ -1,3 +1,5 @@
+use prisma_client_rust::{BatchMode, QueryError};
+
 use crate::db::*;
 use crate::utils::*;
 
@@ -78,4 +80,126 @@ async fn delete_many() -> TestResult {
     Ok(())
 }
 
-// TODO: Errors
+#[tokio::test]
+async fn errors() -> TestResult {
+    let (client, mock) = PrismaClient::_mock();
+
+    let query = || client.user().count(vec![]);
+
+    mock.expect_error(query(), || QueryError::Mock("Connection lost".to_string()))
+        .await;
+
+    let result = query().exec().await;
+
+    assert!(matches!(result, Err(QueryError::Mock(_))));
+
+    mock.verify();
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn times() -> TestResult {
+    let (client, mock) = PrismaClient::_mock();
+
+    let query = || client.user().count(vec![]);
+
+    mock.expect(query(), 1).await.times(2);
+    mock.expect(query(), 2).await.times(1);
+
+    assert_eq!(query().exec().await?, 1);
+    assert_eq!(query().exec().await?, 1);
+    assert_eq!(query().exec().await?, 2);
+    assert!(query().exec().await.is_err());
+
+    assert_eq!(mock.call_count(query()), 4);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn sequence() -> TestResult {
+    let (client, mock) = PrismaClient::_mock();
+
+    let delete = || client.user().delete_many(vec![]);
+    let count = || client.user().count(vec![]);
+
+    let sequence = mock.sequence();
+
+    mock.expect(delete(), 2)
+        .await
+        .times(1)
+        .in_sequence(&sequence);
+    mock.expect(count(), 0)
+        .await
+        .times(1)
+        .in_sequence(&sequence);
+
+    assert!(count().exec().await.is_err());
+
+    assert_eq!(delete().exec().await?, 2);
+    assert_eq!(count().exec().await?, 0);
+
+    Ok(())
+}
+
+#[tokio::test]
+#[should_panic(expected = "Unexpected query")]
+async fn verify_unexpected() {
+    let (client, mock) = PrismaClient::_mock();
+
+    client.user().count(vec![]).exec().await.ok();
+
+    mock.verify();
+}
+
+#[tokio::test]
+#[should_panic(expected = "Unmet expectation")]
+async fn verify_unmet() {
+    let (client, mock) = PrismaClient::_mock();
+
+    mock.expect(client.user().count(vec![]), 1).await.times(2);
+
+    client.user().count(vec![]).exec().await.ok();
+
+    mock.verify();
+}
+
+#[tokio::test]
+async fn records_batches_and_transactions() -> TestResult {
+    let (client, mock) = PrismaClient::_mock();
+
+    let query = || client.user().count(vec![]);
+
+    mock.expect(query(), 1).await.any_times();
+    mock.expect_error(client.user().delete_many(vec![]), || {
+        QueryError::Mock("Not allowed".to_string())
+    })
+    .await;
+
+    let (count, deleted) = client
+        ._batch_settled(
+            (query(), client.user().delete_many(vec![])),
+            BatchMode::Independent,
+        )
+        .await?;
+
+    assert_eq!(count?, 1);
+    assert!(deleted.is_err());
+
+    client
+        ._transaction()
+        .run(|tx| async move { tx.user().count(vec![]).exec().await })
+        .await?;
+
+    let calls = mock.calls();
+
+    assert_eq!(calls.len(), 3);
+    assert!(calls[0].batch && calls[0].tx_id.is_none());
+    assert!(calls[1].batch && calls[1].tx_id.is_none());
+    assert!(!calls[2].batch && calls[2].tx_id.is_some());
+
+    mock.verify();
+
+    Ok(())
+}
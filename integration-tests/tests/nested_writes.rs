@@ -0,0 +1,151 @@
+use crate::{db::*, utils::*};
+
+#[tokio::test]
+async fn create() -> TestResult {
+    let client = client().await;
+
+    let user = client
+        .user()
+        .create(
+            "Brendan".to_string(),
+            vec![
+                user::posts::create(vec![
+                    user::posts::Create {
+                        title: "Post 1".to_string(),
+                        published: true,
+                        _params: vec![],
+                    },
+                    user::posts::Create {
+                        title: "Post 2".to_string(),
+                        published: false,
+                        _params: vec![post::views::set(5)],
+                    },
+                ]),
+                user::profile::create(user::profile::Create {
+                    bio: "Bio".to_string(),
+                    country: "Australia".to_string(),
+                    _params: vec![],
+                }),
+            ],
+        )
+        .include(user::include!({ posts profile }))
+        .exec()
+        .await?;
+
+    assert_eq!(user.posts.len(), 2);
+    assert!(user.posts.iter().any(|p| p.title == "Post 2" && p.views == 5));
+    assert_eq!(user.profile.unwrap().bio, "Bio");
+
+    cleanup(client).await
+}
+
+#[tokio::test]
+async fn connect_or_create() -> TestResult {
+    let client = client().await;
+
+    let user = client
+        .user()
+        .create("Brendan".to_string(), vec![])
+        .exec()
+        .await?;
+
+    let post = client
+        .post()
+        .create(
+            "Post".to_string(),
+            true,
+            vec![post::author::connect_or_create(
+                user::id::equals(user.id.clone()),
+                post::author::Create {
+                    name: "Oscar".to_string(),
+                    _params: vec![],
+                },
+            )],
+        )
+        .exec()
+        .await?;
+
+    assert_eq!(post.author_id, Some(user.id));
+    assert_eq!(client.user().count(vec![]).exec().await?, 1);
+
+    cleanup(client).await
+}
+
+#[tokio::test]
+async fn update() -> TestResult {
+    let client = client().await;
+
+    let user = client
+        .user()
+        .create(
+            "Brendan".to_string(),
+            vec![user::posts::create(vec![
+                user::posts::Create {
+                    title: "Post 1".to_string(),
+                    published: false,
+                    _params: vec![],
+                },
+                user::posts::Create {
+                    title: "Post 2".to_string(),
+                    published: false,
+                    _params: vec![],
+                },
+            ])],
+        )
+        .exec()
+        .await?;
+
+    let user = client
+        .user()
+        .update(
+            user::id::equals(user.id),
+            vec![
+                user::posts::update_many(
+                    vec![post::title::equals("Post 1".to_string())],
+                    vec![post::published::set(true)],
+                ),
+                user::posts::delete_many(vec![post::title::equals("Post 2".to_string())]),
+                user::profile::upsert(
+                    user::profile::Create {
+                        bio: "Bio".to_string(),
+                        country: "Australia".to_string(),
+                        _params: vec![],
+                    },
+                    vec![profile::bio::set("Updated".to_string())],
+                ),
+            ],
+        )
+        .include(user::include!({ posts profile }))
+        .exec()
+        .await?;
+
+    assert_eq!(user.posts.len(), 1);
+    assert!(user.posts[0].published);
+    assert_eq!(user.profile.unwrap().bio, "Bio");
+
+    let user = client
+        .user()
+        .update(
+            user::id::equals(user.id),
+            vec![user::profile::update(vec![profile::bio::set(
+                "Updated".to_string(),
+            )])],
+        )
+        .include(user::include!({ profile }))
+        .exec()
+        .await?;
+
+    assert_eq!(user.profile.unwrap().bio, "Updated");
+
+    let user = client
+        .user()
+        .update(user::id::equals(user.id), vec![user::profile::delete()])
+        .include(user::include!({ profile }))
+        .exec()
+        .await?;
+
+    assert!(user.profile.is_none());
+    assert_eq!(client.profile().count(vec![]).exec().await?, 0);
+
+    cleanup(client).await
+}
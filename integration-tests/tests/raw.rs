@@ -1,4 +1,4 @@
-use prisma_client_rust::{prisma_models::PrismaValue, raw};
+use prisma_client_rust::{prisma_models::PrismaValue, raw, raw_typed, Query, QueryError};
 
 use crate::{db::*, utils::*};
 
@@ -93,4 +93,178 @@ async fn execute_raw_no_result() -> TestResult {
     cleanup(client).await
 }
 
+raw_typed!(
+    struct PostWithAuthor {
+        #[flatten]
+        post: post::Data,
+        author_name: Option<String>,
+    }
+);
+
+raw_typed!(
+    struct AuthorWithTitle {
+        #[flatten]
+        author: Option<user::Data>,
+        title: String,
+    }
+);
+
+#[tokio::test]
+async fn raw_typed_row() -> TestResult {
+    let client = client().await;
+
+    let user = client
+        .user()
+        .create("Brendan".to_string(), vec![])
+        .exec()
+        .await?;
+
+    let post = client
+        .post()
+        .create(
+            "My post title!".to_string(),
+            false,
+            vec![post::author::connect(user::id::equals(user.id.clone()))],
+        )
+        .exec()
+        .await?;
+
+    let result: Vec<PostWithAuthor> = client
+        ._query_raw(raw_typed!(
+            "SELECT Post.*, User.name AS author_name FROM Post LEFT JOIN User ON User.id = Post.author_id WHERE Post.id = {}",
+            PrismaValue::String(post.id.clone())
+        ))
+        .exec()
+        .await?;
+
+    assert_eq!(result.len(), 1);
+    assert_eq!(&result[0].post.id, &post.id);
+    assert_eq!(&result[0].post.title, "My post title!");
+    assert_eq!(result[0].author_name.as_deref(), Some("Brendan"));
+
+    client
+        .post()
+        .create("No author".to_string(), false, vec![])
+        .exec()
+        .await?;
+
+    let result: Vec<AuthorWithTitle> = client
+        ._query_raw(raw_typed!(
+            "SELECT User.*, Post.title AS title FROM Post LEFT JOIN User ON User.id = Post.author_id ORDER BY Post.title"
+        ))
+        .exec()
+        .await?;
+
+    assert_eq!(result.len(), 2);
+    assert_eq!(result[0].title, "My post title!");
+    assert_eq!(result[0].author.as_ref().map(|a| &a.id), Some(&user.id));
+    assert_eq!(result[1].title, "No author");
+    assert!(result[1].author.is_none());
+
+    let count = client
+        ._execute_raw(raw_typed!("DELETE FROM Post"))
+        .exec()
+        .await?;
+    assert_eq!(count, 2);
+
+    cleanup(client).await
+}
+
+#[tokio::test]
+async fn query_raw_values() -> TestResult {
+    let client = client().await;
+
+    #[derive(serde::Deserialize)]
+    struct Row {
+        float: f64,
+        string: String,
+        nothing: Option<i32>,
+    }
+
+    let result: Vec<Row> = client
+        ._query_raw(raw!(
+            "SELECT 1.5 AS float, {} AS string, NULL AS nothing",
+            PrismaValue::String("text".to_string())
+        ))
+        .exec()
+        .await?;
+
+    assert_eq!(result.len(), 1);
+    assert_eq!(result[0].float, 1.5);
+    assert_eq!(&result[0].string, "text");
+    assert_eq!(result[0].nothing, None);
+
+    cleanup(client).await
+}
+
+#[tokio::test]
+async fn raw_placeholders() -> TestResult {
+    let query = || {
+        raw!(
+            "SELECT * FROM Post WHERE id = {} AND views > {}",
+            PrismaValue::String("1".to_string()),
+            PrismaValue::Int(5)
+        )
+    };
+
+    let (sql, params) = query().convert("sqlserver")?;
+    assert_eq!(sql, "SELECT * FROM Post WHERE id = @P1 AND views > @P2");
+    assert_eq!(params.len(), 2);
+
+    let (sql, _) = query().convert("postgresql")?;
+    assert_eq!(sql, "SELECT * FROM Post WHERE id = $1 AND views > $2");
+
+    let (sql, _) = query().convert("sqlite")?;
+    assert_eq!(sql, "SELECT * FROM Post WHERE id = ? AND views > ?");
+
+    assert!(matches!(
+        query().convert("mongodb"),
+        Err(QueryError::UnsupportedRawQuery(provider)) if provider == "mongodb"
+    ));
+    assert!(matches!(
+        raw!("SELECT 1").convert("mongodb"),
+        Err(QueryError::UnsupportedRawQuery(provider)) if provider == "mongodb"
+    ));
+
+    let (sql, params) = raw!("SELECT 1").convert("sqlserver")?;
+    assert_eq!(sql, "SELECT 1");
+    assert!(params.is_empty());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn raw_missing_parameter() -> TestResult {
+    let client = client().await;
+
+    let result = client
+        ._execute_raw(raw!("DELETE FROM Post WHERE id = {}"))
+        .exec()
+        .await;
+
+    assert!(matches!(
+        result,
+        Err(QueryError::RawQueryParameters {
+            placeholders: 1,
+            parameters: 0
+        })
+    ));
+
+    let result = client
+        ._batch(vec![
+            client._execute_raw(raw!("DELETE FROM Post WHERE id = {}"))
+        ])
+        .await;
+
+    assert!(matches!(result, Err(QueryError::RawQueryParameters { .. })));
+
+    let result = client
+        ._query_raw::<post::Data>(raw!("SELECT * FROM Post WHERE id = {}"))
+        .to_query_string();
+
+    assert!(matches!(result, Err(QueryError::RawQueryParameters { .. })));
+
+    cleanup(client).await
+}
+
 // query_first?
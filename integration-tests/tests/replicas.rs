@@ -0,0 +1,94 @@
+use crate::db::*;
+use crate::utils::*;
+
+async fn replica_client(replicas: Vec<&str>) -> PrismaClient {
+    PrismaClient::_builder()
+        .with_read_replicas(replicas.into_iter().map(ToString::to_string).collect())
+        .build()
+        .await
+        .unwrap()
+}
+
+const REPLICA_URL: &str = "file:replica.db";
+
+/// Creates a replica database separate from the primary, containing only a user named "Replica"
+async fn setup_replica() -> PrismaClient {
+    let replica = PrismaClient::_builder()
+        .with_url(REPLICA_URL.to_string())
+        .build()
+        .await
+        .unwrap();
+
+    replica._db_push().accept_data_loss().await.unwrap();
+
+    replica.user().delete_many(vec![]).exec().await.unwrap();
+    replica
+        .user()
+        .create("Replica".to_string(), vec![])
+        .exec()
+        .await
+        .unwrap();
+
+    replica
+}
+
+#[tokio::test]
+async fn reads_and_writes() -> TestResult {
+    let replica = setup_replica().await;
+    let client = replica_client(vec![REPLICA_URL]).await;
+
+    let user = client
+        .user()
+        .create("Brendan".to_string(), vec![])
+        .exec()
+        .await?;
+
+    // Writes go to the primary, so the replica doesn't see the new user
+    let replica_user = client
+        .user()
+        .find_unique(user::id::equals(user.id.clone()))
+        .exec()
+        .await?;
+
+    assert!(replica_user.is_none());
+
+    // Reads go to the replica, which only contains its own user
+    let replica_users = client.user().find_many(vec![]).exec().await?;
+
+    assert_eq!(replica_users.len(), 1);
+    assert_eq!(replica_users[0].name, "Replica");
+
+    // _primary bypasses the replica
+    let primary_user = client
+        ._primary()
+        .user()
+        .find_unique(user::id::equals(user.id.clone()))
+        .exec()
+        .await?;
+
+    assert_eq!(primary_user.unwrap().name, "Brendan");
+
+    let primary_users = client._primary().user().find_many(vec![]).exec().await?;
+
+    assert!(primary_users.iter().all(|u| u.name != "Replica"));
+
+    replica.user().delete_many(vec![]).exec().await?;
+
+    cleanup(client).await
+}
+
+#[tokio::test]
+async fn unreachable_replica() -> TestResult {
+    let client = replica_client(vec!["file:./missing/directory/dev.db"]).await;
+
+    client
+        .user()
+        .create("Brendan".to_string(), vec![])
+        .exec()
+        .await?;
+
+    // Reads fall back to the primary
+    assert_eq!(client.user().count(vec![]).exec().await?, 1);
+
+    cleanup(client).await
+}
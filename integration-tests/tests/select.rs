@@ -83,6 +83,60 @@ async fn many_relation_args() -> TestResult {
     cleanup(client).await
 }
 
+#[tokio::test]
+async fn relation_count() -> TestResult {
+    let client = client().await;
+
+    let user = client
+        .user()
+        .create(
+            "Brendan".to_string(),
+            vec![user::posts::create(vec![
+                user::posts::Create {
+                    title: "Post 1".to_string(),
+                    published: true,
+                    _params: vec![],
+                },
+                user::posts::Create {
+                    title: "Post 2".to_string(),
+                    published: false,
+                    _params: vec![],
+                },
+            ])],
+        )
+        .select(user::select!({
+            id
+            _count {
+                posts
+                favourite_posts(vec![post::published::equals(true)])
+            }
+        }))
+        .exec()
+        .await?;
+
+    assert_eq!(user._count.posts, 2);
+    assert_eq!(user._count.favourite_posts, 0);
+
+    let user = client
+        .user()
+        .find_unique(user::id::equals(user.id))
+        .select(user::select!({
+            posts: select {
+                id
+                _count { categories }
+            }
+            _count { posts(vec![post::published::equals(true)]) }
+        }))
+        .exec()
+        .await?
+        .unwrap();
+
+    assert_eq!(user._count.posts, 1);
+    assert!(user.posts.iter().all(|p| p._count.categories == 0));
+
+    cleanup(client).await
+}
+
 #[tokio::test]
 async fn arguments() -> TestResult {
     let client = client().await;
@@ -0,0 +1,197 @@
+use futures::TryStreamExt;
+use prisma_client_rust::QueryError;
+
+use crate::{db::*, utils::*};
+
+async fn create_posts(client: &PrismaClient, count: usize) -> Result<(), QueryError> {
+    for i in 0..count {
+        client
+            .post()
+            .create(format!("Post {i}"), i % 2 == 0, vec![])
+            .exec()
+            .await?;
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn all_records() -> TestResult {
+    let client = client().await;
+
+    create_posts(&client, 5).await?;
+
+    let posts: Vec<post::Data> = client
+        .post()
+        .find_many(vec![])
+        .stream(2)
+        .try_collect()
+        .await?;
+    assert_eq!(posts.len(), 5);
+
+    let mut ids = posts.iter().map(|p| p.id.clone()).collect::<Vec<_>>();
+    ids.sort();
+    ids.dedup();
+    assert_eq!(ids.len(), 5);
+
+    cleanup(client).await
+}
+
+#[tokio::test]
+async fn where_and_take() -> TestResult {
+    let client = client().await;
+
+    create_posts(&client, 7).await?;
+
+    let posts: Vec<post::Data> = client
+        .post()
+        .find_many(vec![post::published::equals(true)])
+        .stream(3)
+        .try_collect()
+        .await?;
+    assert_eq!(posts.len(), 4);
+    assert!(posts.iter().all(|p| p.published));
+
+    let posts: Vec<post::Data> = client
+        .post()
+        .find_many(vec![])
+        .take(5)
+        .stream(2)
+        .try_collect()
+        .await?;
+    assert_eq!(posts.len(), 5);
+
+    cleanup(client).await
+}
+
+#[tokio::test]
+async fn select() -> TestResult {
+    let client = client().await;
+
+    create_posts(&client, 3).await?;
+
+    let titles: Vec<_> = client
+        .post()
+        .find_many(vec![])
+        .order_by(post::title::order(SortOrder::Desc))
+        .stream_select(post::select!({ title }), 2)
+        .map_ok(|post| post.title)
+        .try_collect()
+        .await?;
+    assert_eq!(titles, vec!["Post 2", "Post 1", "Post 0"]);
+
+    cleanup(client).await
+}
+
+#[tokio::test]
+async fn transaction() -> TestResult {
+    let client = client().await;
+
+    let count = client
+        ._transaction()
+        .run(|client| async move {
+            create_posts(&client, 3).await?;
+
+            client
+                .post()
+                .find_many(vec![])
+                .stream(1)
+                .try_fold(0, |count, _| async move { Ok(count + 1) })
+                .await
+        })
+        .await?;
+    assert_eq!(count, 3);
+
+    cleanup(client).await
+}
+
+#[tokio::test]
+async fn compound_primary_key() -> TestResult {
+    let client = client().await;
+
+    let user = client
+        .user()
+        .create("Brendan".to_string(), vec![])
+        .exec()
+        .await?;
+
+    for local_id in 0..5 {
+        client
+            .file_path()
+            .create_unchecked(local_id, format!("/{local_id}"), user.id.clone(), vec![])
+            .exec()
+            .await?;
+    }
+
+    let file_paths: Vec<file_path::Data> = client
+        .file_path()
+        .find_many(vec![])
+        .stream(2)
+        .try_collect()
+        .await?;
+
+    assert_eq!(
+        file_paths.iter().map(|f| f.local_id).collect::<Vec<_>>(),
+        vec![0, 1, 2, 3, 4]
+    );
+
+    cleanup(client).await
+}
+
+#[tokio::test]
+async fn invalid_batch_size() -> TestResult {
+    let client = client().await;
+
+    create_posts(&client, 1).await?;
+
+    for batch_size in [0, -1] {
+        let result: Result<Vec<post::Data>, _> = client
+            .post()
+            .find_many(vec![])
+            .stream(batch_size)
+            .try_collect()
+            .await;
+
+        assert!(matches!(result, Err(QueryError::Serialize(_))));
+    }
+
+    cleanup(client).await
+}
+
+#[tokio::test]
+async fn negative_take() -> TestResult {
+    let client = client().await;
+
+    create_posts(&client, 1).await?;
+
+    let result: Result<Vec<post::Data>, _> = client
+        .post()
+        .find_many(vec![])
+        .take(-1)
+        .stream(10)
+        .try_collect()
+        .await;
+
+    assert!(matches!(result, Err(QueryError::Serialize(_))));
+
+    cleanup(client).await
+}
+
+#[tokio::test]
+async fn distinct() -> TestResult {
+    let client = client().await;
+
+    create_posts(&client, 1).await?;
+
+    let result: Result<Vec<post::Data>, _> = client
+        .post()
+        .find_many(vec![])
+        .distinct(vec![post::ScalarField::Published])
+        .stream(10)
+        .try_collect()
+        .await;
+
+    assert!(matches!(result, Err(QueryError::Serialize(_))));
+
+    cleanup(client).await
+}
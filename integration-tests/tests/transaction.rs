@@ -1,6 +1,11 @@
-use std::time::Duration;
+use std::{
+    sync::atomic::{AtomicUsize, Ordering},
+    time::Duration,
+};
 
-use prisma_client_rust::QueryError;
+use prisma_client_rust::{
+    prisma_errors::query_engine::RecordRequiredButNotFound, QueryError, RetryPolicy,
+};
 
 use crate::db::*;
 use crate::utils::*;
@@ -166,6 +171,231 @@ async fn batch() -> TestResult {
     cleanup(client).await
 }
 
+#[tokio::test]
+async fn batch_rolls_back() -> TestResult {
+    let client = client().await;
+
+    let result: Result<_, QueryError> = client
+        ._transaction()
+        .run(|client| async move {
+            client
+                ._batch(vec![
+                    client.user().create("brendan".to_string(), vec![]),
+                    client.user().create("oscar".to_string(), vec![]),
+                ])
+                .await?;
+
+            client
+                .user()
+                .create(
+                    "brendan".to_string(),
+                    vec![user::id::set("abc".to_string())],
+                )
+                .exec()
+                .await?;
+
+            client
+                .user()
+                .create("oscar".to_string(), vec![user::id::set("abc".to_string())])
+                .exec()
+                .await
+        })
+        .await;
+
+    assert!(result.is_err());
+    assert_eq!(client.user().count(vec![]).exec().await?, 0);
+
+    cleanup(client).await
+}
+
+// Nested
+
+#[tokio::test]
+async fn nested_commits() -> TestResult {
+    let client = client().await;
+
+    client
+        ._transaction()
+        .run(|client| async move {
+            client
+                .user()
+                .create("brendan".to_string(), vec![])
+                .exec()
+                .await?;
+
+            client
+                ._transaction()
+                .run(|client| async move {
+                    client
+                        .user()
+                        .create("oscar".to_string(), vec![])
+                        .exec()
+                        .await
+                })
+                .await
+        })
+        .await?;
+
+    assert_eq!(client.user().count(vec![]).exec().await?, 2);
+
+    cleanup(client).await
+}
+
+#[tokio::test]
+async fn nested_rolls_back() -> TestResult {
+    let client = client().await;
+
+    client
+        ._transaction()
+        .run(|client| async move {
+            client
+                .user()
+                .create("brendan".to_string(), vec![])
+                .exec()
+                .await?;
+
+            // Only the nested transaction's changes are rolled back
+            let nested: Result<_, QueryError> = client
+                ._transaction()
+                .run(|client| async move {
+                    client
+                        .user()
+                        .create("oscar".to_string(), vec![])
+                        .exec()
+                        .await?;
+
+                    client
+                        .user()
+                        .update(user::id::equals("".to_string()), vec![])
+                        .exec()
+                        .await
+                })
+                .await;
+
+            assert!(nested.is_err());
+
+            Ok::<_, QueryError>(())
+        })
+        .await?;
+
+    let users = client.user().find_many(vec![]).exec().await?;
+
+    assert_eq!(users.len(), 1);
+    assert_eq!(users[0].name, "brendan");
+
+    cleanup(client).await
+}
+
+// Retries
+
+fn retry_not_found(max_attempts: u32) -> RetryPolicy {
+    RetryPolicy::new(max_attempts)
+        .with_backoff(Duration::ZERO, Duration::ZERO)
+        .with_retryable(|e| e.is_prisma_error::<RecordRequiredButNotFound>())
+}
+
+#[tokio::test]
+async fn retries_until_success() -> TestResult {
+    let client = client().await;
+
+    let attempts = AtomicUsize::new(0);
+
+    let (user, attempts_made) = client
+        ._transaction()
+        .with_retries(retry_not_found(3))
+        .run(|client| {
+            let attempt = attempts.fetch_add(1, Ordering::SeqCst);
+
+            async move {
+                let user = client
+                    .user()
+                    .create("brendan".to_string(), vec![])
+                    .exec()
+                    .await?;
+
+                let id = match attempt {
+                    0 | 1 => "".to_string(),
+                    _ => user.id,
+                };
+
+                client
+                    .user()
+                    .update(user::id::equals(id), vec![])
+                    .exec()
+                    .await
+            }
+        })
+        .await?;
+
+    assert_eq!(attempts.load(Ordering::SeqCst), 3);
+    assert_eq!(attempts_made, 3);
+    assert_eq!(user.name, "brendan");
+
+    // Failed attempts are rolled back
+    assert_eq!(client.user().count(vec![]).exec().await?, 1);
+
+    cleanup(client).await
+}
+
+#[tokio::test]
+async fn retries_exhausted() -> TestResult {
+    let client = client().await;
+
+    let attempts = AtomicUsize::new(0);
+
+    let result: Result<_, QueryError> = client
+        ._transaction()
+        .with_retries(retry_not_found(2))
+        .run(|client| {
+            attempts.fetch_add(1, Ordering::SeqCst);
+
+            async move {
+                client
+                    .user()
+                    .update(user::id::equals("".to_string()), vec![])
+                    .exec()
+                    .await
+            }
+        })
+        .await;
+
+    assert!(result.is_err());
+    assert_eq!(attempts.load(Ordering::SeqCst), 2);
+
+    cleanup(client).await
+}
+
+#[tokio::test]
+async fn not_retryable() -> TestResult {
+    let client = client().await;
+
+    let attempts = AtomicUsize::new(0);
+
+    let result: Result<_, QueryError> = client
+        ._transaction()
+        .with_retries(RetryPolicy::default())
+        .run(|client| {
+            attempts.fetch_add(1, Ordering::SeqCst);
+
+            async move {
+                client
+                    .user()
+                    .update(user::id::equals("".to_string()), vec![])
+                    .exec()
+                    .await
+            }
+        })
+        .await;
+
+    assert!(matches!(
+        result,
+        Err(e) if e.is_prisma_error::<RecordRequiredButNotFound>()
+    ));
+    assert_eq!(attempts.load(Ordering::SeqCst), 1);
+
+    cleanup(client).await
+}
+
 // Imperative
 
 #[tokio::test]
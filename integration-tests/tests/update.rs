@@ -1,5 +1,5 @@
 use prisma_client_rust::{
-    prisma_errors::query_engine::RecordRequiredButNotFound, queries::QueryError,
+    prisma_errors::query_engine::RecordRequiredButNotFound, queries::QueryError, PrismaErrorKind,
 };
 
 use crate::{db::*, utils::*};
@@ -209,6 +209,28 @@ async fn record_not_found() -> TestResult {
     cleanup(client).await
 }
 
+#[tokio::test]
+async fn record_not_found_kind() -> TestResult {
+    let client = client().await;
+
+    let error = client
+        .post()
+        .update(
+            post::id::equals("wow".to_string()),
+            vec![post::title::set("My post".to_string())],
+        )
+        .exec()
+        .await
+        .unwrap_err();
+
+    assert!(matches!(
+        error.kind(),
+        PrismaErrorKind::RecordNotFound { .. }
+    ));
+
+    cleanup(client).await
+}
+
 #[tokio::test]
 async fn set_none() -> TestResult {
     let client = client().await;
@@ -300,3 +300,57 @@ async fn nested_with() -> TestResult {
 
 //     cleanup(client).await
 // }
+
+#[tokio::test]
+async fn relation_count() -> TestResult {
+    let client = client().await;
+
+    let user_id = setup(&client).await?;
+
+    let user = client
+        .user()
+        .find_unique(user::id::equals(user_id.clone()))
+        .with(user::_count::fetch(vec![
+            user::_count::posts(vec![]),
+            user::_count::favourite_posts(vec![]),
+        ]))
+        .exec()
+        .await?
+        .unwrap();
+
+    assert!(user.posts().is_err());
+
+    let count = user._count().unwrap();
+    assert_eq!(count.posts, Some(4));
+    assert_eq!(count.favourite_posts, Some(0));
+
+    let user = client
+        .user()
+        .find_unique(user::id::equals(user_id.clone()))
+        .with(user::_count::fetch(vec![user::_count::posts(vec![
+            post::published::equals(true),
+        ])]))
+        .exec()
+        .await?
+        .unwrap();
+
+    let count = user._count().unwrap();
+    assert_eq!(count.posts, Some(2));
+    assert_eq!(count.favourite_posts, None);
+
+    let user = client
+        .user()
+        .find_unique(user::id::equals(user_id.clone()))
+        .with(user::_count::fetch(vec![user::_count::posts(vec![
+            post::title::starts_with("post".to_string()),
+            post::title::ends_with("3".to_string()),
+        ])]))
+        .exec()
+        .await?
+        .unwrap();
+
+    let count = user._count().unwrap();
+    assert_eq!(count.posts, Some(1));
+
+    cleanup(client).await
+}